@@ -0,0 +1,4 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE questions DROP COLUMN archived_at;
+ALTER TABLE games DROP COLUMN archived_at;
+ALTER TABLE game_boards DROP COLUMN archived_at;
@@ -0,0 +1,4 @@
+-- Soft-delete support. A NULL archived_at means the row is live.
+ALTER TABLE game_boards ADD COLUMN archived_at TIMESTAMPTZ;
+ALTER TABLE games ADD COLUMN archived_at TIMESTAMPTZ;
+ALTER TABLE questions ADD COLUMN archived_at TIMESTAMPTZ;
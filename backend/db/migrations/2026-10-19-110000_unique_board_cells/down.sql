@@ -0,0 +1,12 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE game_board_question_mappings DROP CONSTRAINT unique_board_cell;
+
+-- Put displaced cells back where their board and question still exist
+INSERT INTO game_board_question_mappings
+    (board_id, question_id, daily_double, points, grid_row, grid_col)
+SELECT d.board_id, d.question_id, d.daily_double, d.points, d.grid_row, d.grid_col
+FROM displaced_board_cells d
+JOIN game_boards b ON b.id = d.board_id
+JOIN questions q ON q.id = d.question_id
+ON CONFLICT (board_id, question_id) DO NOTHING;
+DROP TABLE displaced_board_cells;
@@ -0,0 +1,25 @@
+-- One question per grid cell.
+-- Earlier versions allowed collisions. In each occupied cell the mapping
+-- with the lowest question id stays on the board; the others are moved to
+-- displaced_board_cells (the questions stay in the library), so they can be
+-- put back by hand.
+CREATE TABLE displaced_board_cells (LIKE game_board_question_mappings);
+ALTER TABLE displaced_board_cells
+    ADD COLUMN displaced_at TIMESTAMPTZ NOT NULL DEFAULT now();
+
+WITH displaced AS (
+    DELETE FROM game_board_question_mappings m
+    USING game_board_question_mappings keep
+    WHERE m.board_id = keep.board_id
+        AND m.grid_row = keep.grid_row
+        AND m.grid_col = keep.grid_col
+        AND m.question_id > keep.question_id
+    RETURNING m.*
+)
+INSERT INTO displaced_board_cells
+SELECT * FROM displaced;
+
+-- Deferrable so cells can be swapped or shuffled inside a transaction.
+ALTER TABLE game_board_question_mappings
+ADD CONSTRAINT unique_board_cell UNIQUE (board_id, grid_row, grid_col)
+DEFERRABLE INITIALLY IMMEDIATE;
@@ -0,0 +1,15 @@
+-- Snapshots of a board's title, categories and cells (with question text)
+-- taken after every edit.
+CREATE TABLE IF NOT EXISTS game_board_revisions (
+    id BIGSERIAL PRIMARY KEY,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    board_id BIGINT NOT NULL,
+    revision_number INT NOT NULL,
+    snapshot JSONB NOT NULL,
+    FOREIGN KEY (board_id) REFERENCES game_boards(id) ON DELETE CASCADE,
+    CONSTRAINT unique_board_revision UNIQUE (board_id, revision_number)
+);
+
+-- The revision of the board a game was started from.
+ALTER TABLE games ADD COLUMN board_revision_id BIGINT
+    REFERENCES game_board_revisions(id) ON DELETE SET NULL;
@@ -0,0 +1,6 @@
+-- This file should undo anything in `up.sql`
+DROP TABLE IF EXISTS game_questions;
+
+ALTER TABLE games
+    DROP COLUMN IF EXISTS categories,
+    DROP COLUMN IF EXISTS title;
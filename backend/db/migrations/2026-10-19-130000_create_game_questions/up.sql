@@ -0,0 +1,36 @@
+-- A game's own copy of its board, taken when the game is created, so later
+-- edits to the board, its mappings or its questions never reach the game.
+ALTER TABLE games
+    ADD COLUMN title TEXT NOT NULL DEFAULT '',
+    ADD COLUMN categories TEXT[] NOT NULL DEFAULT '{}';
+
+CREATE TABLE IF NOT EXISTS game_questions (
+    id BIGSERIAL PRIMARY KEY,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    game_id BIGINT NOT NULL,
+    source_question_id BIGINT,
+    question TEXT NOT NULL,
+    answer TEXT NOT NULL,
+    daily_double BOOLEAN NOT NULL DEFAULT FALSE,
+    points INT NOT NULL,
+    grid_row INT NOT NULL,
+    grid_col INT NOT NULL,
+    FOREIGN KEY (game_id) REFERENCES games(id) ON DELETE CASCADE,
+    FOREIGN KEY (source_question_id) REFERENCES questions(id) ON DELETE SET NULL,
+    CONSTRAINT unique_game_cell UNIQUE (game_id, grid_row, grid_col)
+);
+
+-- Freeze existing games with their board as it is today
+UPDATE games
+SET title = game_boards.title,
+    categories = game_boards.categories
+FROM game_boards
+WHERE games.game_board_id = game_boards.id;
+
+INSERT INTO game_questions
+    (game_id, source_question_id, question, answer, daily_double, points, grid_row, grid_col)
+SELECT games.id, questions.id, questions.question, questions.answer,
+       m.daily_double, m.points, m.grid_row, m.grid_col
+FROM games
+JOIN game_board_question_mappings m ON m.board_id = games.game_board_id
+JOIN questions ON questions.id = m.question_id;
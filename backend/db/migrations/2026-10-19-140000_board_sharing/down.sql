@@ -0,0 +1,7 @@
+-- This file should undo anything in `up.sql`
+DROP TABLE IF EXISTS board_collaborators;
+
+ALTER TABLE game_boards
+    DROP CONSTRAINT IF EXISTS valid_board_visibility,
+    DROP COLUMN IF EXISTS share_token,
+    DROP COLUMN IF EXISTS visibility;
@@ -0,0 +1,17 @@
+-- Who besides the owner may see a board, and through which link.
+ALTER TABLE game_boards
+    ADD COLUMN visibility TEXT NOT NULL DEFAULT 'private',
+    ADD COLUMN share_token TEXT UNIQUE,
+    ADD CONSTRAINT valid_board_visibility CHECK (visibility IN ('private', 'unlisted', 'public'));
+
+-- Users invited to a board, each as an editor or a viewer.
+CREATE TABLE IF NOT EXISTS board_collaborators (
+    board_id BIGINT NOT NULL,
+    user_id BIGINT NOT NULL,
+    role TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (board_id, user_id),
+    FOREIGN KEY (board_id) REFERENCES game_boards(id) ON DELETE CASCADE,
+    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE,
+    CONSTRAINT valid_collaborator_role CHECK (role IN ('editor', 'viewer'))
+);
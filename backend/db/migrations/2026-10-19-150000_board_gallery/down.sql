@@ -0,0 +1,8 @@
+-- This file should undo anything in `up.sql`
+DROP INDEX IF EXISTS idx_games_game_board_id;
+DROP TABLE IF EXISTS board_reports;
+DROP TABLE IF EXISTS board_favourites;
+DROP TABLE IF EXISTS board_ratings;
+
+ALTER TABLE game_boards DROP COLUMN IF EXISTS flagged_at;
+ALTER TABLE users DROP COLUMN IF EXISTS is_moderator;
@@ -0,0 +1,47 @@
+-- Users allowed to review reported boards.
+ALTER TABLE users ADD COLUMN is_moderator BOOLEAN NOT NULL DEFAULT FALSE;
+
+-- Set when a board is hidden from the gallery for moderation, either
+-- automatically after enough reports or by a moderator.
+ALTER TABLE game_boards ADD COLUMN flagged_at TIMESTAMPTZ;
+
+-- One 1-5 star rating per user per board.
+CREATE TABLE IF NOT EXISTS board_ratings (
+    board_id BIGINT NOT NULL,
+    user_id BIGINT NOT NULL,
+    rating INT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (board_id, user_id),
+    FOREIGN KEY (board_id) REFERENCES game_boards(id) ON DELETE CASCADE,
+    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE,
+    CONSTRAINT valid_board_rating CHECK (rating BETWEEN 1 AND 5)
+);
+
+CREATE TABLE IF NOT EXISTS board_favourites (
+    board_id BIGINT NOT NULL,
+    user_id BIGINT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (board_id, user_id),
+    FOREIGN KEY (board_id) REFERENCES game_boards(id) ON DELETE CASCADE,
+    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+);
+
+-- Reports of inappropriate content. A report is open until a moderator
+-- resolves it.
+CREATE TABLE IF NOT EXISTS board_reports (
+    id BIGSERIAL PRIMARY KEY,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    board_id BIGINT NOT NULL,
+    user_id BIGINT NOT NULL,
+    reason TEXT NOT NULL,
+    resolved_at TIMESTAMPTZ,
+    FOREIGN KEY (board_id) REFERENCES game_boards(id) ON DELETE CASCADE,
+    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+);
+
+-- A user may only have one open report per board.
+CREATE UNIQUE INDEX IF NOT EXISTS unique_open_board_report
+    ON board_reports (board_id, user_id) WHERE resolved_at IS NULL;
+
+CREATE INDEX IF NOT EXISTS idx_games_game_board_id ON games (game_board_id);
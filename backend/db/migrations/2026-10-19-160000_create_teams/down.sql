@@ -0,0 +1,5 @@
+-- This file should undo anything in `up.sql`
+DROP TABLE IF EXISTS team_answers;
+DROP TABLE IF EXISTS team_buzzes;
+DROP TABLE IF EXISTS team_members;
+DROP TABLE IF EXISTS teams;
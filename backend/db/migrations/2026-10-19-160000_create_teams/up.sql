@@ -0,0 +1,51 @@
+-- Teams compete as a single score holder made up of several members.
+CREATE TABLE IF NOT EXISTS teams (
+    id BIGSERIAL PRIMARY KEY,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    game_id BIGINT NOT NULL,
+    team_name TEXT NOT NULL,
+    score INT NOT NULL DEFAULT 0,
+    FOREIGN KEY (game_id) REFERENCES games(id) ON DELETE CASCADE,
+    CONSTRAINT unique_team_per_game UNIQUE (game_id, team_name)
+);
+
+-- A member is either a registered user or a guest known only by name.
+CREATE TABLE IF NOT EXISTS team_members (
+    id BIGSERIAL PRIMARY KEY,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    team_id BIGINT NOT NULL,
+    user_id BIGINT,
+    member_name TEXT NOT NULL,
+    FOREIGN KEY (team_id) REFERENCES teams(id) ON DELETE CASCADE,
+    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE SET NULL,
+    CONSTRAINT unique_member_per_team UNIQUE (team_id, member_name)
+);
+
+-- Teams buzzing in on a question, in the order they buzzed.
+CREATE TABLE IF NOT EXISTS team_buzzes (
+    id BIGSERIAL PRIMARY KEY,
+    buzzed_at TIMESTAMPTZ NOT NULL DEFAULT clock_timestamp(),
+    game_question_id BIGINT NOT NULL,
+    team_id BIGINT NOT NULL,
+    member_id BIGINT,
+    FOREIGN KEY (game_question_id) REFERENCES game_questions(id) ON DELETE CASCADE,
+    FOREIGN KEY (team_id) REFERENCES teams(id) ON DELETE CASCADE,
+    FOREIGN KEY (member_id) REFERENCES team_members(id) ON DELETE SET NULL,
+    CONSTRAINT unique_team_buzz UNIQUE (game_question_id, team_id)
+);
+
+-- The host's ruling on a team's answer, credited to the member who gave it.
+CREATE TABLE IF NOT EXISTS team_answers (
+    id BIGSERIAL PRIMARY KEY,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    game_question_id BIGINT NOT NULL,
+    team_id BIGINT NOT NULL,
+    member_id BIGINT,
+    correct BOOLEAN NOT NULL,
+    points_awarded INT NOT NULL,
+    FOREIGN KEY (game_question_id) REFERENCES game_questions(id) ON DELETE CASCADE,
+    FOREIGN KEY (team_id) REFERENCES teams(id) ON DELETE CASCADE,
+    FOREIGN KEY (member_id) REFERENCES team_members(id) ON DELETE SET NULL,
+    CONSTRAINT unique_team_answer UNIQUE (game_question_id, team_id)
+);
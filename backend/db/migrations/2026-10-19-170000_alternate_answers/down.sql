@@ -0,0 +1,3 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE game_questions DROP COLUMN IF EXISTS alternate_answers;
+ALTER TABLE questions DROP COLUMN IF EXISTS alternate_answers;
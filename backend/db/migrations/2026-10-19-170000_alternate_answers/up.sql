@@ -0,0 +1,7 @@
+-- Other responses a host should accept for a question, e.g. "JFK" for
+-- "John F. Kennedy". Game questions keep their own frozen copy.
+ALTER TABLE questions
+    ADD COLUMN alternate_answers TEXT[] NOT NULL DEFAULT '{}';
+
+ALTER TABLE game_questions
+    ADD COLUMN alternate_answers TEXT[] NOT NULL DEFAULT '{}';
@@ -0,0 +1,5 @@
+-- This file should undo anything in `up.sql`
+DROP TABLE IF EXISTS game_responses;
+ALTER TABLE games
+    DROP COLUMN IF EXISTS response_deadline,
+    DROP COLUMN IF EXISTS open_question_id;
@@ -0,0 +1,20 @@
+-- The cell the host has opened for typed responses, and until when
+-- contestants may answer it.
+ALTER TABLE games
+    ADD COLUMN open_question_id BIGINT REFERENCES game_questions(id) ON DELETE SET NULL,
+    ADD COLUMN response_deadline TIMESTAMPTZ;
+
+-- One typed response per player per game cell, judged by the host.
+CREATE TABLE IF NOT EXISTS game_responses (
+    id BIGSERIAL PRIMARY KEY,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT clock_timestamp(),
+    game_question_id BIGINT NOT NULL,
+    player_id BIGINT NOT NULL,
+    response TEXT NOT NULL,
+    correct BOOLEAN,
+    points_awarded INT NOT NULL DEFAULT 0,
+    judged_at TIMESTAMPTZ,
+    FOREIGN KEY (game_question_id) REFERENCES game_questions(id) ON DELETE CASCADE,
+    FOREIGN KEY (player_id) REFERENCES players(id) ON DELETE CASCADE,
+    CONSTRAINT unique_player_response UNIQUE (game_question_id, player_id)
+);
@@ -0,0 +1,3 @@
+-- This file should undo anything in `up.sql`
+DROP INDEX IF EXISTS idx_game_timers_ends_at;
+DROP TABLE IF EXISTS game_timers;
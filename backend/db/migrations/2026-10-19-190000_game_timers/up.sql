@@ -0,0 +1,22 @@
+-- The server-side clock of a game: which phase it is in and when that phase
+-- ends, plus how long each phase lasts for this game.
+CREATE TABLE IF NOT EXISTS game_timers (
+    game_id BIGINT PRIMARY KEY,
+    phase TEXT NOT NULL DEFAULT 'idle',
+    game_question_id BIGINT,
+    started_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    ends_at TIMESTAMPTZ,
+    reading_seconds INT NOT NULL DEFAULT 5,
+    buzz_seconds INT NOT NULL DEFAULT 5,
+    answer_seconds INT NOT NULL DEFAULT 8,
+    final_seconds INT NOT NULL DEFAULT 30,
+    FOREIGN KEY (game_id) REFERENCES games(id) ON DELETE CASCADE,
+    FOREIGN KEY (game_question_id) REFERENCES game_questions(id) ON DELETE SET NULL,
+    CONSTRAINT valid_game_phase
+        CHECK (phase IN ('idle', 'reading', 'buzz_window', 'answering', 'final_jeopardy'))
+);
+
+-- The timer loop looks for running phases that have run out.
+CREATE INDEX IF NOT EXISTS idx_game_timers_ends_at
+    ON game_timers (ends_at)
+    WHERE ends_at IS NOT NULL;
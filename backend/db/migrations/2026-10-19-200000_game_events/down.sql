@@ -0,0 +1,5 @@
+-- This file should undo anything in `up.sql`
+DROP TRIGGER IF EXISTS game_events_append_only ON game_events;
+DROP FUNCTION IF EXISTS reject_game_event_update();
+DROP TABLE IF EXISTS game_events;
+ALTER TABLE games DROP COLUMN IF EXISTS round;
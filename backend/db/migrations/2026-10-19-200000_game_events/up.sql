@@ -0,0 +1,30 @@
+-- The round a game is in; advancing it is recorded as an event.
+ALTER TABLE games
+    ADD COLUMN round INT NOT NULL DEFAULT 1;
+
+-- Everything that happened in a game, in order. `sequence` counts from 0
+-- within each game and `data` holds the event's details as JSON.
+CREATE TABLE IF NOT EXISTS game_events (
+    id BIGSERIAL PRIMARY KEY,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT clock_timestamp(),
+    game_id BIGINT NOT NULL,
+    sequence INT NOT NULL,
+    event_type TEXT NOT NULL,
+    data JSONB NOT NULL DEFAULT '{}',
+    FOREIGN KEY (game_id) REFERENCES games(id) ON DELETE CASCADE,
+    CONSTRAINT unique_game_event_sequence UNIQUE (game_id, sequence),
+    CONSTRAINT valid_game_event_type CHECK (event_type IN (
+        'cell_selected', 'buzz', 'response', 'judged', 'score_changed', 'round_advanced'
+    ))
+);
+
+-- Events are append-only: once written they never change.
+CREATE OR REPLACE FUNCTION reject_game_event_update() RETURNS trigger AS $$
+BEGIN
+    RAISE EXCEPTION 'game_events are append-only';
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER game_events_append_only
+    BEFORE UPDATE ON game_events
+    FOR EACH ROW EXECUTE FUNCTION reject_game_event_update();
@@ -0,0 +1,3 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE game_timers DROP COLUMN IF EXISTS paused_remaining_ms;
+ALTER TABLE games DROP COLUMN IF EXISTS paused_at;
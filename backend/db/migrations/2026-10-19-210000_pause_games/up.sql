@@ -0,0 +1,8 @@
+-- When the host paused a game; NULL while it is being played.
+ALTER TABLE games
+    ADD COLUMN paused_at TIMESTAMPTZ;
+
+-- A paused timer has no `ends_at`, so the timer loop leaves it alone, and
+-- keeps the time its phase had left until the game is unpaused.
+ALTER TABLE game_timers
+    ADD COLUMN paused_remaining_ms BIGINT;
@@ -0,0 +1,7 @@
+-- This file should undo anything in `up.sql`
+DELETE FROM game_events WHERE event_type = 'control_changed';
+ALTER TABLE game_events DROP CONSTRAINT valid_game_event_type;
+ALTER TABLE game_events ADD CONSTRAINT valid_game_event_type CHECK (event_type IN (
+    'cell_selected', 'buzz', 'response', 'judged', 'score_changed', 'round_advanced'
+));
+ALTER TABLE games DROP COLUMN IF EXISTS control_player_id;
@@ -0,0 +1,10 @@
+-- The player who picks the next cell.
+ALTER TABLE games
+    ADD COLUMN control_player_id BIGINT REFERENCES players(id) ON DELETE SET NULL;
+
+-- Changes of control are part of the game's event log.
+ALTER TABLE game_events DROP CONSTRAINT valid_game_event_type;
+ALTER TABLE game_events ADD CONSTRAINT valid_game_event_type CHECK (event_type IN (
+    'cell_selected', 'buzz', 'response', 'judged', 'score_changed', 'round_advanced',
+    'control_changed'
+));
@@ -0,0 +1,17 @@
+-- Pictures, audio and video attached to a question. The files themselves
+-- live in media storage under `storage_key`.
+CREATE TABLE IF NOT EXISTS question_media (
+    id BIGSERIAL PRIMARY KEY,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    question_id BIGINT NOT NULL,
+    media_kind TEXT NOT NULL,
+    content_type TEXT NOT NULL,
+    file_name TEXT NOT NULL,
+    size_bytes BIGINT NOT NULL,
+    storage_key TEXT NOT NULL UNIQUE,
+    FOREIGN KEY (question_id) REFERENCES questions(id) ON DELETE CASCADE,
+    CONSTRAINT valid_media_kind CHECK (media_kind IN ('image', 'audio', 'video'))
+);
+
+CREATE INDEX IF NOT EXISTS idx_question_media_question_id
+    ON question_media (question_id);
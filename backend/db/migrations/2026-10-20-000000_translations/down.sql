@@ -0,0 +1,5 @@
+-- This file should undo anything in `up.sql`
+DROP TABLE IF EXISTS game_board_translations;
+DROP TABLE IF EXISTS question_translations;
+ALTER TABLE game_boards DROP COLUMN IF EXISTS locale;
+ALTER TABLE questions DROP COLUMN IF EXISTS locale;
@@ -0,0 +1,42 @@
+-- The locale a question or board was written in. Translations into other
+-- locales are kept alongside; anything left untranslated falls back to the
+-- original text. A translation without an answer keeps the original answer,
+-- and a missing category keeps the original category.
+ALTER TABLE questions ADD COLUMN locale TEXT NOT NULL DEFAULT 'en';
+ALTER TABLE game_boards ADD COLUMN locale TEXT NOT NULL DEFAULT 'en';
+
+CREATE TABLE IF NOT EXISTS question_translations (
+    id BIGSERIAL PRIMARY KEY,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    question_id BIGINT NOT NULL,
+    locale TEXT NOT NULL,
+    question TEXT NOT NULL,
+    answer TEXT,
+    FOREIGN KEY (question_id) REFERENCES questions(id) ON DELETE CASCADE,
+    UNIQUE (question_id, locale)
+);
+
+CREATE TRIGGER question_translations_updated_at
+    BEFORE UPDATE
+    ON question_translations
+    FOR EACH ROW
+EXECUTE PROCEDURE diesel_set_updated_at();
+
+CREATE TABLE IF NOT EXISTS game_board_translations (
+    id BIGSERIAL PRIMARY KEY,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    game_board_id BIGINT NOT NULL,
+    locale TEXT NOT NULL,
+    title TEXT NOT NULL,
+    categories TEXT[] NOT NULL,
+    FOREIGN KEY (game_board_id) REFERENCES game_boards(id) ON DELETE CASCADE,
+    UNIQUE (game_board_id, locale)
+);
+
+CREATE TRIGGER game_board_translations_updated_at
+    BEFORE UPDATE
+    ON game_board_translations
+    FOR EACH ROW
+EXECUTE PROCEDURE diesel_set_updated_at();
@@ -0,0 +1,4 @@
+-- This file should undo anything in `up.sql`
+DROP INDEX IF EXISTS idx_questions_tags;
+ALTER TABLE questions DROP COLUMN IF EXISTS difficulty;
+ALTER TABLE questions DROP COLUMN IF EXISTS tags;
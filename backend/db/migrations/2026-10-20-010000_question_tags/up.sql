@@ -0,0 +1,6 @@
+-- Tags and a difficulty from 1 (easiest) to 5 let the board generator pick
+-- questions from a library. Tags are stored lowercased.
+ALTER TABLE questions ADD COLUMN tags TEXT[] NOT NULL DEFAULT '{}';
+ALTER TABLE questions ADD COLUMN difficulty INT CHECK (difficulty BETWEEN 1 AND 5);
+
+CREATE INDEX IF NOT EXISTS idx_questions_tags ON questions USING GIN (tags);
@@ -0,0 +1,4 @@
+-- A secret per player. Remote contestants send it with their typed
+-- responses, so nobody can answer in another player's name.
+ALTER TABLE players ADD COLUMN player_token TEXT NOT NULL
+    DEFAULT replace(gen_random_uuid()::text, '-', '');
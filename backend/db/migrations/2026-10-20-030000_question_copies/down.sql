@@ -0,0 +1,3 @@
+-- This file should undo anything in `up.sql`
+DROP INDEX IF EXISTS idx_questions_copied_from;
+ALTER TABLE questions DROP COLUMN IF EXISTS copied_from_id;
@@ -0,0 +1,5 @@
+-- Copies the board generator makes of other users' questions point back at
+-- the original, so a copy can be reused and recent games checked against both.
+ALTER TABLE questions ADD COLUMN copied_from_id BIGINT REFERENCES questions(id) ON DELETE SET NULL;
+
+CREATE INDEX IF NOT EXISTS idx_questions_copied_from ON questions (user_id, copied_from_id);
@@ -0,0 +1,2 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE game_questions DROP COLUMN IF EXISTS played_at;
@@ -0,0 +1,13 @@
+-- When a cell of a game was first played, whether it was opened by the host
+-- or buzzed on in a team game. Finding abandoned games relies on it.
+ALTER TABLE game_questions ADD COLUMN played_at TIMESTAMPTZ;
+
+UPDATE game_questions q SET played_at = (
+    SELECT MIN(e.created_at) FROM game_events e
+     WHERE e.game_id = q.game_id
+       AND e.event_type IN ('cell_selected', 'buzz', 'response', 'judged')
+       AND (e.data->>'game_question_id')::BIGINT = q.id
+);
+UPDATE game_questions q SET played_at = (
+    SELECT MIN(a.created_at) FROM team_answers a WHERE a.game_question_id = q.id
+) WHERE played_at IS NULL;
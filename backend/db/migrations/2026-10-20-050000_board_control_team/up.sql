@@ -0,0 +1,3 @@
+-- The team that picks the next cell, when teams are playing.
+ALTER TABLE games
+    ADD COLUMN control_team_id BIGINT REFERENCES teams(id) ON DELETE SET NULL;
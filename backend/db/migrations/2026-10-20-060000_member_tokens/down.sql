@@ -0,0 +1,2 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE team_members DROP COLUMN IF EXISTS member_token;
@@ -0,0 +1,4 @@
+-- A secret per team member. Remote members send it when they buzz, so
+-- nobody can buzz in for a team they are not on.
+ALTER TABLE team_members ADD COLUMN member_token TEXT NOT NULL
+    DEFAULT replace(gen_random_uuid()::text, '-', '');
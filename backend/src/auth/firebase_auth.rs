@@ -1,3 +1,4 @@
+use crate::models::user::User;
 use async_graphql::Context;
 use axum::{extract::FromRequestParts, http::StatusCode};
 use jsonwebtoken::jwk::JwkSet;
@@ -19,6 +20,9 @@ static FIREBASE_JWKS_CACHE: LazyLock<RwLock<CachedJwkSet>> = LazyLock::new(|| {
     })
 });
 
+/// How long fetched keys are trusted before they are fetched again.
+const JWKS_CACHE_TTL: Duration = Duration::from_secs(3600);
+
 struct CachedJwkSet {
     jwks: JwkSet,
     expires_at: Instant,
@@ -33,6 +37,26 @@ async fn fetch_firebase_jwks() -> Result<JwkSet, Box<dyn std::error::Error>> {
     Ok(jwks)
 }
 
+/// Make sure the Firebase keys are cached, fetching them if they have
+/// expired, so the readiness check finds out before a request does.
+/// Returns how many keys are cached.
+pub async fn ensure_firebase_keys() -> Result<usize, Box<dyn std::error::Error>> {
+    {
+        let cache = FIREBASE_JWKS_CACHE.read().await;
+        if Instant::now() < cache.expires_at && !cache.jwks.keys.is_empty() {
+            return Ok(cache.jwks.keys.len());
+        }
+    }
+    let jwks = fetch_firebase_jwks().await?;
+    if jwks.keys.is_empty() {
+        return Err("Firebase returned no public keys".into());
+    }
+    let mut cache = FIREBASE_JWKS_CACHE.write().await;
+    cache.jwks = jwks;
+    cache.expires_at = Instant::now() + JWKS_CACHE_TTL;
+    Ok(cache.jwks.keys.len())
+}
+
 /// Retrieve the DecodingKey corresponding to the provided `kid` from the cached JWK set.
 /// If the cache is expired or the key is missing, fetch a fresh set.
 async fn get_decoding_key_from_jwks(kid: &str) -> Result<DecodingKey, Box<dyn std::error::Error>> {
@@ -52,7 +76,7 @@ async fn get_decoding_key_from_jwks(kid: &str) -> Result<DecodingKey, Box<dyn st
     }
     // Cache expired or key not found; fetch fresh JWK set.
     let jwks = fetch_firebase_jwks().await?;
-    let expires_at = Instant::now() + Duration::from_secs(3600);
+    let expires_at = Instant::now() + JWKS_CACHE_TTL;
     {
         let mut cache = FIREBASE_JWKS_CACHE.write().await;
         cache.jwks = jwks;
@@ -92,6 +116,11 @@ pub struct AuthenticatedUser {
 }
 
 impl AuthenticatedUser {
+    /// Wrap already-verified claims, e.g. when building a request in tests.
+    pub fn new(claims: FirebaseClaims) -> Self {
+        Self { claims }
+    }
+
     /// firebase sub (user id)
     pub fn sub(&self) -> &str {
         &self.claims.sub
@@ -128,6 +157,48 @@ pub fn require_auth<'a>(
         .map_err(|_| async_graphql::Error::new("Authentication required"))
 }
 
+/// Returns the backend `User` behind the authenticated firebase user,
+/// or an error if not authenticated or no such user exists
+pub async fn require_user(
+    ctx: &Context<'_>,
+    conn: &mut diesel_async::AsyncPgConnection,
+) -> Result<User, async_graphql::Error> {
+    let auth_user = require_auth(ctx)?;
+    User::find_by_firebase_uid(conn, auth_user.sub().to_string())
+        .await
+        .map_err(|e| async_graphql::Error::new(format!("Database error: {:?}", e)))?
+        .ok_or_else(|| async_graphql::Error::new("Backend user not found"))
+}
+
+/// Returns an error unless `requestor` owns the resource
+pub fn require_owner(
+    requestor: &User,
+    owner_id: i64,
+    resource: &str,
+) -> Result<(), async_graphql::Error> {
+    if requestor.id != owner_id {
+        tracing::info!(
+            "MISMATCH Requestor: {} | {} Owner: {}",
+            requestor.id,
+            resource,
+            owner_id
+        );
+        return Err(async_graphql::Error::new(format!(
+            "Requestor does not own this {}",
+            resource
+        )));
+    }
+    Ok(())
+}
+
+/// Returns an error unless `requestor` is a moderator
+pub fn require_moderator(requestor: &User) -> Result<(), async_graphql::Error> {
+    if !requestor.is_moderator {
+        return Err(async_graphql::Error::new("Requestor is not a moderator"));
+    }
+    Ok(())
+}
+
 impl<S> FromRequestParts<S> for AuthenticatedUser
 where
     S: Send + Sync,
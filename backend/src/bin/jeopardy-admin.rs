@@ -0,0 +1,285 @@
+// src/bin/jeopardy-admin.rs
+//! Operations on the Jeopardy database, run from a shell. `DATABASE_URL` is
+//! read from the environment or `.env`, as for the server.
+
+use backend::db::migrations::{pending_migrations, run_pending_migrations};
+use backend::db::pool::create_app_pool;
+use backend::graphql::validation::{Validate, Validator};
+use backend::models::game_board::GameBoard;
+use backend::models::user::User;
+use backend::services::admin::{
+    abandoned_games, database_stats, export_board, import_board, purge_games, BoardExport,
+};
+use backend::services::board_generator::normalize_tags;
+use chrono::{Duration, Utc};
+use diesel_async::pooled_connection::deadpool::Object;
+use diesel_async::AsyncPgConnection;
+use dotenvy::dotenv;
+use std::collections::HashMap;
+use std::env;
+
+const USAGE: &str = "Usage:
+  jeopardy-admin migrate [--status]
+  jeopardy-admin stats
+  jeopardy-admin users list
+  jeopardy-admin users create <username> <firebase-uid> [--moderator]
+  jeopardy-admin boards transfer <board-id> <user-id>
+  jeopardy-admin boards export <board-id> [--out <file>]
+  jeopardy-admin boards import <file> --user-id <id>
+  jeopardy-admin games purge-abandoned [--idle-days <days>] [--dry-run]
+
+Exports leave out question media, translations and the board's visibility.";
+
+/// Options that are followed by a value.
+const VALUE_OPTIONS: [&str; 3] = ["--out", "--user-id", "--idle-days"];
+
+/// Games idle for this many days, with cells left unplayed, are abandoned.
+const DEFAULT_IDLE_DAYS: i64 = 30;
+
+type CliResult = Result<(), Box<dyn std::error::Error>>;
+
+/// The command line, split into words and `--options`.
+struct Args {
+    words: Vec<String>,
+    options: HashMap<String, Option<String>>,
+}
+
+impl Args {
+    fn parse(args: impl Iterator<Item = String>) -> Result<Self, String> {
+        let mut words = Vec::new();
+        let mut options = HashMap::new();
+        let mut args = args.peekable();
+        while let Some(arg) = args.next() {
+            if !arg.starts_with("--") {
+                words.push(arg);
+            } else if VALUE_OPTIONS.contains(&arg.as_str()) {
+                let value = args
+                    .next_if(|value| !value.starts_with("--"))
+                    .ok_or_else(|| format!("{} needs a value", arg))?;
+                options.insert(arg, Some(value));
+            } else {
+                options.insert(arg, None);
+            }
+        }
+        Ok(Args { words, options })
+    }
+
+    fn flag(&self, name: &str) -> bool {
+        self.options.contains_key(name)
+    }
+
+    fn value(&self, name: &str) -> Option<&str> {
+        self.options.get(name).and_then(|value| value.as_deref())
+    }
+}
+
+fn parse_id(value: &str, what: &str) -> Result<i64, String> {
+    value
+        .parse()
+        .map_err(|_| format!("{} must be a number, got \"{}\"", what, value))
+}
+
+async fn connect() -> Result<Object<AsyncPgConnection>, Box<dyn std::error::Error>> {
+    let pool = create_app_pool()?;
+    Ok(pool.get().await?)
+}
+
+async fn require_user(conn: &mut AsyncPgConnection, user_id: i64) -> Result<User, String> {
+    User::find_by_id(conn, user_id)
+        .await
+        .map_err(|e| format!("Database error: {:?}", e))?
+        .ok_or_else(|| format!("No user with id {}", user_id))
+}
+
+#[tokio::main]
+async fn main() {
+    dotenv().ok();
+    let result = match Args::parse(env::args().skip(1)) {
+        Ok(args) => run(args).await,
+        Err(err) => Err(err.into()),
+    };
+    if let Err(err) = result {
+        eprintln!("{}", err);
+        std::process::exit(1);
+    }
+}
+
+async fn run(args: Args) -> CliResult {
+    let words: Vec<&str> = args.words.iter().map(String::as_str).collect();
+    match words.as_slice() {
+        ["migrate"] => migrate(args.flag("--status")).await,
+        ["stats"] => stats().await,
+        ["users", "list"] => list_users().await,
+        ["users", "create", username, firebase_uid] => {
+            create_user(username, firebase_uid, args.flag("--moderator")).await
+        }
+        ["boards", "transfer", board_id, user_id] => {
+            transfer_board(
+                parse_id(board_id, "board-id")?,
+                parse_id(user_id, "user-id")?,
+            )
+            .await
+        }
+        ["boards", "export", board_id] => {
+            export(parse_id(board_id, "board-id")?, args.value("--out")).await
+        }
+        ["boards", "import", file] => {
+            let user_id = args.value("--user-id").ok_or(USAGE)?;
+            import(file, parse_id(user_id, "--user-id")?).await
+        }
+        ["games", "purge-abandoned"] => {
+            let idle_days = match args.value("--idle-days") {
+                Some(days) => parse_id(days, "--idle-days")?,
+                None => DEFAULT_IDLE_DAYS,
+            };
+            purge_abandoned(idle_days, args.flag("--dry-run")).await
+        }
+        ["help"] | [] => {
+            println!("{}", USAGE);
+            Ok(())
+        }
+        _ => Err(USAGE.into()),
+    }
+}
+
+/// Apply pending migrations, or with `status` only list them.
+async fn migrate(status: bool) -> CliResult {
+    let database_url = env::var("DATABASE_URL").map_err(|_| "DATABASE_URL must be set")?;
+    let versions = tokio::task::spawn_blocking(move || {
+        if status {
+            pending_migrations(&database_url)
+        } else {
+            run_pending_migrations(&database_url)
+        }
+    })
+    .await?
+    .map_err(|e| e.to_string())?;
+
+    let verb = if status { "Pending" } else { "Applied" };
+    if versions.is_empty() {
+        println!("No migrations {}", verb.to_lowercase());
+    }
+    for version in versions {
+        println!("{} {}", verb, version);
+    }
+    Ok(())
+}
+
+async fn stats() -> CliResult {
+    let mut conn = connect().await?;
+    println!("{}", database_stats(&mut conn).await?);
+    Ok(())
+}
+
+async fn list_users() -> CliResult {
+    let mut conn = connect().await?;
+    let mut users = User::all(&mut conn).await?;
+    users.sort_by_key(|user| user.id);
+    println!(
+        "{:>6}  {:<32}  {:<28}  created",
+        "id", "username", "firebase uid"
+    );
+    for user in users {
+        println!(
+            "{:>6}  {:<32}  {:<28}  {}{}",
+            user.id,
+            user.username,
+            user.firebase_uid,
+            user.created_at.format("%Y-%m-%d"),
+            if user.is_moderator { "  moderator" } else { "" }
+        );
+    }
+    Ok(())
+}
+
+async fn create_user(username: &str, firebase_uid: &str, moderator: bool) -> CliResult {
+    let mut v = Validator::default();
+    v.display_name("username", username)
+        .non_blank("firebase-uid", firebase_uid);
+    v.finish().map_err(|errors| {
+        errors
+            .violations()
+            .iter()
+            .map(|violation| format!("{} {}", violation.field, violation.message))
+            .collect::<Vec<_>>()
+            .join("\n")
+    })?;
+
+    let mut conn = connect().await?;
+    let mut user = User::create(&mut conn, username.to_string(), firebase_uid.to_string()).await?;
+    if moderator {
+        user = User::set_moderator(&mut conn, user.id, true).await?;
+    }
+    println!("Created user {} ({})", user.id, user.username);
+    Ok(())
+}
+
+async fn transfer_board(board_id: i64, user_id: i64) -> CliResult {
+    let mut conn = connect().await?;
+    let new_owner = require_user(&mut conn, user_id).await?;
+    let board = GameBoard::transfer_ownership(&mut conn, board_id, new_owner.id).await?;
+    println!(
+        "Board {} (\"{}\") now belongs to {}",
+        board.id, board.title, new_owner.username
+    );
+    Ok(())
+}
+
+async fn export(board_id: i64, out: Option<&str>) -> CliResult {
+    let mut conn = connect().await?;
+    let export = export_board(&mut conn, board_id).await?;
+    let json = serde_json::to_string_pretty(&export)?;
+    match out {
+        Some(path) => {
+            tokio::fs::write(path, json).await?;
+            eprintln!("Wrote board {} to {}", board_id, path);
+        }
+        None => println!("{}", json),
+    }
+    Ok(())
+}
+
+async fn import(path: &str, user_id: i64) -> CliResult {
+    let json = tokio::fs::read_to_string(path).await?;
+    let mut export: BoardExport =
+        serde_json::from_str(&json).map_err(|e| format!("{}: {}", path, e))?;
+    for cell in export.cells.iter_mut() {
+        cell.tags = normalize_tags(&cell.tags);
+    }
+    export.validated().map_err(|errors| {
+        errors
+            .violations()
+            .iter()
+            .map(|violation| format!("{}: {} {}", path, violation.field, violation.message))
+            .collect::<Vec<_>>()
+            .join("\n")
+    })?;
+
+    let mut conn = connect().await?;
+    require_user(&mut conn, user_id).await?;
+    let board = import_board(&mut conn, user_id, export).await?;
+    println!("Created board {} (\"{}\")", board.id, board.title);
+    Ok(())
+}
+
+async fn purge_abandoned(idle_days: i64, dry_run: bool) -> CliResult {
+    let mut conn = connect().await?;
+    let games = abandoned_games(&mut conn, Utc::now() - Duration::days(idle_days)).await?;
+    for game in &games {
+        println!(
+            "Game {} (host {}): {} of {} cells played, last active {}",
+            game.id,
+            game.user_id,
+            game.cells_played,
+            game.cells,
+            game.last_activity.format("%Y-%m-%d")
+        );
+    }
+    if dry_run {
+        println!("{} abandoned games; nothing deleted", games.len());
+    } else {
+        let purged = purge_games(&mut conn, &games).await?;
+        println!("Deleted {} abandoned games", purged);
+    }
+    Ok(())
+}
@@ -0,0 +1,89 @@
+// backend/src/db/migrations.rs
+//! The migrations in `db/migrations`, embedded in the binary so they can be
+//! applied without the Diesel CLI or the source tree.
+
+use diesel::migration::MigrationSource;
+use diesel::pg::{Pg, PgConnection};
+use diesel::Connection;
+use diesel_migrations::{embed_migrations, EmbeddedMigrations, MigrationHarness};
+use std::collections::HashSet;
+
+pub const MIGRATIONS: EmbeddedMigrations = embed_migrations!("db/migrations");
+
+pub type MigrationError = Box<dyn std::error::Error + Send + Sync>;
+
+/// Apply every pending migration.
+///
+/// This opens its own synchronous connection, so call it from a blocking
+/// context such as `tokio::task::spawn_blocking`.
+///
+/// # Arguments
+/// * `database_url` - The database to migrate.
+///
+/// # Returns
+/// The versions applied, oldest first, or why migrating failed.
+pub fn run_pending_migrations(database_url: &str) -> Result<Vec<String>, MigrationError> {
+    let mut conn = PgConnection::establish(database_url)?;
+    let applied = conn.run_pending_migrations(MIGRATIONS)?;
+    Ok(applied.iter().map(|version| version.to_string()).collect())
+}
+
+/// List the migrations the database has not had yet.
+///
+/// # Arguments
+/// * `database_url` - The database to check.
+///
+/// # Returns
+/// The pending versions, oldest first, or why they could not be listed.
+pub fn pending_migrations(database_url: &str) -> Result<Vec<String>, MigrationError> {
+    let mut conn = PgConnection::establish(database_url)?;
+    let pending = conn.pending_migrations(MIGRATIONS)?;
+    Ok(pending
+        .iter()
+        .map(|migration| migration.name().version().to_string())
+        .collect())
+}
+
+/// Where a database's schema stands against the migrations in this binary.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SchemaStatus {
+    /// Versions this binary has that the database has not had yet, oldest first.
+    pub pending: Vec<String>,
+    /// Versions applied to the database that this binary does not know about,
+    /// which means the schema is ahead of it.
+    pub unknown: Vec<String>,
+}
+
+impl SchemaStatus {
+    pub fn is_ahead(&self) -> bool {
+        !self.unknown.is_empty()
+    }
+}
+
+/// Compare the migrations applied to a database with the embedded ones.
+///
+/// # Arguments
+/// * `database_url` - The database to check.
+///
+/// # Returns
+/// The schema's status, or why it could not be read.
+pub fn schema_status(database_url: &str) -> Result<SchemaStatus, MigrationError> {
+    let mut conn = PgConnection::establish(database_url)?;
+    let known: HashSet<String> = MigrationSource::<Pg>::migrations(&MIGRATIONS)?
+        .iter()
+        .map(|migration| migration.name().version().to_string())
+        .collect();
+    let mut unknown: Vec<String> = conn
+        .applied_migrations()?
+        .iter()
+        .map(|version| version.to_string())
+        .filter(|version| !known.contains(version))
+        .collect();
+    unknown.sort();
+    let pending = conn
+        .pending_migrations(MIGRATIONS)?
+        .iter()
+        .map(|migration| migration.name().version().to_string())
+        .collect();
+    Ok(SchemaStatus { pending, unknown })
+}
@@ -1,4 +1,5 @@
 // backend/src/db/mod.rs
 
+pub mod migrations;
 pub mod pool;
 pub mod schema;
@@ -1,5 +1,43 @@
 // @generated automatically by Diesel CLI.
 
+diesel::table! {
+    board_collaborators (board_id, user_id) {
+        board_id -> Int8,
+        user_id -> Int8,
+        role -> Text,
+        created_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    board_favourites (board_id, user_id) {
+        board_id -> Int8,
+        user_id -> Int8,
+        created_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    board_ratings (board_id, user_id) {
+        board_id -> Int8,
+        user_id -> Int8,
+        rating -> Int4,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    board_reports (id) {
+        id -> Int8,
+        created_at -> Timestamptz,
+        board_id -> Int8,
+        user_id -> Int8,
+        reason -> Text,
+        resolved_at -> Nullable<Timestamptz>,
+    }
+}
+
 diesel::table! {
     game_board_question_mappings (board_id, question_id) {
         board_id -> Int8,
@@ -11,6 +49,28 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    game_board_revisions (id) {
+        id -> Int8,
+        created_at -> Timestamptz,
+        board_id -> Int8,
+        revision_number -> Int4,
+        snapshot -> Jsonb,
+    }
+}
+
+diesel::table! {
+    game_board_translations (id) {
+        id -> Int8,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
+        game_board_id -> Int8,
+        locale -> Text,
+        title -> Text,
+        categories -> Array<Nullable<Text>>,
+    }
+}
+
 diesel::table! {
     game_boards (id) {
         id -> Int8,
@@ -19,6 +79,67 @@ diesel::table! {
         user_id -> Int8,
         title -> Text,
         categories -> Array<Nullable<Text>>,
+        archived_at -> Nullable<Timestamptz>,
+        visibility -> Text,
+        share_token -> Nullable<Text>,
+        flagged_at -> Nullable<Timestamptz>,
+        locale -> Text,
+    }
+}
+
+diesel::table! {
+    game_events (id) {
+        id -> Int8,
+        created_at -> Timestamptz,
+        game_id -> Int8,
+        sequence -> Int4,
+        event_type -> Text,
+        data -> Jsonb,
+    }
+}
+
+diesel::table! {
+    game_questions (id) {
+        id -> Int8,
+        created_at -> Timestamptz,
+        game_id -> Int8,
+        source_question_id -> Nullable<Int8>,
+        question -> Text,
+        answer -> Text,
+        daily_double -> Bool,
+        points -> Int4,
+        grid_row -> Int4,
+        grid_col -> Int4,
+        alternate_answers -> Array<Text>,
+        played_at -> Nullable<Timestamptz>,
+    }
+}
+
+diesel::table! {
+    game_responses (id) {
+        id -> Int8,
+        created_at -> Timestamptz,
+        game_question_id -> Int8,
+        player_id -> Int8,
+        response -> Text,
+        correct -> Nullable<Bool>,
+        points_awarded -> Int4,
+        judged_at -> Nullable<Timestamptz>,
+    }
+}
+
+diesel::table! {
+    game_timers (game_id) {
+        game_id -> Int8,
+        phase -> Text,
+        game_question_id -> Nullable<Int8>,
+        started_at -> Timestamptz,
+        ends_at -> Nullable<Timestamptz>,
+        reading_seconds -> Int4,
+        buzz_seconds -> Int4,
+        answer_seconds -> Int4,
+        final_seconds -> Int4,
+        paused_remaining_ms -> Nullable<Int8>,
     }
 }
 
@@ -29,6 +150,16 @@ diesel::table! {
         updated_at -> Timestamptz,
         user_id -> Int8,
         game_board_id -> Int8,
+        archived_at -> Nullable<Timestamptz>,
+        board_revision_id -> Nullable<Int8>,
+        title -> Text,
+        categories -> Array<Nullable<Text>>,
+        open_question_id -> Nullable<Int8>,
+        response_deadline -> Nullable<Timestamptz>,
+        round -> Int4,
+        paused_at -> Nullable<Timestamptz>,
+        control_player_id -> Nullable<Int8>,
+        control_team_id -> Nullable<Int8>,
     }
 }
 
@@ -40,6 +171,32 @@ diesel::table! {
         game_id -> Int8,
         player_name -> Text,
         score -> Int4,
+        player_token -> Text,
+    }
+}
+
+diesel::table! {
+    question_media (id) {
+        id -> Int8,
+        created_at -> Timestamptz,
+        question_id -> Int8,
+        media_kind -> Text,
+        content_type -> Text,
+        file_name -> Text,
+        size_bytes -> Int8,
+        storage_key -> Text,
+    }
+}
+
+diesel::table! {
+    question_translations (id) {
+        id -> Int8,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
+        question_id -> Int8,
+        locale -> Text,
+        question -> Text,
+        answer -> Nullable<Text>,
     }
 }
 
@@ -51,6 +208,56 @@ diesel::table! {
         user_id -> Int8,
         question -> Text,
         answer -> Text,
+        archived_at -> Nullable<Timestamptz>,
+        alternate_answers -> Array<Text>,
+        locale -> Text,
+        tags -> Array<Text>,
+        difficulty -> Nullable<Int4>,
+        copied_from_id -> Nullable<Int8>,
+    }
+}
+
+diesel::table! {
+    team_answers (id) {
+        id -> Int8,
+        created_at -> Timestamptz,
+        game_question_id -> Int8,
+        team_id -> Int8,
+        member_id -> Nullable<Int8>,
+        correct -> Bool,
+        points_awarded -> Int4,
+    }
+}
+
+diesel::table! {
+    team_buzzes (id) {
+        id -> Int8,
+        buzzed_at -> Timestamptz,
+        game_question_id -> Int8,
+        team_id -> Int8,
+        member_id -> Nullable<Int8>,
+    }
+}
+
+diesel::table! {
+    team_members (id) {
+        id -> Int8,
+        created_at -> Timestamptz,
+        team_id -> Int8,
+        user_id -> Nullable<Int8>,
+        member_name -> Text,
+        member_token -> Text,
+    }
+}
+
+diesel::table! {
+    teams (id) {
+        id -> Int8,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
+        game_id -> Int8,
+        team_name -> Text,
+        score -> Int4,
     }
 }
 
@@ -61,22 +268,68 @@ diesel::table! {
         updated_at -> Timestamptz,
         username -> Text,
         firebase_uid -> Text,
+        is_moderator -> Bool,
     }
 }
 
+diesel::joinable!(board_collaborators -> game_boards (board_id));
+diesel::joinable!(board_collaborators -> users (user_id));
+diesel::joinable!(board_favourites -> game_boards (board_id));
+diesel::joinable!(board_favourites -> users (user_id));
+diesel::joinable!(board_ratings -> game_boards (board_id));
+diesel::joinable!(board_ratings -> users (user_id));
+diesel::joinable!(board_reports -> game_boards (board_id));
+diesel::joinable!(board_reports -> users (user_id));
 diesel::joinable!(game_board_question_mappings -> game_boards (board_id));
 diesel::joinable!(game_board_question_mappings -> questions (question_id));
+diesel::joinable!(game_board_revisions -> game_boards (board_id));
+diesel::joinable!(game_board_translations -> game_boards (game_board_id));
 diesel::joinable!(game_boards -> users (user_id));
+diesel::joinable!(game_events -> games (game_id));
+diesel::joinable!(game_questions -> games (game_id));
+diesel::joinable!(game_questions -> questions (source_question_id));
+diesel::joinable!(game_responses -> game_questions (game_question_id));
+diesel::joinable!(game_responses -> players (player_id));
+diesel::joinable!(game_timers -> game_questions (game_question_id));
+diesel::joinable!(game_timers -> games (game_id));
+diesel::joinable!(games -> game_board_revisions (board_revision_id));
 diesel::joinable!(games -> game_boards (game_board_id));
 diesel::joinable!(games -> users (user_id));
 diesel::joinable!(players -> games (game_id));
+diesel::joinable!(question_media -> questions (question_id));
+diesel::joinable!(question_translations -> questions (question_id));
 diesel::joinable!(questions -> users (user_id));
+diesel::joinable!(team_answers -> game_questions (game_question_id));
+diesel::joinable!(team_answers -> team_members (member_id));
+diesel::joinable!(team_answers -> teams (team_id));
+diesel::joinable!(team_buzzes -> game_questions (game_question_id));
+diesel::joinable!(team_buzzes -> team_members (member_id));
+diesel::joinable!(team_buzzes -> teams (team_id));
+diesel::joinable!(team_members -> teams (team_id));
+diesel::joinable!(team_members -> users (user_id));
+diesel::joinable!(teams -> games (game_id));
 
 diesel::allow_tables_to_appear_in_same_query!(
+    board_collaborators,
+    board_favourites,
+    board_ratings,
+    board_reports,
     game_board_question_mappings,
+    game_board_revisions,
+    game_board_translations,
     game_boards,
+    game_events,
+    game_questions,
+    game_responses,
+    game_timers,
     games,
     players,
+    question_media,
+    question_translations,
     questions,
+    team_answers,
+    team_buzzes,
+    team_members,
+    teams,
     users,
 );
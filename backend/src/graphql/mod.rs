@@ -2,4 +2,6 @@
 pub mod mutations;
 pub mod query;
 pub mod schema;
+pub mod subscriptions;
 pub mod types;
+pub mod validation;
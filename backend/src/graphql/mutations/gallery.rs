@@ -0,0 +1,145 @@
+// graphql/mutations/gallery.rs
+
+use async_graphql::{Context, Object, Result};
+
+use crate::auth::firebase_auth::{require_moderator, require_user};
+use crate::db::pool::DBPool;
+use crate::graphql::validation::{Validator, MAX_ANSWER_LENGTH};
+use crate::models::board_rating::{BoardFavourite, BoardRating, MAX_RATING, MIN_RATING};
+use crate::models::board_report::{BoardReport, NewBoardReport};
+use crate::models::game_board::GameBoard;
+use crate::services::board_access::{require_board_access, BoardAccess};
+use crate::services::gallery::{moderate_board, report_board, ModerationAction};
+
+#[derive(Default)]
+pub struct GalleryMutation;
+
+#[Object]
+impl GalleryMutation {
+    /// Rate a gameboard from 1 to 5 stars, replacing any earlier rating
+    async fn rate_game_board(
+        &self,
+        ctx: &Context<'_>,
+        board_id: i64,
+        rating: i32,
+    ) -> Result<BoardRating> {
+        let mut v = Validator::default();
+        v.range("rating", rating, MIN_RATING, MAX_RATING);
+        v.finish()?;
+
+        let pool = ctx.data::<DBPool>().map_err(|e| {
+            async_graphql::Error::new(format!("Cannot get DBPool from context: {:?}", e))
+        })?;
+        let mut conn = pool
+            .get()
+            .await
+            .map_err(|e| async_graphql::Error::new(format!("Failed to get connection: {}", e)))?;
+
+        let (requestor, game_board) =
+            require_board_access(ctx, &mut conn, board_id, BoardAccess::View).await?;
+        if game_board.user_id == requestor.id {
+            return Err(async_graphql::Error::new("Cannot rate your own game board"));
+        }
+
+        let rating = BoardRating::rate(&mut conn, board_id, requestor.id, rating).await?;
+        Ok(rating)
+    }
+
+    /// Add a gameboard to the requestor's favourites
+    async fn favourite_game_board(&self, ctx: &Context<'_>, board_id: i64) -> Result<bool> {
+        let pool = ctx.data::<DBPool>().map_err(|e| {
+            async_graphql::Error::new(format!("Cannot get DBPool from context: {:?}", e))
+        })?;
+        let mut conn = pool
+            .get()
+            .await
+            .map_err(|e| async_graphql::Error::new(format!("Failed to get connection: {}", e)))?;
+
+        let (requestor, _) =
+            require_board_access(ctx, &mut conn, board_id, BoardAccess::View).await?;
+        BoardFavourite::add(&mut conn, board_id, requestor.id).await?;
+        Ok(true)
+    }
+
+    /// Remove a gameboard from the requestor's favourites
+    async fn unfavourite_game_board(&self, ctx: &Context<'_>, board_id: i64) -> Result<bool> {
+        let pool = ctx.data::<DBPool>().map_err(|e| {
+            async_graphql::Error::new(format!("Cannot get DBPool from context: {:?}", e))
+        })?;
+        let mut conn = pool
+            .get()
+            .await
+            .map_err(|e| async_graphql::Error::new(format!("Failed to get connection: {}", e)))?;
+
+        let requestor = require_user(ctx, &mut conn).await?;
+        let rows_deleted = BoardFavourite::remove(&mut conn, board_id, requestor.id).await?;
+        Ok(rows_deleted > 0)
+    }
+
+    /// Report a gameboard with inappropriate content. Boards reported by
+    /// enough users are hidden from the gallery until reviewed
+    async fn report_game_board(
+        &self,
+        ctx: &Context<'_>,
+        board_id: i64,
+        reason: String,
+    ) -> Result<BoardReport> {
+        let mut v = Validator::default();
+        v.non_blank("reason", &reason)
+            .max_length("reason", &reason, MAX_ANSWER_LENGTH);
+        v.finish()?;
+
+        let pool = ctx.data::<DBPool>().map_err(|e| {
+            async_graphql::Error::new(format!("Cannot get DBPool from context: {:?}", e))
+        })?;
+        let mut conn = pool
+            .get()
+            .await
+            .map_err(|e| async_graphql::Error::new(format!("Failed to get connection: {}", e)))?;
+
+        let (requestor, _) =
+            require_board_access(ctx, &mut conn, board_id, BoardAccess::View).await?;
+
+        let new_report = NewBoardReport {
+            board_id,
+            user_id: requestor.id,
+            reason,
+        };
+        match report_board(&mut conn, new_report).await {
+            Ok(report) => Ok(report),
+            Err(diesel::result::Error::DatabaseError(
+                diesel::result::DatabaseErrorKind::UniqueViolation,
+                _,
+            )) => Err(async_graphql::Error::new(
+                "You have already reported this game board",
+            )),
+            Err(e) => Err(async_graphql::Error::new(format!(
+                "Database error: {:?}",
+                e
+            ))),
+        }
+    }
+
+    /// Resolve a gameboard's reports, keeping it hidden or listing it again.
+    /// Moderators only
+    async fn moderate_game_board(
+        &self,
+        ctx: &Context<'_>,
+        board_id: i64,
+        action: ModerationAction,
+    ) -> Result<GameBoard> {
+        let pool = ctx.data::<DBPool>().map_err(|e| {
+            async_graphql::Error::new(format!("Cannot get DBPool from context: {:?}", e))
+        })?;
+        let mut conn = pool
+            .get()
+            .await
+            .map_err(|e| async_graphql::Error::new(format!("Failed to get connection: {}", e)))?;
+
+        let requestor = require_user(ctx, &mut conn).await?;
+        require_moderator(&requestor)?;
+
+        let game_board = moderate_board(&mut conn, board_id, action).await?;
+        Ok(game_board)
+    }
+}
@@ -1,15 +1,35 @@
 // graphql/mutations/game.rs
 
-use async_graphql::{Context, InputObject, Object, Result};
+use async_graphql::{Context, ErrorExtensions, InputObject, Object, Result};
+use diesel_async::scoped_futures::ScopedFutureExt;
+use diesel_async::AsyncConnection;
 
+use crate::auth::firebase_auth::{require_owner, require_user};
 use crate::db::pool::DBPool;
-use crate::models::game::{Game, NewGame};
+use crate::graphql::validation::{Validate, Validator};
+use crate::models::game::Game;
+use crate::models::game_event::{GameEvent, GameEventData, GameEventType};
 use crate::models::player::{NewPlayer, Player};
+use crate::services::board_access::{require_board_access, BoardAccess};
+use crate::services::board_control::{set_control, BoardControl, ControlHub};
+use crate::services::board_validation::validate_game_board;
+use crate::services::game_timers::TimerHub;
+use crate::services::live_games::{game_snapshot, pause_game, unpause_game, GameSnapshot};
 
 #[derive(InputObject)]
 pub struct CreateGameInput {
+    /// Must be the requestor, who hosts the game
     pub user_id: i64,
     pub game_board_id: i64,
+    /// Refuse to start the game unless the board passes `validateGameBoard`
+    pub require_ready_board: Option<bool>,
+}
+
+impl Validate for CreateGameInput {
+    fn validate(&self, v: &mut Validator) {
+        v.id("userId", self.user_id);
+        v.id("gameBoardId", self.game_board_id);
+    }
 }
 
 #[derive(Default)]
@@ -17,7 +37,10 @@ pub struct GameMutation;
 
 #[Object]
 impl GameMutation {
+    /// Start a game on a board the requestor can view, hosted by the requestor
     async fn create_game(&self, ctx: &Context<'_>, input: CreateGameInput) -> Result<Game> {
+        input.validated()?;
+
         let pool = ctx.data::<DBPool>().map_err(|e| {
             async_graphql::Error::new(format!("Cannot get DBPool from context: {:?}", e))
         })?;
@@ -26,11 +49,34 @@ impl GameMutation {
             .await
             .map_err(|e| async_graphql::Error::new(format!("Failed to get connection: {}", e)))?;
 
-        let new_game: NewGame = NewGame {
-            user_id: input.user_id,
-            game_board_id: input.game_board_id,
-        };
-        let game: Game = Game::create(&mut conn, new_game).await?;
+        let requestor = require_user(ctx, &mut conn).await?;
+        if requestor.id != input.user_id {
+            return Err(async_graphql::Error::new(
+                "Requestor cannot start a game for another user",
+            ));
+        }
+        let (_, game_board) =
+            require_board_access(ctx, &mut conn, input.game_board_id, BoardAccess::View).await?;
+        if game_board.archived_at.is_some() {
+            return Err(async_graphql::Error::new(
+                "Cannot start a game on an archived game board",
+            ));
+        }
+
+        if input.require_ready_board.unwrap_or(false) {
+            let report = validate_game_board(&mut conn, input.game_board_id).await?;
+            if !report.ready {
+                let issues: Vec<String> = report.issues.into_iter().map(|i| i.message).collect();
+                return Err(async_graphql::Error::new("Game board is not ready to play")
+                    .extend_with(|_, e| {
+                        e.set("code", "BOARD_NOT_READY");
+                        e.set("issues", issues.clone());
+                    }));
+            }
+        }
+
+        // The game gets its own copy of the board's content
+        let game: Game = Game::start(&mut conn, requestor.id, input.game_board_id).await?;
 
         // Create default 3 players
         let default_players = vec![
@@ -54,4 +100,180 @@ impl GameMutation {
 
         Ok(game)
     }
+
+    /// Archive a game. It disappears from listings but can be restored
+    async fn delete_game(&self, ctx: &Context<'_>, game_id: i64) -> Result<bool> {
+        let pool = ctx.data::<DBPool>().map_err(|e| {
+            async_graphql::Error::new(format!("Cannot get DBPool from context: {:?}", e))
+        })?;
+        let mut conn = pool
+            .get()
+            .await
+            .map_err(|e| async_graphql::Error::new(format!("Failed to get connection: {}", e)))?;
+
+        let requestor = require_user(ctx, &mut conn).await?;
+        let game = Game::find_by_id(&mut conn, game_id).await?;
+        require_owner(&requestor, game.user_id, "game")?;
+
+        let archived = Game::archive(&mut conn, game_id).await?;
+        Ok(archived.archived_at.is_some())
+    }
+
+    /// Restore an archived game
+    async fn restore_game(&self, ctx: &Context<'_>, game_id: i64) -> Result<Game> {
+        let pool = ctx.data::<DBPool>().map_err(|e| {
+            async_graphql::Error::new(format!("Cannot get DBPool from context: {:?}", e))
+        })?;
+        let mut conn = pool
+            .get()
+            .await
+            .map_err(|e| async_graphql::Error::new(format!("Failed to get connection: {}", e)))?;
+
+        let requestor = require_user(ctx, &mut conn).await?;
+        let game = Game::find_by_id(&mut conn, game_id).await?;
+        require_owner(&requestor, game.user_id, "game")?;
+
+        let restored = Game::restore(&mut conn, game_id).await?;
+        Ok(restored)
+    }
+
+    /// Permanently delete a game and its players
+    async fn purge_game(&self, ctx: &Context<'_>, game_id: i64) -> Result<bool> {
+        let pool = ctx.data::<DBPool>().map_err(|e| {
+            async_graphql::Error::new(format!("Cannot get DBPool from context: {:?}", e))
+        })?;
+        let mut conn = pool
+            .get()
+            .await
+            .map_err(|e| async_graphql::Error::new(format!("Failed to get connection: {}", e)))?;
+
+        let requestor = require_user(ctx, &mut conn).await?;
+        let game = Game::find_by_id(&mut conn, game_id).await?;
+        require_owner(&requestor, game.user_id, "game")?;
+
+        let rows_deleted = Game::purge(&mut conn, game_id).await?;
+        Ok(rows_deleted > 0)
+    }
+
+    /// Move a game on to its next round. Host only
+    async fn advance_round(&self, ctx: &Context<'_>, game_id: i64) -> Result<Game> {
+        let pool = ctx.data::<DBPool>().map_err(|e| {
+            async_graphql::Error::new(format!("Cannot get DBPool from context: {:?}", e))
+        })?;
+        let mut conn = pool
+            .get()
+            .await
+            .map_err(|e| async_graphql::Error::new(format!("Failed to get connection: {}", e)))?;
+
+        let requestor = require_user(ctx, &mut conn).await?;
+        let game = Game::find_by_id(&mut conn, game_id).await?;
+        require_owner(&requestor, game.user_id, "game")?;
+
+        let game = conn
+            .transaction::<_, diesel::result::Error, _>(|conn| {
+                async move {
+                    let game = Game::advance_round(conn, game_id).await?;
+                    GameEvent::record(
+                        conn,
+                        game_id,
+                        GameEventType::RoundAdvanced,
+                        GameEventData {
+                            round: Some(game.round),
+                            ..Default::default()
+                        },
+                    )
+                    .await?;
+                    Ok(game)
+                }
+                .scope_boxed()
+            })
+            .await?;
+        Ok(game)
+    }
+
+    /// Pause a game, stopping its clock. Host only
+    async fn pause_game(&self, ctx: &Context<'_>, game_id: i64) -> Result<GameSnapshot> {
+        let pool = ctx.data::<DBPool>().map_err(|e| {
+            async_graphql::Error::new(format!("Cannot get DBPool from context: {:?}", e))
+        })?;
+        let hub = ctx.data::<TimerHub>().map_err(|e| {
+            async_graphql::Error::new(format!("Cannot get TimerHub from context: {:?}", e))
+        })?;
+        let mut conn = pool
+            .get()
+            .await
+            .map_err(|e| async_graphql::Error::new(format!("Failed to get connection: {}", e)))?;
+
+        let requestor = require_user(ctx, &mut conn).await?;
+        let game = Game::find_by_id(&mut conn, game_id).await?;
+        require_owner(&requestor, game.user_id, "game")?;
+
+        pause_game(&mut conn, hub, game_id).await?;
+        let snapshot = game_snapshot(&mut conn, hub, game_id).await?;
+        Ok(snapshot)
+    }
+
+    /// Unpause a game, restarting its clock where it stopped. Host only
+    async fn unpause_game(&self, ctx: &Context<'_>, game_id: i64) -> Result<GameSnapshot> {
+        let pool = ctx.data::<DBPool>().map_err(|e| {
+            async_graphql::Error::new(format!("Cannot get DBPool from context: {:?}", e))
+        })?;
+        let hub = ctx.data::<TimerHub>().map_err(|e| {
+            async_graphql::Error::new(format!("Cannot get TimerHub from context: {:?}", e))
+        })?;
+        let mut conn = pool
+            .get()
+            .await
+            .map_err(|e| async_graphql::Error::new(format!("Failed to get connection: {}", e)))?;
+
+        let requestor = require_user(ctx, &mut conn).await?;
+        let game = Game::find_by_id(&mut conn, game_id).await?;
+        require_owner(&requestor, game.user_id, "game")?;
+
+        unpause_game(&mut conn, hub, game_id).await?;
+        let snapshot = game_snapshot(&mut conn, hub, game_id).await?;
+        Ok(snapshot)
+    }
+
+    /// Give control of the board to a player of the game, or to nobody.
+    /// Host only
+    async fn set_board_control(
+        &self,
+        ctx: &Context<'_>,
+        game_id: i64,
+        player_id: Option<i64>,
+    ) -> Result<BoardControl> {
+        let pool = ctx.data::<DBPool>().map_err(|e| {
+            async_graphql::Error::new(format!("Cannot get DBPool from context: {:?}", e))
+        })?;
+        let hub = ctx.data::<ControlHub>().map_err(|e| {
+            async_graphql::Error::new(format!("Cannot get ControlHub from context: {:?}", e))
+        })?;
+        let mut conn = pool
+            .get()
+            .await
+            .map_err(|e| async_graphql::Error::new(format!("Failed to get connection: {}", e)))?;
+
+        let requestor = require_user(ctx, &mut conn).await?;
+        let game = Game::find_by_id(&mut conn, game_id).await?;
+        require_owner(&requestor, game.user_id, "game")?;
+
+        if let Some(player_id) = player_id {
+            match Player::find_by_id(&mut conn, player_id).await {
+                Ok(player) if player.game_id == game_id => {}
+                Ok(_) | Err(diesel::NotFound) => {
+                    return Err(async_graphql::Error::new("Player is not in this game"))
+                }
+                Err(e) => {
+                    return Err(async_graphql::Error::new(format!(
+                        "Database error: {:?}",
+                        e
+                    )))
+                }
+            }
+        }
+
+        let control = set_control(&mut conn, hub, game_id, player_id).await?;
+        Ok(control)
+    }
 }
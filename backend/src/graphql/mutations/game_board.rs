@@ -1,12 +1,31 @@
 // graphql/mutations/game_board.rs
 
+use crate::auth::firebase_auth::{require_owner, require_user};
 use crate::db::pool::DBPool;
-use crate::models::game_board::{GameBoard, NewGameBoard, UpdateGameBoard};
+use crate::graphql::validation::{
+    Validate, Validator, GRID_COLS, GRID_ROWS, MAX_CATEGORY_LENGTH, MAX_POINTS, MAX_TITLE_LENGTH,
+};
+use crate::models::board_collaborator::{
+    BoardCollaborator, CollaboratorRole, NewBoardCollaborator,
+};
+use crate::models::game_board::{BoardVisibility, GameBoard, NewGameBoard, UpdateGameBoard};
 use crate::models::game_board_question_mapping::{
     GameBoardQuestionMapping, NewGameBoardQuestionMapping,
 };
+use crate::models::game_board_revision::GameBoardRevision;
 use crate::models::question::{NewQuestion, Question};
+use crate::models::user::User;
+use crate::services::board_access::{
+    board_sharing, require_board_access, require_share_token, BoardAccess, BoardSharing,
+};
+use crate::services::board_generator::{
+    generate_board, GeneratedGameBoard, GeneratorOptions, QuestionSource, DEFAULT_BASE_POINTS,
+    DEFAULT_DAILY_DOUBLES, DEFAULT_RECENT_GAMES, MAX_DAILY_DOUBLES, MAX_RECENT_GAMES,
+};
+use crate::services::localization::normalize_locale;
 use async_graphql::{Context, InputObject, Object, Result};
+use diesel_async::scoped_futures::ScopedFutureExt;
+use diesel_async::AsyncConnection;
 
 #[derive(InputObject)]
 pub struct CreateGameBoardInput {
@@ -19,6 +38,113 @@ pub struct UpdateGameBoardInput {
     pub board_id: i64,
     pub title: Option<String>,
     pub categories: Option<Vec<String>>,
+    /// The locale the board is written in, such as `en` or `es-MX`.
+    pub locale: Option<String>,
+}
+
+#[derive(InputObject)]
+pub struct GenerateGameBoardInput {
+    pub title: String,
+    /// One category per column, matched against question tags and the
+    /// categories questions already sit under. Leave out to pick categories
+    /// from the library.
+    pub categories: Option<Vec<String>>,
+    /// Rows per column, 5 by default.
+    pub rows: Option<i32>,
+    /// Columns to fill when no categories are given, 5 by default.
+    pub cols: Option<i32>,
+    pub min_difficulty: Option<i32>,
+    pub max_difficulty: Option<i32>,
+    /// Whose questions to use, the requestor's own by default.
+    pub source: Option<QuestionSource>,
+    /// The first row's points; each row below adds as much again. 100 by default.
+    pub base_points: Option<i32>,
+    /// 1 by default.
+    pub daily_doubles: Option<i32>,
+    /// Avoid questions played in this many of the requestor's latest games,
+    /// 5 by default.
+    pub avoid_recent_games: Option<i32>,
+    /// Generating again with the same seed over the same library gives the
+    /// same board. A random seed is used when left out.
+    pub seed: Option<i64>,
+}
+
+impl Validate for GenerateGameBoardInput {
+    fn validate(&self, v: &mut Validator) {
+        v.non_blank("title", &self.title)
+            .max_length("title", &self.title, MAX_TITLE_LENGTH);
+        let rows = self.rows.unwrap_or(GRID_ROWS);
+        v.range("rows", rows, 1, GRID_ROWS);
+        if let Some(cols) = self.cols {
+            v.range("cols", cols, 1, GRID_COLS);
+        }
+        if let Some(categories) = &self.categories {
+            if categories.is_empty() || categories.len() > GRID_COLS as usize {
+                v.add(
+                    "categories",
+                    format!("must have between 1 and {} categories", GRID_COLS),
+                );
+            }
+            if self
+                .cols
+                .is_some_and(|cols| cols as usize != categories.len())
+            {
+                v.add("cols", "must match the number of categories");
+            }
+            v.each("categories", categories, |v, field, category| {
+                v.non_blank(field, category)
+                    .max_length(field, category, MAX_CATEGORY_LENGTH);
+            });
+        }
+        if let Some(min) = self.min_difficulty {
+            v.difficulty("minDifficulty", min);
+        }
+        if let Some(max) = self.max_difficulty {
+            v.difficulty("maxDifficulty", max);
+            if self.min_difficulty.is_some_and(|min| min > max) {
+                v.add("maxDifficulty", "must be at least minDifficulty");
+            }
+        }
+        if let Some(base_points) = self.base_points {
+            v.range("basePoints", base_points, 1, MAX_POINTS / rows.max(1));
+        }
+        if let Some(daily_doubles) = self.daily_doubles {
+            v.range("dailyDoubles", daily_doubles, 0, MAX_DAILY_DOUBLES);
+        }
+        if let Some(recent) = self.avoid_recent_games {
+            v.range("avoidRecentGames", recent, 0, MAX_RECENT_GAMES);
+        }
+    }
+}
+
+impl Validate for CreateGameBoardInput {
+    fn validate(&self, v: &mut Validator) {
+        v.id("userId", self.user_id);
+        v.non_blank("title", &self.title)
+            .max_length("title", &self.title, MAX_TITLE_LENGTH);
+    }
+}
+
+impl Validate for UpdateGameBoardInput {
+    fn validate(&self, v: &mut Validator) {
+        v.id("boardId", self.board_id);
+        if let Some(title) = &self.title {
+            v.non_blank("title", title)
+                .max_length("title", title, MAX_TITLE_LENGTH);
+        }
+        if let Some(categories) = &self.categories {
+            if categories.len() != GRID_COLS as usize {
+                v.add("categories", format!("must have {} categories", GRID_COLS));
+            }
+            v.each("categories", categories, |v, field, category| {
+                v.non_blank(field, category)
+                    .max_length(field, category, MAX_CATEGORY_LENGTH);
+            });
+        }
+        if let Some(locale) = &self.locale {
+            v.locale("locale", locale);
+        }
+    }
 }
 
 #[derive(Default)]
@@ -32,6 +158,8 @@ impl GameBoardMutation {
         ctx: &Context<'_>,
         input: CreateGameBoardInput,
     ) -> Result<GameBoard> {
+        input.validated()?;
+
         let pool = ctx.data::<DBPool>().map_err(|e| {
             async_graphql::Error::new(format!("Cannot get DBPool from context: {:?}", e))
         })?;
@@ -44,30 +172,42 @@ impl GameBoardMutation {
             user_id: input.user_id,
             title: input.title,
         };
-        let game_board: GameBoard = GameBoard::create(&mut conn, new_game_board).await?;
-        // Create example question and associate it with this gameboard
-        let example_question: Question = Question::create(
-            &mut conn,
-            NewQuestion {
-                user_id: input.user_id,
-                question: "Example Question".to_string(),
-                answer: "Example Answer".to_string(),
-            },
-        )
-        .await?;
-        // Create an example board game question mapping
-        let _example_board_game_question_mapping: GameBoardQuestionMapping =
-            GameBoardQuestionMapping::create_mapping(
-                &mut conn,
-                NewGameBoardQuestionMapping {
-                    board_id: game_board.id,
-                    question_id: example_question.id,
-                    daily_double: false,
-                    points: 100,
-                    grid_row: 0,
-                    grid_col: 0,
-                },
-            )
+        // The board, its example and its first revision are saved together
+        let game_board = conn
+            .transaction::<_, diesel::result::Error, _>(|conn| {
+                async move {
+                    let game_board: GameBoard = GameBoard::create(conn, new_game_board).await?;
+                    // Create example question and associate it with this gameboard
+                    let example_question: Question = Question::create(
+                        conn,
+                        NewQuestion {
+                            user_id: input.user_id,
+                            question: "Example Question".to_string(),
+                            answer: "Example Answer".to_string(),
+                            alternate_answers: Vec::new(),
+                            tags: Vec::new(),
+                            difficulty: None,
+                        },
+                    )
+                    .await?;
+                    // Create an example board game question mapping
+                    GameBoardQuestionMapping::create_mapping(
+                        conn,
+                        NewGameBoardQuestionMapping {
+                            board_id: game_board.id,
+                            question_id: example_question.id,
+                            daily_double: false,
+                            points: 100,
+                            grid_row: 0,
+                            grid_col: 0,
+                        },
+                    )
+                    .await?;
+                    GameBoardRevision::record(conn, game_board.id).await?;
+                    Ok(game_board)
+                }
+                .scope_boxed()
+            })
             .await?;
         Ok(game_board)
     }
@@ -86,38 +226,24 @@ impl GameBoardMutation {
             .await
             .map_err(|e| async_graphql::Error::new(format!("Failed to get connection: {}", e)))?;
 
-        let existing_game_board_result = GameBoard::find_by_id(&mut conn, input.board_id).await;
-
-        let _existing_game_board = match existing_game_board_result {
-            Ok(game_board) => game_board,
-            Err(diesel::NotFound) => return Err(async_graphql::Error::new("Gameboard not found")),
-            Err(e) => {
-                return Err(async_graphql::Error::new(format!(
-                    "Database error: {:?}",
-                    e
-                )));
-            }
-        };
+        // Owner or an invited editor
+        require_board_access(ctx, &mut conn, input.board_id, BoardAccess::Edit).await?;
 
         // Input validation
-        if let Some(title) = &input.title {
-            if title.is_empty() {
-                return Err(async_graphql::Error::new("Title length must be positive"));
-            }
-        }
-        if let Some(categories) = &input.categories {
-            if categories.len() != 5 {
-                return Err(async_graphql::Error::new("Must have 5 categories"));
-            }
-        }
+        input.validated()?;
 
         let updated_fields: UpdateGameBoard = UpdateGameBoard {
             title: input.title,
             categories: input.categories,
+            locale: input.locale.as_deref().and_then(normalize_locale),
         };
 
+        let board_id = input.board_id;
         let updated: GameBoard =
-            GameBoard::update_game_board(&mut conn, input.board_id, updated_fields).await?;
+            GameBoardRevision::with_revision(&mut conn, board_id, move |conn| {
+                GameBoard::update_game_board(conn, board_id, updated_fields).scope_boxed()
+            })
+            .await?;
 
         Ok(updated)
     }
@@ -130,9 +256,11 @@ impl GameBoardMutation {
         index: i32,
         category: String,
     ) -> Result<GameBoard> {
-        if !(0..=4).contains(&index) {
-            return Err(async_graphql::Error::new("Category index must be 0 - 4"));
-        }
+        let mut v = Validator::default();
+        v.grid_col("index", index);
+        v.non_blank("category", &category)
+            .max_length("category", &category, MAX_CATEGORY_LENGTH);
+        v.finish()?;
 
         let pool = ctx.data::<DBPool>().map_err(|e| {
             async_graphql::Error::new(format!("Cannot get DBPool from context: {:?}", e))
@@ -142,10 +270,338 @@ impl GameBoardMutation {
             .await
             .map_err(|e| async_graphql::Error::new(format!("Failed to get connection: {}", e)))?;
 
+        require_board_access(ctx, &mut conn, game_board_id, BoardAccess::Edit).await?;
+
         let updated: GameBoard =
-            GameBoard::update_game_board_category(&mut conn, game_board_id, index, category)
-                .await?;
+            GameBoardRevision::with_revision(&mut conn, game_board_id, move |conn| {
+                GameBoard::update_game_board_category(conn, game_board_id, index, category)
+                    .scope_boxed()
+            })
+            .await?;
+
+        Ok(updated)
+    }
+
+    /// Archive a gameboard. It disappears from listings but can be restored
+    async fn delete_game_board(&self, ctx: &Context<'_>, game_board_id: i64) -> Result<bool> {
+        let pool = ctx.data::<DBPool>().map_err(|e| {
+            async_graphql::Error::new(format!("Cannot get DBPool from context: {:?}", e))
+        })?;
+        let mut conn = pool
+            .get()
+            .await
+            .map_err(|e| async_graphql::Error::new(format!("Failed to get connection: {}", e)))?;
+
+        let requestor = require_user(ctx, &mut conn).await?;
+        let game_board = GameBoard::find_by_id(&mut conn, game_board_id).await?;
+        require_owner(&requestor, game_board.user_id, "game board")?;
+
+        let archived = GameBoard::archive(&mut conn, game_board_id).await?;
+        Ok(archived.archived_at.is_some())
+    }
+
+    /// Restore an archived gameboard
+    async fn restore_game_board(&self, ctx: &Context<'_>, game_board_id: i64) -> Result<GameBoard> {
+        let pool = ctx.data::<DBPool>().map_err(|e| {
+            async_graphql::Error::new(format!("Cannot get DBPool from context: {:?}", e))
+        })?;
+        let mut conn = pool
+            .get()
+            .await
+            .map_err(|e| async_graphql::Error::new(format!("Failed to get connection: {}", e)))?;
+
+        let requestor = require_user(ctx, &mut conn).await?;
+        let game_board = GameBoard::find_by_id(&mut conn, game_board_id).await?;
+        require_owner(&requestor, game_board.user_id, "game board")?;
+
+        let restored = GameBoard::restore(&mut conn, game_board_id).await?;
+        Ok(restored)
+    }
+
+    /// Permanently delete a gameboard along with its mappings and games
+    async fn purge_game_board(&self, ctx: &Context<'_>, game_board_id: i64) -> Result<bool> {
+        let pool = ctx.data::<DBPool>().map_err(|e| {
+            async_graphql::Error::new(format!("Cannot get DBPool from context: {:?}", e))
+        })?;
+        let mut conn = pool
+            .get()
+            .await
+            .map_err(|e| async_graphql::Error::new(format!("Failed to get connection: {}", e)))?;
+
+        let requestor = require_user(ctx, &mut conn).await?;
+        let game_board = GameBoard::find_by_id(&mut conn, game_board_id).await?;
+        require_owner(&requestor, game_board.user_id, "game board")?;
+
+        let rows_deleted = GameBoard::purge(&mut conn, game_board_id).await?;
+        Ok(rows_deleted > 0)
+    }
+
+    /// Copy a gameboard and all of its cells into the requestor's boards.
+    /// With `deep_copy_questions` the questions are duplicated into the
+    /// requestor's library, otherwise the copy shares the original questions
+    async fn clone_game_board(
+        &self,
+        ctx: &Context<'_>,
+        board_id: i64,
+        new_title: String,
+        deep_copy_questions: bool,
+        share_token: Option<String>,
+    ) -> Result<GameBoard> {
+        let mut v = Validator::default();
+        v.non_blank("newTitle", &new_title)
+            .max_length("newTitle", &new_title, MAX_TITLE_LENGTH);
+        v.finish()?;
+
+        let pool = ctx.data::<DBPool>().map_err(|e| {
+            async_graphql::Error::new(format!("Cannot get DBPool from context: {:?}", e))
+        })?;
+        let mut conn = pool
+            .get()
+            .await
+            .map_err(|e| async_graphql::Error::new(format!("Failed to get connection: {}", e)))?;
+
+        // Anything the requestor can see, or holds the share link of, may be cloned
+        let requestor = match share_token {
+            Some(share_token) => {
+                let requestor = require_user(ctx, &mut conn).await?;
+                require_share_token(&mut conn, board_id, &share_token).await?;
+                requestor
+            }
+            None => {
+                require_board_access(ctx, &mut conn, board_id, BoardAccess::View)
+                    .await?
+                    .0
+            }
+        };
+
+        let cloned = match GameBoard::clone_board(
+            &mut conn,
+            board_id,
+            requestor.id,
+            new_title,
+            deep_copy_questions,
+        )
+        .await
+        {
+            Ok(board) => board,
+            Err(diesel::NotFound) => return Err(async_graphql::Error::new("Gameboard not found")),
+            Err(e) => {
+                return Err(async_graphql::Error::new(format!(
+                    "Database error: {:?}",
+                    e
+                )));
+            }
+        };
+
+        Ok(cloned)
+    }
+
+    /// Build a gameboard from the question library, filling each category
+    /// with questions from easiest to hardest
+    async fn generate_game_board(
+        &self,
+        ctx: &Context<'_>,
+        input: GenerateGameBoardInput,
+    ) -> Result<GeneratedGameBoard> {
+        input.validated()?;
+
+        let pool = ctx.data::<DBPool>().map_err(|e| {
+            async_graphql::Error::new(format!("Cannot get DBPool from context: {:?}", e))
+        })?;
+        let mut conn = pool
+            .get()
+            .await
+            .map_err(|e| async_graphql::Error::new(format!("Failed to get connection: {}", e)))?;
+
+        let requestor = require_user(ctx, &mut conn).await?;
+
+        let seed = input
+            .seed
+            .unwrap_or_else(|| rand::random_range(0..i64::MAX));
+        let categories = input.categories.unwrap_or_default();
+        let options = GeneratorOptions {
+            title: input.title,
+            rows: input.rows.unwrap_or(GRID_ROWS),
+            cols: match categories.len() {
+                0 => input.cols.unwrap_or(GRID_COLS),
+                n => n as i32,
+            },
+            categories,
+            min_difficulty: input.min_difficulty,
+            max_difficulty: input.max_difficulty,
+            source: input.source.unwrap_or_default(),
+            base_points: input.base_points.unwrap_or(DEFAULT_BASE_POINTS),
+            daily_doubles: input.daily_doubles.unwrap_or(DEFAULT_DAILY_DOUBLES),
+            recent_games: input.avoid_recent_games.unwrap_or(DEFAULT_RECENT_GAMES),
+            seed: seed as u64,
+        };
+
+        let game_board = generate_board(&mut conn, requestor.id, options).await?;
+        Ok(GeneratedGameBoard { game_board, seed })
+    }
+
+    /// Move a category column, with its questions, to a new position
+    async fn move_column(
+        &self,
+        ctx: &Context<'_>,
+        game_board_id: i64,
+        from_col: i32,
+        to_col: i32,
+    ) -> Result<GameBoard> {
+        let mut v = Validator::default();
+        v.grid_col("fromCol", from_col);
+        v.grid_col("toCol", to_col);
+        v.finish()?;
 
+        let pool = ctx.data::<DBPool>().map_err(|e| {
+            async_graphql::Error::new(format!("Cannot get DBPool from context: {:?}", e))
+        })?;
+        let mut conn = pool
+            .get()
+            .await
+            .map_err(|e| async_graphql::Error::new(format!("Failed to get connection: {}", e)))?;
+
+        require_board_access(ctx, &mut conn, game_board_id, BoardAccess::Edit).await?;
+
+        let updated = GameBoardRevision::with_revision(&mut conn, game_board_id, move |conn| {
+            GameBoard::move_column(conn, game_board_id, from_col, to_col).scope_boxed()
+        })
+        .await?;
         Ok(updated)
     }
+
+    /// Restore a gameboard's title, categories and cells to an earlier revision.
+    /// The revert is itself recorded as a new revision
+    async fn revert_game_board(
+        &self,
+        ctx: &Context<'_>,
+        board_id: i64,
+        revision_id: i64,
+    ) -> Result<GameBoard> {
+        let pool = ctx.data::<DBPool>().map_err(|e| {
+            async_graphql::Error::new(format!("Cannot get DBPool from context: {:?}", e))
+        })?;
+        let mut conn = pool
+            .get()
+            .await
+            .map_err(|e| async_graphql::Error::new(format!("Failed to get connection: {}", e)))?;
+
+        require_board_access(ctx, &mut conn, board_id, BoardAccess::Edit).await?;
+
+        match GameBoardRevision::revert(&mut conn, board_id, revision_id).await {
+            Ok(board) => Ok(board),
+            Err(diesel::NotFound) => Err(async_graphql::Error::new(
+                "Revision not found for this game board",
+            )),
+            Err(e) => Err(async_graphql::Error::new(format!(
+                "Database error: {:?}",
+                e
+            ))),
+        }
+    }
+
+    /// Invite a user to collaborate on a gameboard, or change their role
+    async fn invite_collaborator(
+        &self,
+        ctx: &Context<'_>,
+        board_id: i64,
+        user_id: i64,
+        role: CollaboratorRole,
+    ) -> Result<BoardCollaborator> {
+        let pool = ctx.data::<DBPool>().map_err(|e| {
+            async_graphql::Error::new(format!("Cannot get DBPool from context: {:?}", e))
+        })?;
+        let mut conn = pool
+            .get()
+            .await
+            .map_err(|e| async_graphql::Error::new(format!("Failed to get connection: {}", e)))?;
+
+        let (requestor, _) =
+            require_board_access(ctx, &mut conn, board_id, BoardAccess::Own).await?;
+        if requestor.id == user_id {
+            return Err(async_graphql::Error::new(
+                "The owner cannot be invited to their own game board",
+            ));
+        }
+        if User::find_by_id(&mut conn, user_id).await.is_err() {
+            return Err(async_graphql::Error::new("User not found"));
+        }
+
+        let collaborator = BoardCollaborator::upsert(
+            &mut conn,
+            NewBoardCollaborator {
+                board_id,
+                user_id,
+                role,
+            },
+        )
+        .await?;
+        Ok(collaborator)
+    }
+
+    /// Remove a collaborator from a gameboard. Collaborators may also remove
+    /// themselves
+    async fn remove_collaborator(
+        &self,
+        ctx: &Context<'_>,
+        board_id: i64,
+        user_id: i64,
+    ) -> Result<bool> {
+        let pool = ctx.data::<DBPool>().map_err(|e| {
+            async_graphql::Error::new(format!("Cannot get DBPool from context: {:?}", e))
+        })?;
+        let mut conn = pool
+            .get()
+            .await
+            .map_err(|e| async_graphql::Error::new(format!("Failed to get connection: {}", e)))?;
+
+        let requestor = require_user(ctx, &mut conn).await?;
+        if requestor.id != user_id {
+            require_board_access(ctx, &mut conn, board_id, BoardAccess::Own).await?;
+        }
+
+        let rows_deleted = BoardCollaborator::remove(&mut conn, board_id, user_id).await?;
+        Ok(rows_deleted > 0)
+    }
+
+    /// Make a gameboard private, unlisted (reachable by share link) or public
+    async fn set_board_visibility(
+        &self,
+        ctx: &Context<'_>,
+        board_id: i64,
+        visibility: BoardVisibility,
+    ) -> Result<BoardSharing> {
+        let pool = ctx.data::<DBPool>().map_err(|e| {
+            async_graphql::Error::new(format!("Cannot get DBPool from context: {:?}", e))
+        })?;
+        let mut conn = pool
+            .get()
+            .await
+            .map_err(|e| async_graphql::Error::new(format!("Failed to get connection: {}", e)))?;
+
+        require_board_access(ctx, &mut conn, board_id, BoardAccess::Own).await?;
+
+        let updated = GameBoard::set_visibility(&mut conn, board_id, visibility).await?;
+        Ok(board_sharing(&mut conn, updated).await?)
+    }
+
+    /// Replace a gameboard's share link, so the old link stops working
+    async fn regenerate_share_token(
+        &self,
+        ctx: &Context<'_>,
+        board_id: i64,
+    ) -> Result<BoardSharing> {
+        let pool = ctx.data::<DBPool>().map_err(|e| {
+            async_graphql::Error::new(format!("Cannot get DBPool from context: {:?}", e))
+        })?;
+        let mut conn = pool
+            .get()
+            .await
+            .map_err(|e| async_graphql::Error::new(format!("Failed to get connection: {}", e)))?;
+
+        require_board_access(ctx, &mut conn, board_id, BoardAccess::Own).await?;
+
+        let updated = GameBoard::regenerate_share_token(&mut conn, board_id).await?;
+        Ok(board_sharing(&mut conn, updated).await?)
+    }
 }
@@ -1,11 +1,15 @@
 // src/graphql/mutation/game_board_question_mapping.rs
 
 use async_graphql::{Context, InputObject, Object, Result};
+use diesel_async::scoped_futures::ScopedFutureExt;
 
+use crate::auth::firebase_auth::require_owner;
 use crate::db::pool::DBPool;
-use crate::models::{
-    game_board::GameBoard, question::Question, GBQMapping, NewGBQMapping, UpdateGBQMapping,
-};
+use crate::graphql::validation::{Validate, Validator, GRID_ROWS, MAX_POINTS};
+use crate::models::game_board_question_mapping::is_cell_collision;
+use crate::models::game_board_revision::GameBoardRevision;
+use crate::models::{question::Question, GBQMapping, NewGBQMapping, UpdateGBQMapping};
+use crate::services::board_access::{require_board_access, BoardAccess};
 
 #[derive(InputObject)]
 pub struct CreateGameBoardMappingInput {
@@ -27,6 +31,46 @@ pub struct UpdateGameBoardMappingInput {
     pub grid_col: Option<i32>,
 }
 
+impl Validate for CreateGameBoardMappingInput {
+    fn validate(&self, v: &mut Validator) {
+        v.id("boardId", self.board_id);
+        v.id("questionId", self.question_id);
+        v.range("points", self.points, 0, MAX_POINTS);
+        v.grid_row("gridRow", self.grid_row);
+        v.grid_col("gridCol", self.grid_col);
+    }
+}
+
+impl Validate for UpdateGameBoardMappingInput {
+    fn validate(&self, v: &mut Validator) {
+        v.id("boardId", self.board_id);
+        v.id("questionId", self.question_id);
+        if let Some(points) = self.points {
+            v.range("points", points, 0, MAX_POINTS);
+        }
+        if let Some(row) = self.grid_row {
+            v.grid_row("gridRow", row);
+        }
+        if let Some(col) = self.grid_col {
+            v.grid_col("gridCol", col);
+        }
+    }
+}
+
+/// A position on the game board grid
+#[derive(InputObject)]
+pub struct GridCellInput {
+    pub row: i32,
+    pub col: i32,
+}
+
+impl Validate for GridCellInput {
+    fn validate(&self, v: &mut Validator) {
+        v.grid_row("row", self.row);
+        v.grid_col("col", self.col);
+    }
+}
+
 #[derive(Default)]
 
 pub struct GameBoardMappingMutation;
@@ -39,6 +83,8 @@ impl GameBoardMappingMutation {
         ctx: &Context<'_>,
         input: CreateGameBoardMappingInput,
     ) -> Result<GBQMapping> {
+        input.validated()?;
+
         let pool = ctx.data::<DBPool>().map_err(|e| {
             async_graphql::Error::new(format!("Cannot get DBPool from context: {:?}", e))
         })?;
@@ -47,17 +93,21 @@ impl GameBoardMappingMutation {
             .await
             .map_err(|e| async_graphql::Error::new(format!("Failed to get connection: {}", e)))?;
 
-        // Validate GameBoard exists
-        let _board = match GameBoard::find_by_id(&mut conn, input.board_id).await {
-            Ok(b) => b,
-            Err(_) => return Err(async_graphql::Error::new("GameBoard not found")),
-        };
+        let (requestor, board) =
+            require_board_access(ctx, &mut conn, input.board_id, BoardAccess::Edit).await?;
+        if board.archived_at.is_some() {
+            return Err(async_graphql::Error::new("GameBoard is archived"));
+        }
 
         // Validate Question Exists
-        let _question = match Question::find_by_id(&mut conn, input.question_id).await {
+        let question = match Question::find_by_id(&mut conn, input.question_id).await {
             Ok(q) => q,
             Err(_) => return Err(async_graphql::Error::new("Question not found")),
         };
+        require_owner(&requestor, question.user_id, "question")?;
+        if question.archived_at.is_some() {
+            return Err(async_graphql::Error::new("Question is archived"));
+        }
 
         // Check for existing mapping between question and board
         if let Ok(_existing_mapping) = GBQMapping::find_mapping_by_board_and_question(
@@ -95,10 +145,14 @@ impl GameBoardMappingMutation {
             grid_col: input.grid_col,
         };
 
-        let mapping = GBQMapping::create_mapping(&mut conn, new_mapping).await?;
+        let mapping = GameBoardRevision::with_revision(&mut conn, input.board_id, move |conn| {
+            GBQMapping::create_mapping(conn, new_mapping).scope_boxed()
+        })
+        .await?;
         Ok(mapping)
     }
 
+    /// Change a mapping's points, Daily Double flag or cell
     async fn update_mapping(
         &self,
         ctx: &Context<'_>,
@@ -112,6 +166,8 @@ impl GameBoardMappingMutation {
             .await
             .map_err(|e| async_graphql::Error::new(format!("Failed to get connection: {}", e)))?;
 
+        require_board_access(ctx, &mut conn, input.board_id, BoardAccess::Edit).await?;
+
         let existing_mapping_result = GBQMapping::find_mapping_by_board_and_question(
             &mut conn,
             input.board_id,
@@ -119,7 +175,7 @@ impl GameBoardMappingMutation {
         )
         .await;
 
-        let _existing_mapping = match existing_mapping_result {
+        let existing_mapping = match existing_mapping_result {
             Ok(mapping) => mapping,
             Err(diesel::NotFound) => return Err(async_graphql::Error::new("Mapping not found")),
             Err(e) => {
@@ -131,9 +187,24 @@ impl GameBoardMappingMutation {
         };
 
         // Input validation
-        if let Some(p) = input.points {
-            if p < 0 {
-                return Err(async_graphql::Error::new("Points must be positive"));
+        input.validated()?;
+
+        // Moving onto an occupied cell would collide; use swapCells instead
+        let target_row = input.grid_row.unwrap_or(existing_mapping.grid_row);
+        let target_col = input.grid_col.unwrap_or(existing_mapping.grid_col);
+        if let Ok(occupant) = GBQMapping::find_mapping_by_row_and_col(
+            &mut conn,
+            input.board_id,
+            target_row,
+            target_col,
+        )
+        .await
+        {
+            if occupant.question_id != input.question_id {
+                return Err(async_graphql::Error::new(format!(
+                    "Cell [{},{}] is already occupied",
+                    target_row, target_col
+                )));
             }
         }
 
@@ -144,14 +215,254 @@ impl GameBoardMappingMutation {
             grid_col: input.grid_col,
         };
 
-        let updated: GBQMapping = GBQMapping::update_mapping(
-            &mut conn,
-            input.board_id,
-            input.question_id,
-            updated_fields,
-        )
+        let (board_id, question_id) = (input.board_id, input.question_id);
+        let updated: GBQMapping =
+            GameBoardRevision::with_revision(&mut conn, board_id, move |conn| {
+                GBQMapping::update_mapping(conn, board_id, question_id, updated_fields)
+                    .scope_boxed()
+            })
+            .await
+            .map_err(|e| {
+                if is_cell_collision(&e) {
+                    async_graphql::Error::new(format!(
+                        "Cell [{},{}] is already occupied",
+                        target_row, target_col
+                    ))
+                } else {
+                    e.into()
+                }
+            })?;
+        Ok(updated)
+    }
+
+    /// Swap the questions in two cells of a gameboard atomically
+    async fn swap_cells(
+        &self,
+        ctx: &Context<'_>,
+        board_id: i64,
+        first: GridCellInput,
+        second: GridCellInput,
+    ) -> Result<Vec<GBQMapping>> {
+        let mut v = Validator::default();
+        first.validate(&mut v);
+        second.validate(&mut v);
+        v.finish()?;
+
+        let pool = ctx.data::<DBPool>().map_err(|e| {
+            async_graphql::Error::new(format!("Cannot get DBPool from context: {:?}", e))
+        })?;
+        let mut conn = pool
+            .get()
+            .await
+            .map_err(|e| async_graphql::Error::new(format!("Failed to get connection: {}", e)))?;
+
+        require_board_access(ctx, &mut conn, board_id, BoardAccess::Edit).await?;
+
+        let (first, second) = ((first.row, first.col), (second.row, second.col));
+        match GameBoardRevision::with_revision(&mut conn, board_id, move |conn| {
+            GBQMapping::swap_cells(conn, board_id, first, second).scope_boxed()
+        })
+        .await
+        {
+            Ok(moved) => Ok(moved),
+            Err(diesel::NotFound) => Err(async_graphql::Error::new("Both cells are empty")),
+            Err(e) => Err(async_graphql::Error::new(format!(
+                "Database error: {:?}",
+                e
+            ))),
+        }
+    }
+
+    /// Reorder the rows of a gameboard. `row_order[i]` is the current row
+    /// that should move to row `i`
+    async fn reorder_rows(
+        &self,
+        ctx: &Context<'_>,
+        board_id: i64,
+        row_order: Vec<i32>,
+    ) -> Result<Vec<GBQMapping>> {
+        let mut sorted = row_order.clone();
+        sorted.sort_unstable();
+        if sorted != (0..GRID_ROWS).collect::<Vec<i32>>() {
+            let mut v = Validator::default();
+            v.add(
+                "rowOrder",
+                format!("must be a permutation of the rows 0 - {}", GRID_ROWS - 1),
+            );
+            v.finish()?;
+        }
+
+        let pool = ctx.data::<DBPool>().map_err(|e| {
+            async_graphql::Error::new(format!("Cannot get DBPool from context: {:?}", e))
+        })?;
+        let mut conn = pool
+            .get()
+            .await
+            .map_err(|e| async_graphql::Error::new(format!("Failed to get connection: {}", e)))?;
+
+        require_board_access(ctx, &mut conn, board_id, BoardAccess::Edit).await?;
+
+        let mappings = GameBoardRevision::with_revision(&mut conn, board_id, move |conn| {
+            GBQMapping::reorder_rows(conn, board_id, row_order).scope_boxed()
+        })
         .await?;
+        Ok(mappings)
+    }
 
-        Ok(updated)
+    /// Set every cell's points to `basePoints * (row + 1)`. Defaults to 100
+    async fn assign_points_by_row(
+        &self,
+        ctx: &Context<'_>,
+        board_id: i64,
+        base_points: Option<i32>,
+    ) -> Result<Vec<GBQMapping>> {
+        let base_points = base_points.unwrap_or(100);
+        let mut v = Validator::default();
+        v.range("basePoints", base_points, 1, MAX_POINTS / GRID_ROWS);
+        v.finish()?;
+
+        let pool = ctx.data::<DBPool>().map_err(|e| {
+            async_graphql::Error::new(format!("Cannot get DBPool from context: {:?}", e))
+        })?;
+        let mut conn = pool
+            .get()
+            .await
+            .map_err(|e| async_graphql::Error::new(format!("Failed to get connection: {}", e)))?;
+
+        require_board_access(ctx, &mut conn, board_id, BoardAccess::Edit).await?;
+
+        let mappings = GameBoardRevision::with_revision(&mut conn, board_id, move |conn| {
+            GBQMapping::assign_points_by_row(conn, board_id, base_points).scope_boxed()
+        })
+        .await?;
+        Ok(mappings)
+    }
+
+    /// Remove a question from a gameboard without deleting the question
+    async fn delete_mapping(
+        &self,
+        ctx: &Context<'_>,
+        board_id: i64,
+        question_id: i64,
+    ) -> Result<GBQMapping> {
+        let pool = ctx.data::<DBPool>().map_err(|e| {
+            async_graphql::Error::new(format!("Cannot get DBPool from context: {:?}", e))
+        })?;
+        let mut conn = pool
+            .get()
+            .await
+            .map_err(|e| async_graphql::Error::new(format!("Failed to get connection: {}", e)))?;
+
+        require_board_access(ctx, &mut conn, board_id, BoardAccess::Edit).await?;
+
+        match GameBoardRevision::with_revision(&mut conn, board_id, move |conn| {
+            GBQMapping::delete_mapping(conn, board_id, question_id).scope_boxed()
+        })
+        .await
+        {
+            Ok(mapping) => Ok(mapping),
+            Err(diesel::NotFound) => Err(async_graphql::Error::new("Mapping not found")),
+            Err(e) => Err(async_graphql::Error::new(format!(
+                "Database error: {:?}",
+                e
+            ))),
+        }
+    }
+
+    /// Empty a cell of a gameboard without deleting its question
+    async fn clear_cell(
+        &self,
+        ctx: &Context<'_>,
+        board_id: i64,
+        cell: GridCellInput,
+    ) -> Result<GBQMapping> {
+        cell.validated()?;
+
+        let pool = ctx.data::<DBPool>().map_err(|e| {
+            async_graphql::Error::new(format!("Cannot get DBPool from context: {:?}", e))
+        })?;
+        let mut conn = pool
+            .get()
+            .await
+            .map_err(|e| async_graphql::Error::new(format!("Failed to get connection: {}", e)))?;
+
+        require_board_access(ctx, &mut conn, board_id, BoardAccess::Edit).await?;
+
+        let (row, col) = (cell.row, cell.col);
+        match GameBoardRevision::with_revision(&mut conn, board_id, move |conn| {
+            GBQMapping::clear_cell(conn, board_id, row, col).scope_boxed()
+        })
+        .await
+        {
+            Ok(mapping) => Ok(mapping),
+            Err(diesel::NotFound) => Err(async_graphql::Error::new(format!(
+                "Cell [{},{}] is already empty",
+                cell.row, cell.col
+            ))),
+            Err(e) => Err(async_graphql::Error::new(format!(
+                "Database error: {:?}",
+                e
+            ))),
+        }
+    }
+
+    /// Put a different question in an occupied cell, keeping its points and
+    /// Daily Double flag. The replaced question is not deleted
+    async fn replace_cell_question(
+        &self,
+        ctx: &Context<'_>,
+        board_id: i64,
+        cell: GridCellInput,
+        question_id: i64,
+    ) -> Result<GBQMapping> {
+        cell.validated()?;
+
+        let pool = ctx.data::<DBPool>().map_err(|e| {
+            async_graphql::Error::new(format!("Cannot get DBPool from context: {:?}", e))
+        })?;
+        let mut conn = pool
+            .get()
+            .await
+            .map_err(|e| async_graphql::Error::new(format!("Failed to get connection: {}", e)))?;
+
+        let (requestor, _) =
+            require_board_access(ctx, &mut conn, board_id, BoardAccess::Edit).await?;
+
+        // Validate Question Exists
+        let question = match Question::find_by_id(&mut conn, question_id).await {
+            Ok(q) => q,
+            Err(_) => return Err(async_graphql::Error::new("Question not found")),
+        };
+        require_owner(&requestor, question.user_id, "question")?;
+        if question.archived_at.is_some() {
+            return Err(async_graphql::Error::new("Question is archived"));
+        }
+
+        // A question can only appear once per board
+        if let Ok(existing) =
+            GBQMapping::find_mapping_by_board_and_question(&mut conn, board_id, question_id).await
+        {
+            return Err(async_graphql::Error::new(format!(
+                "Question is already on this board at [{},{}]",
+                existing.grid_row, existing.grid_col
+            )));
+        }
+
+        let (row, col) = (cell.row, cell.col);
+        match GameBoardRevision::with_revision(&mut conn, board_id, move |conn| {
+            GBQMapping::replace_question(conn, board_id, row, col, question_id).scope_boxed()
+        })
+        .await
+        {
+            Ok(mapping) => Ok(mapping),
+            Err(diesel::NotFound) => Err(async_graphql::Error::new(format!(
+                "Cell [{},{}] is empty; use createMapping",
+                cell.row, cell.col
+            ))),
+            Err(e) => Err(async_graphql::Error::new(format!(
+                "Database error: {:?}",
+                e
+            ))),
+        }
     }
 }
@@ -1,8 +1,15 @@
 // graphql/mutations/player.rs
 
+use crate::auth::firebase_auth::{require_owner, require_user};
 use crate::db::pool::DBPool;
+use crate::graphql::validation::{Validate, Validator};
+use crate::models::game::Game;
+use crate::models::game_event::{GameEvent, GameEventData, GameEventType};
 use crate::models::player::{NewPlayer, Player, UpdatePlayer};
+use crate::services::board_control::{hand_control, ControlHub};
 use async_graphql::{Context, InputObject, Object, Result};
+use diesel_async::scoped_futures::ScopedFutureExt;
+use diesel_async::AsyncConnection;
 
 #[derive(InputObject)]
 pub struct CreatePlayerInput {
@@ -10,6 +17,13 @@ pub struct CreatePlayerInput {
     pub player_name: String,
 }
 
+impl Validate for CreatePlayerInput {
+    fn validate(&self, v: &mut Validator) {
+        v.id("gameId", self.game_id);
+        v.display_name("playerName", &self.player_name);
+    }
+}
+
 #[derive(Default)]
 pub struct PlayerMutation;
 
@@ -17,6 +31,8 @@ pub struct PlayerMutation;
 impl PlayerMutation {
     /// Create a new player
     async fn create_player(&self, ctx: &Context<'_>, input: CreatePlayerInput) -> Result<Player> {
+        input.validated()?;
+
         let pool = ctx.data::<DBPool>().map_err(|e| {
             async_graphql::Error::new(format!("Cannot get DBPool from context: {:?}", e))
         })?;
@@ -34,6 +50,8 @@ impl PlayerMutation {
         Ok(player)
     }
 
+    /// Set a player's score directly. Raising it credits a correct response,
+    /// so the player also takes control of the board. Host only
     async fn update_player_score(
         &self,
         ctx: &Context<'_>,
@@ -43,20 +61,55 @@ impl PlayerMutation {
         let pool = ctx.data::<DBPool>().map_err(|e| {
             async_graphql::Error::new(format!("Cannot get DBPool from context: {:?}", e))
         })?;
+        let control_hub = ctx.data::<ControlHub>().map_err(|e| {
+            async_graphql::Error::new(format!("Cannot get ControlHub from context: {:?}", e))
+        })?;
         let mut conn = pool
             .get()
             .await
             .map_err(|e| async_graphql::Error::new(format!("Failed to get connection: {}", e)))?;
 
-        let updated_player = Player::update_player(
-            &mut conn,
-            player_id,
-            UpdatePlayer {
-                player_name: None,
-                score: Some(score),
-            },
-        )
-        .await?;
+        let requestor = require_user(ctx, &mut conn).await?;
+        let player = Player::find_by_id(&mut conn, player_id).await?;
+        let game = Game::find_by_id(&mut conn, player.game_id).await?;
+        require_owner(&requestor, game.user_id, "game")?;
+        let (updated_player, control) = conn
+            .transaction::<_, diesel::result::Error, _>(|conn| {
+                async move {
+                    let updated_player = Player::update_player(
+                        conn,
+                        player_id,
+                        UpdatePlayer {
+                            player_name: None,
+                            score: Some(score),
+                        },
+                    )
+                    .await?;
+                    GameEvent::record(
+                        conn,
+                        updated_player.game_id,
+                        GameEventType::ScoreChanged,
+                        GameEventData {
+                            player_id: Some(player_id),
+                            points: Some(score - player.score),
+                            score: Some(score),
+                            ..Default::default()
+                        },
+                    )
+                    .await?;
+                    let control = if score > player.score {
+                        hand_control(conn, player.game_id, Some(player_id), None).await?
+                    } else {
+                        None
+                    };
+                    Ok((updated_player, control))
+                }
+                .scope_boxed()
+            })
+            .await?;
+        if let Some(control) = &control {
+            control_hub.publish(control);
+        }
         Ok(updated_player)
     }
 
@@ -66,6 +119,10 @@ impl PlayerMutation {
         player_id: i64,
         player_name: String,
     ) -> Result<Player> {
+        let mut v = Validator::default();
+        v.display_name("playerName", &player_name);
+        v.finish()?;
+
         let pool = ctx.data::<DBPool>().map_err(|e| {
             async_graphql::Error::new(format!("Cannot get DBPool from context: {:?}", e))
         })?;
@@ -1,16 +1,38 @@
 // graphql/mutatons/question.rs
 
-use crate::auth::firebase_auth::require_auth;
+use crate::auth::firebase_auth::{require_auth, require_owner, require_user};
 use crate::db::pool::DBPool;
+use crate::graphql::validation::{
+    Validate, Validator, MAX_ALTERNATE_ANSWERS, MAX_ANSWER_LENGTH, MAX_QUESTION_LENGTH, MAX_TAGS,
+    MAX_TAG_LENGTH,
+};
+use crate::models::game_board_revision::GameBoardRevision;
 use crate::models::question::{NewQuestion, Question, UpdateQuestion};
+use crate::models::question_media::QuestionMedia;
 use crate::models::user::User;
-use async_graphql::{Context, InputObject, Object, Result};
+use crate::services::board_generator::normalize_tags;
+use crate::services::localization::normalize_locale;
+use crate::services::media::{attach_media, detach_media};
+use crate::services::rich_text::sanitize_markdown;
+use crate::storage::MediaStore;
+use async_graphql::{Context, InputObject, Object, Result, Upload};
+use diesel_async::scoped_futures::ScopedFutureExt;
+use diesel_async::AsyncConnection;
+use std::io::Read;
 
 #[derive(InputObject)]
 pub struct CreateQuestionInput {
     pub user_id: i64,
+    /// Markdown: emphasis, code, `$math$` and line breaks.
     pub question: String,
+    /// Markdown, like `question`.
     pub answer: String,
+    /// Other responses to accept besides `answer`.
+    pub alternate_answers: Option<Vec<String>>,
+    /// Topics to file the question under, for the board generator.
+    pub tags: Option<Vec<String>>,
+    /// From 1 (easiest) to 5.
+    pub difficulty: Option<i32>,
 }
 
 #[derive(InputObject)]
@@ -18,6 +40,85 @@ pub struct UpdateQuestionInput {
     pub id: i64,
     pub question: Option<String>,
     pub answer: Option<String>,
+    pub alternate_answers: Option<Vec<String>>,
+    /// The locale the question is written in, such as `en` or `es-MX`.
+    pub locale: Option<String>,
+    pub tags: Option<Vec<String>>,
+    pub difficulty: Option<i32>,
+}
+
+/// Tags are checked after they have been normalized.
+fn validate_tags(v: &mut Validator, tags: &[String]) {
+    if tags.len() > MAX_TAGS {
+        v.add("tags", format!("must have at most {} entries", MAX_TAGS));
+    }
+    v.each("tags", tags, |v, field, tag| {
+        v.non_blank(field, tag)
+            .max_length(field, tag, MAX_TAG_LENGTH);
+    });
+}
+
+/// Each alternate answer follows the same rules as the answer itself.
+fn validate_alternate_answers(v: &mut Validator, alternates: &[String]) {
+    if alternates.len() > MAX_ALTERNATE_ANSWERS {
+        v.add(
+            "alternateAnswers",
+            format!("must have at most {} entries", MAX_ALTERNATE_ANSWERS),
+        );
+    }
+    v.each("alternateAnswers", alternates, |v, field, a| {
+        v.non_blank(field, a)
+            .max_length(field, a, MAX_ANSWER_LENGTH);
+    });
+}
+
+impl Validate for CreateQuestionInput {
+    fn validate(&self, v: &mut Validator) {
+        v.id("userId", self.user_id);
+        v.non_blank("question", &self.question)
+            .max_length("question", &self.question, MAX_QUESTION_LENGTH)
+            .markdown("question", &self.question);
+        v.non_blank("answer", &self.answer)
+            .max_length("answer", &self.answer, MAX_ANSWER_LENGTH)
+            .markdown("answer", &self.answer);
+        if let Some(ref alternates) = self.alternate_answers {
+            validate_alternate_answers(v, alternates);
+        }
+        if let Some(ref tags) = self.tags {
+            validate_tags(v, tags);
+        }
+        if let Some(difficulty) = self.difficulty {
+            v.difficulty("difficulty", difficulty);
+        }
+    }
+}
+
+impl Validate for UpdateQuestionInput {
+    fn validate(&self, v: &mut Validator) {
+        v.id("id", self.id);
+        if let Some(ref q) = self.question {
+            v.non_blank("question", q)
+                .max_length("question", q, MAX_QUESTION_LENGTH)
+                .markdown("question", q);
+        }
+        if let Some(ref a) = self.answer {
+            v.non_blank("answer", a)
+                .max_length("answer", a, MAX_ANSWER_LENGTH)
+                .markdown("answer", a);
+        }
+        if let Some(ref alternates) = self.alternate_answers {
+            validate_alternate_answers(v, alternates);
+        }
+        if let Some(ref locale) = self.locale {
+            v.locale("locale", locale);
+        }
+        if let Some(ref tags) = self.tags {
+            validate_tags(v, tags);
+        }
+        if let Some(difficulty) = self.difficulty {
+            v.difficulty("difficulty", difficulty);
+        }
+    }
 }
 
 #[derive(Default)]
@@ -29,8 +130,13 @@ impl QuestionMutation {
     async fn create_question(
         &self,
         ctx: &Context<'_>,
-        input: CreateQuestionInput,
+        mut input: CreateQuestionInput,
     ) -> Result<Question> {
+        input.question = sanitize_markdown(&input.question);
+        input.answer = sanitize_markdown(&input.answer);
+        input.tags = input.tags.as_deref().map(normalize_tags);
+        input.validated()?;
+
         let pool = ctx.data::<DBPool>().map_err(|e| {
             async_graphql::Error::new(format!("Cannot get DBPool from context: {:?}", e))
         })?;
@@ -43,6 +149,9 @@ impl QuestionMutation {
             user_id: input.user_id,
             question: input.question,
             answer: input.answer,
+            alternate_answers: input.alternate_answers.unwrap_or_default(),
+            tags: input.tags.unwrap_or_default(),
+            difficulty: input.difficulty,
         };
         let question: Question = Question::create(&mut conn, new_question).await?;
         Ok(question)
@@ -52,7 +161,7 @@ impl QuestionMutation {
     async fn update_question(
         &self,
         ctx: &Context<'_>,
-        input: UpdateQuestionInput,
+        mut input: UpdateQuestionInput,
     ) -> Result<Question> {
         let pool = ctx.data::<DBPool>().map_err(|e| {
             async_graphql::Error::new(format!("Cannot get DBPool from context: {:?}", e))
@@ -92,33 +201,41 @@ impl QuestionMutation {
         }
 
         // Input Validation
-        if let Some(ref q) = input.question {
-            if q.trim().is_empty() {
-                return Err(async_graphql::Error::new("Question cannot be empty"));
-            }
-        }
-
-        if let Some(ref a) = input.answer {
-            if a.trim().is_empty() {
-                return Err(async_graphql::Error::new("Answer cannot be empty"));
-            }
-        }
+        input.question = input.question.as_deref().map(sanitize_markdown);
+        input.answer = input.answer.as_deref().map(sanitize_markdown);
+        input.tags = input.tags.as_deref().map(normalize_tags);
+        input.validated()?;
 
         // Prepare the updated fields using UpdateQuestion struct
         let updated_fields = UpdateQuestion {
             question: input.question.clone(),
             answer: input.answer.clone(),
+            alternate_answers: input.alternate_answers.clone(),
+            locale: input.locale.as_deref().and_then(normalize_locale),
+            tags: input.tags.clone(),
+            difficulty: input.difficulty,
         };
 
-        // Perform the update
-        let updated = Question::update_question(&mut conn, input.id, updated_fields).await?;
+        // Perform the update; every board showing this question has changed too
+        let question_id = input.id;
+        let updated = conn
+            .transaction::<_, diesel::result::Error, _>(|conn| {
+                async move {
+                    let updated =
+                        Question::update_question(conn, question_id, updated_fields).await?;
+                    GameBoardRevision::record_for_question(conn, updated.id).await?;
+                    Ok(updated)
+                }
+                .scope_boxed()
+            })
+            .await?;
 
         tracing::info!("Updated question: {:?}", updated);
 
         Ok(updated)
     }
 
-    /// Delete a question by ID
+    /// Archive a question. Boards already using it keep it; it can be restored
     async fn delete_question(&self, ctx: &Context<'_>, question_id: i64) -> Result<bool> {
         let pool = ctx.data::<DBPool>().map_err(|e| {
             async_graphql::Error::new(format!("Cannot get DBPool from context: {:?}", e))
@@ -128,7 +245,121 @@ impl QuestionMutation {
             .await
             .map_err(|e| async_graphql::Error::new(format!("Failed to get connection: {}", e)))?;
 
+        let requestor = require_user(ctx, &mut conn).await?;
+        let question = Question::find_by_id(&mut conn, question_id).await?;
+        require_owner(&requestor, question.user_id, "question")?;
+
+        let archived = Question::archive(&mut conn, question_id).await?;
+        Ok(archived.archived_at.is_some())
+    }
+
+    /// Restore an archived question
+    async fn restore_question(&self, ctx: &Context<'_>, question_id: i64) -> Result<Question> {
+        let pool = ctx.data::<DBPool>().map_err(|e| {
+            async_graphql::Error::new(format!("Cannot get DBPool from context: {:?}", e))
+        })?;
+        let mut conn = pool
+            .get()
+            .await
+            .map_err(|e| async_graphql::Error::new(format!("Failed to get connection: {}", e)))?;
+
+        let requestor = require_user(ctx, &mut conn).await?;
+        let question = Question::find_by_id(&mut conn, question_id).await?;
+        require_owner(&requestor, question.user_id, "question")?;
+
+        let restored = Question::restore(&mut conn, question_id).await?;
+        Ok(restored)
+    }
+
+    /// Permanently delete a question, removing it from every board that uses it
+    async fn purge_question(&self, ctx: &Context<'_>, question_id: i64) -> Result<bool> {
+        let pool = ctx.data::<DBPool>().map_err(|e| {
+            async_graphql::Error::new(format!("Cannot get DBPool from context: {:?}", e))
+        })?;
+        let mut conn = pool
+            .get()
+            .await
+            .map_err(|e| async_graphql::Error::new(format!("Failed to get connection: {}", e)))?;
+
+        let requestor = require_user(ctx, &mut conn).await?;
+        let question = Question::find_by_id(&mut conn, question_id).await?;
+        require_owner(&requestor, question.user_id, "question")?;
+
+        let media = QuestionMedia::fetch_by_question_id(&mut conn, question_id).await?;
         let rows_deleted = Question::delete_by_id(&mut conn, question_id).await?;
+
+        // The media rows went with the question; their files are left to remove
+        let storage = ctx.data::<MediaStore>().map_err(|e| {
+            async_graphql::Error::new(format!("Cannot get MediaStore from context: {:?}", e))
+        })?;
+        for file in media {
+            if let Err(e) = storage.delete(&file.storage_key).await {
+                tracing::warn!("Failed to remove {}: {}", file.storage_key, e);
+            }
+        }
+
         Ok(rows_deleted > 0) // Return true if a row was deleted
     }
+
+    /// Attach an image, audio or video file to a question
+    async fn upload_question_media(
+        &self,
+        ctx: &Context<'_>,
+        question_id: i64,
+        file: Upload,
+    ) -> Result<QuestionMedia> {
+        let pool = ctx.data::<DBPool>().map_err(|e| {
+            async_graphql::Error::new(format!("Cannot get DBPool from context: {:?}", e))
+        })?;
+        let storage = ctx.data::<MediaStore>().map_err(|e| {
+            async_graphql::Error::new(format!("Cannot get MediaStore from context: {:?}", e))
+        })?;
+        let mut conn = pool
+            .get()
+            .await
+            .map_err(|e| async_graphql::Error::new(format!("Failed to get connection: {}", e)))?;
+
+        let requestor = require_user(ctx, &mut conn).await?;
+        let question = Question::find_by_id(&mut conn, question_id).await?;
+        require_owner(&requestor, question.user_id, "question")?;
+
+        let upload = file.value(ctx)?;
+        let file_name = upload.filename.clone();
+        let content_type = upload.content_type.clone();
+        let mut bytes = Vec::new();
+        upload.into_read().read_to_end(&mut bytes)?;
+
+        let media = attach_media(
+            &mut conn,
+            storage.as_ref(),
+            question_id,
+            &file_name,
+            content_type.as_deref(),
+            bytes,
+        )
+        .await?;
+        Ok(media)
+    }
+
+    /// Remove a media file from its question
+    async fn delete_question_media(&self, ctx: &Context<'_>, media_id: i64) -> Result<bool> {
+        let pool = ctx.data::<DBPool>().map_err(|e| {
+            async_graphql::Error::new(format!("Cannot get DBPool from context: {:?}", e))
+        })?;
+        let storage = ctx.data::<MediaStore>().map_err(|e| {
+            async_graphql::Error::new(format!("Cannot get MediaStore from context: {:?}", e))
+        })?;
+        let mut conn = pool
+            .get()
+            .await
+            .map_err(|e| async_graphql::Error::new(format!("Failed to get connection: {}", e)))?;
+
+        let requestor = require_user(ctx, &mut conn).await?;
+        let media = QuestionMedia::find_by_id(&mut conn, media_id).await?;
+        let question = Question::find_by_id(&mut conn, media.question_id).await?;
+        require_owner(&requestor, question.user_id, "question")?;
+
+        detach_media(&mut conn, storage.as_ref(), media_id).await?;
+        Ok(true)
+    }
 }
@@ -0,0 +1,264 @@
+// graphql/mutations/response.rs
+
+use crate::auth::firebase_auth::{require_owner, require_user};
+use crate::db::pool::DBPool;
+use crate::graphql::validation::{Validate, Validator, MAX_ANSWER_LENGTH};
+use crate::models::game::Game;
+use crate::models::game_event::{GameEvent, GameEventData, GameEventType};
+use crate::models::game_question::GameQuestion;
+use crate::models::game_response::{GameResponse, NewGameResponse};
+use crate::models::player::Player;
+use crate::services::board_control::ControlHub;
+use crate::services::typed_responses::{
+    accepts_responses, judge_response, JudgedResponse, DEFAULT_RESPONSE_SECONDS,
+    MAX_RESPONSE_SECONDS, MIN_RESPONSE_SECONDS,
+};
+use async_graphql::{Context, InputObject, Object, Result};
+use chrono::{Duration, Utc};
+use diesel_async::scoped_futures::ScopedFutureExt;
+use diesel_async::AsyncConnection;
+
+#[derive(InputObject)]
+pub struct SubmitResponseInput {
+    pub game_id: i64,
+    pub player_id: i64,
+    pub response: String,
+    /// The player's token, from `fetchPlayerToken`. The host may leave it out
+    /// to enter a response for a player.
+    pub player_token: Option<String>,
+}
+
+impl Validate for SubmitResponseInput {
+    fn validate(&self, v: &mut Validator) {
+        v.id("gameId", self.game_id);
+        v.id("playerId", self.player_id);
+        v.non_blank("response", &self.response).max_length(
+            "response",
+            &self.response,
+            MAX_ANSWER_LENGTH,
+        );
+    }
+}
+
+#[derive(Default)]
+pub struct ResponseMutation;
+
+#[Object]
+impl ResponseMutation {
+    /// Open a cell of a game for typed responses for `responseSeconds`
+    /// (30 by default). Host only
+    async fn open_cell(
+        &self,
+        ctx: &Context<'_>,
+        game_id: i64,
+        grid_row: i32,
+        grid_col: i32,
+        response_seconds: Option<i32>,
+    ) -> Result<Game> {
+        let seconds = response_seconds.unwrap_or(DEFAULT_RESPONSE_SECONDS);
+        let mut v = Validator::default();
+        v.grid_row("gridRow", grid_row)
+            .grid_col("gridCol", grid_col)
+            .range(
+                "responseSeconds",
+                seconds,
+                MIN_RESPONSE_SECONDS,
+                MAX_RESPONSE_SECONDS,
+            );
+        v.finish()?;
+
+        let pool = ctx.data::<DBPool>().map_err(|e| {
+            async_graphql::Error::new(format!("Cannot get DBPool from context: {:?}", e))
+        })?;
+        let mut conn = pool
+            .get()
+            .await
+            .map_err(|e| async_graphql::Error::new(format!("Failed to get connection: {}", e)))?;
+
+        let requestor = require_user(ctx, &mut conn).await?;
+        let game = Game::find_by_id(&mut conn, game_id).await?;
+        require_owner(&requestor, game.user_id, "game")?;
+        if game.paused_at.is_some() {
+            return Err(async_graphql::Error::new("Game is paused"));
+        }
+
+        let game_question =
+            match GameQuestion::find_by_cell(&mut conn, game_id, grid_row, grid_col).await {
+                Ok(game_question) => game_question,
+                Err(diesel::NotFound) => {
+                    return Err(async_graphql::Error::new(format!(
+                        "Cell [{},{}] of game {} is empty",
+                        grid_row, grid_col, game_id
+                    )))
+                }
+                Err(e) => {
+                    return Err(async_graphql::Error::new(format!(
+                        "Database error: {:?}",
+                        e
+                    )))
+                }
+            };
+
+        let deadline = Utc::now() + Duration::seconds(i64::from(seconds));
+        let game = conn
+            .transaction::<_, diesel::result::Error, _>(|conn| {
+                async move {
+                    let game =
+                        Game::open_question(conn, game_id, game_question.id, deadline).await?;
+                    GameQuestion::mark_played(conn, game_question.id).await?;
+                    GameEvent::record(
+                        conn,
+                        game_id,
+                        GameEventType::CellSelected,
+                        GameEventData {
+                            game_question_id: Some(game_question.id),
+                            grid_row: Some(grid_row),
+                            grid_col: Some(grid_col),
+                            ..Default::default()
+                        },
+                    )
+                    .await?;
+                    Ok(game)
+                }
+                .scope_boxed()
+            })
+            .await?;
+        Ok(game)
+    }
+
+    /// Stop accepting responses for the open cell. Host only
+    async fn close_cell(&self, ctx: &Context<'_>, game_id: i64) -> Result<Game> {
+        let pool = ctx.data::<DBPool>().map_err(|e| {
+            async_graphql::Error::new(format!("Cannot get DBPool from context: {:?}", e))
+        })?;
+        let mut conn = pool
+            .get()
+            .await
+            .map_err(|e| async_graphql::Error::new(format!("Failed to get connection: {}", e)))?;
+
+        let requestor = require_user(ctx, &mut conn).await?;
+        let game = Game::find_by_id(&mut conn, game_id).await?;
+        require_owner(&requestor, game.user_id, "game")?;
+
+        let game = Game::close_question(&mut conn, game_id).await?;
+        Ok(game)
+    }
+
+    /// Submit a player's typed response to the open cell, as that player or as the host
+    async fn submit_response(
+        &self,
+        ctx: &Context<'_>,
+        input: SubmitResponseInput,
+    ) -> Result<GameResponse> {
+        input.validated()?;
+
+        let pool = ctx.data::<DBPool>().map_err(|e| {
+            async_graphql::Error::new(format!("Cannot get DBPool from context: {:?}", e))
+        })?;
+        let mut conn = pool
+            .get()
+            .await
+            .map_err(|e| async_graphql::Error::new(format!("Failed to get connection: {}", e)))?;
+
+        let game = Game::find_by_id(&mut conn, input.game_id).await?;
+        let player = Player::find_by_id(&mut conn, input.player_id).await?;
+        if player.game_id != game.id {
+            return Err(async_graphql::Error::new("Player is not in this game"));
+        }
+        match &input.player_token {
+            Some(token) if *token == player.player_token => {}
+            Some(_) => return Err(async_graphql::Error::new("Invalid player token")),
+            None => {
+                let requestor = require_user(ctx, &mut conn).await?;
+                require_owner(&requestor, game.user_id, "game")?;
+            }
+        }
+        if game.paused_at.is_some() {
+            return Err(async_graphql::Error::new("Game is paused"));
+        }
+        let Some(game_question_id) = game.open_question_id else {
+            return Err(async_graphql::Error::new("No cell is open for responses"));
+        };
+        if !accepts_responses(&game, game_question_id, Utc::now()) {
+            return Err(async_graphql::Error::new("Response window has closed"));
+        }
+
+        let new_response = NewGameResponse {
+            game_question_id,
+            player_id: player.id,
+            response: input.response.trim().to_string(),
+        };
+        let created = conn
+            .transaction::<_, diesel::result::Error, _>(|conn| {
+                async move {
+                    let response = GameResponse::create(conn, new_response).await?;
+                    GameEvent::record(
+                        conn,
+                        game.id,
+                        GameEventType::Response,
+                        GameEventData {
+                            game_question_id: Some(game_question_id),
+                            player_id: Some(player.id),
+                            response_id: Some(response.id),
+                            response: Some(response.response.clone()),
+                            ..Default::default()
+                        },
+                    )
+                    .await?;
+                    Ok(response)
+                }
+                .scope_boxed()
+            })
+            .await;
+        match created {
+            Ok(response) => Ok(response),
+            Err(diesel::result::Error::DatabaseError(
+                diesel::result::DatabaseErrorKind::UniqueViolation,
+                _,
+            )) => Err(async_graphql::Error::new(
+                "Player has already responded to this cell",
+            )),
+            Err(e) => Err(async_graphql::Error::new(format!(
+                "Database error: {:?}",
+                e
+            ))),
+        }
+    }
+
+    /// Judge a typed response and award or deduct the cell's points. A correct
+    /// response gives the player control of the board. Host only
+    async fn judge_response(
+        &self,
+        ctx: &Context<'_>,
+        response_id: i64,
+        correct: bool,
+    ) -> Result<JudgedResponse> {
+        let pool = ctx.data::<DBPool>().map_err(|e| {
+            async_graphql::Error::new(format!("Cannot get DBPool from context: {:?}", e))
+        })?;
+        let control_hub = ctx.data::<ControlHub>().map_err(|e| {
+            async_graphql::Error::new(format!("Cannot get ControlHub from context: {:?}", e))
+        })?;
+        let mut conn = pool
+            .get()
+            .await
+            .map_err(|e| async_graphql::Error::new(format!("Failed to get connection: {}", e)))?;
+
+        let requestor = require_user(ctx, &mut conn).await?;
+        let response = GameResponse::find_by_id(&mut conn, response_id).await?;
+        let game_question = GameQuestion::find_by_id(&mut conn, response.game_question_id).await?;
+        let game = Game::find_by_id(&mut conn, game_question.game_id).await?;
+        require_owner(&requestor, game.user_id, "game")?;
+
+        let Some(judged) = judge_response(&mut conn, response_id, &game_question, correct).await?
+        else {
+            return Err(async_graphql::Error::new(
+                "Response has already been judged",
+            ));
+        };
+        if let Some(control) = &judged.control {
+            control_hub.publish(control);
+        }
+        Ok(judged)
+    }
+}
@@ -0,0 +1,543 @@
+// graphql/mutations/team.rs
+
+use crate::auth::firebase_auth::{require_owner, require_user};
+use crate::db::pool::DBPool;
+use crate::graphql::validation::{Validate, Validator};
+use crate::models::game::Game;
+use crate::models::game_event::{GameEvent, GameEventData, GameEventType};
+use crate::models::game_question::GameQuestion;
+use crate::models::team::{NewTeam, NewTeamMember, Team, TeamMember, UpdateTeam};
+use crate::models::team_answer::{NewTeamAnswer, NewTeamBuzz, TeamBuzz};
+use crate::models::user::User;
+use crate::services::board_control::{hand_control, ControlHub};
+use crate::services::game_timers::{buzzing_open, on_buzz, on_judged, TimerHub};
+use crate::services::team_play::{judge_team_answer, question_is_open, TeamRuling};
+use async_graphql::{Context, InputObject, Object, Result};
+use diesel_async::scoped_futures::ScopedFutureExt;
+use diesel_async::{AsyncConnection, AsyncPgConnection};
+
+#[derive(InputObject)]
+pub struct CreateTeamInput {
+    pub game_id: i64,
+    pub team_name: String,
+}
+
+impl Validate for CreateTeamInput {
+    fn validate(&self, v: &mut Validator) {
+        v.id("gameId", self.game_id);
+        v.display_name("teamName", &self.team_name);
+    }
+}
+
+#[derive(InputObject)]
+pub struct AddTeamMemberInput {
+    pub team_id: i64,
+    /// A registered user joining the team.
+    pub user_id: Option<i64>,
+    /// The name to show; defaults to the user's username. Required for guests.
+    pub member_name: Option<String>,
+}
+
+impl Validate for AddTeamMemberInput {
+    fn validate(&self, v: &mut Validator) {
+        v.id("teamId", self.team_id);
+        if let Some(user_id) = self.user_id {
+            v.id("userId", user_id);
+        }
+        match &self.member_name {
+            Some(name) => {
+                v.display_name("memberName", name);
+            }
+            None if self.user_id.is_none() => {
+                v.add("memberName", "is required for guests");
+            }
+            None => {}
+        }
+    }
+}
+
+/// Load a game question and a team, checking they belong to the same game and
+/// that `member_id`, if given, is on the team.
+async fn load_team_for_question(
+    conn: &mut AsyncPgConnection,
+    game_question_id: i64,
+    team_id: i64,
+    member_id: Option<i64>,
+) -> Result<(GameQuestion, Team)> {
+    let game_question = match GameQuestion::find_by_id(conn, game_question_id).await {
+        Ok(game_question) => game_question,
+        Err(diesel::NotFound) => return Err(async_graphql::Error::new("Game question not found")),
+        Err(e) => {
+            return Err(async_graphql::Error::new(format!(
+                "Database error: {:?}",
+                e
+            )))
+        }
+    };
+    let team = match Team::find_by_id(conn, team_id).await {
+        Ok(team) => team,
+        Err(diesel::NotFound) => return Err(async_graphql::Error::new("Team not found")),
+        Err(e) => {
+            return Err(async_graphql::Error::new(format!(
+                "Database error: {:?}",
+                e
+            )))
+        }
+    };
+    if team.game_id != game_question.game_id {
+        return Err(async_graphql::Error::new(
+            "Team is not playing this game question's game",
+        ));
+    }
+    if let Some(member_id) = member_id {
+        match TeamMember::find_by_id(conn, member_id).await {
+            Ok(member) if member.team_id == team.id => {}
+            Ok(_) | Err(diesel::NotFound) => {
+                return Err(async_graphql::Error::new("Member is not on this team"))
+            }
+            Err(e) => {
+                return Err(async_graphql::Error::new(format!(
+                    "Database error: {:?}",
+                    e
+                )))
+            }
+        }
+    }
+    Ok((game_question, team))
+}
+
+/// Returns an error unless the requestor hosts the game.
+async fn require_host(ctx: &Context<'_>, conn: &mut AsyncPgConnection, game_id: i64) -> Result<()> {
+    let requestor = require_user(ctx, conn).await?;
+    let game = Game::find_by_id(conn, game_id).await?;
+    require_owner(&requestor, game.user_id, "game")?;
+    Ok(())
+}
+
+#[derive(Default)]
+pub struct TeamMutation;
+
+#[Object]
+impl TeamMutation {
+    /// Create a new team in a game. Host only
+    async fn create_team(&self, ctx: &Context<'_>, input: CreateTeamInput) -> Result<Team> {
+        input.validated()?;
+
+        let pool = ctx.data::<DBPool>().map_err(|e| {
+            async_graphql::Error::new(format!("Cannot get DBPool from context: {:?}", e))
+        })?;
+        let mut conn = pool
+            .get()
+            .await
+            .map_err(|e| async_graphql::Error::new(format!("Failed to get connection: {}", e)))?;
+
+        require_host(ctx, &mut conn, input.game_id).await?;
+        let new_team = NewTeam {
+            game_id: input.game_id,
+            team_name: input.team_name,
+        };
+        match Team::create(&mut conn, new_team).await {
+            Ok(team) => Ok(team),
+            Err(diesel::result::Error::DatabaseError(
+                diesel::result::DatabaseErrorKind::UniqueViolation,
+                _,
+            )) => Err(async_graphql::Error::new(
+                "A team with this name already exists in the game",
+            )),
+            Err(e) => Err(async_graphql::Error::new(format!(
+                "Database error: {:?}",
+                e
+            ))),
+        }
+    }
+
+    /// Rename a team. Host only
+    async fn rename_team(
+        &self,
+        ctx: &Context<'_>,
+        team_id: i64,
+        team_name: String,
+    ) -> Result<Team> {
+        let mut v = Validator::default();
+        v.display_name("teamName", &team_name);
+        v.finish()?;
+
+        let pool = ctx.data::<DBPool>().map_err(|e| {
+            async_graphql::Error::new(format!("Cannot get DBPool from context: {:?}", e))
+        })?;
+        let mut conn = pool
+            .get()
+            .await
+            .map_err(|e| async_graphql::Error::new(format!("Failed to get connection: {}", e)))?;
+
+        let team = Team::find_by_id(&mut conn, team_id).await?;
+        require_host(ctx, &mut conn, team.game_id).await?;
+        let team = Team::update_team(
+            &mut conn,
+            team_id,
+            UpdateTeam {
+                team_name: Some(team_name),
+                score: None,
+            },
+        )
+        .await?;
+        Ok(team)
+    }
+
+    /// Set a team's score directly, e.g. to correct a ruling. Host only
+    async fn update_team_score(&self, ctx: &Context<'_>, team_id: i64, score: i32) -> Result<Team> {
+        let pool = ctx.data::<DBPool>().map_err(|e| {
+            async_graphql::Error::new(format!("Cannot get DBPool from context: {:?}", e))
+        })?;
+        let mut conn = pool
+            .get()
+            .await
+            .map_err(|e| async_graphql::Error::new(format!("Failed to get connection: {}", e)))?;
+
+        let previous = Team::find_by_id(&mut conn, team_id).await?;
+        require_host(ctx, &mut conn, previous.game_id).await?;
+        let team = conn
+            .transaction::<_, diesel::result::Error, _>(|conn| {
+                async move {
+                    let team = Team::update_team(
+                        conn,
+                        team_id,
+                        UpdateTeam {
+                            team_name: None,
+                            score: Some(score),
+                        },
+                    )
+                    .await?;
+                    GameEvent::record(
+                        conn,
+                        team.game_id,
+                        GameEventType::ScoreChanged,
+                        GameEventData {
+                            team_id: Some(team_id),
+                            points: Some(score - previous.score),
+                            score: Some(score),
+                            ..Default::default()
+                        },
+                    )
+                    .await?;
+                    Ok(team)
+                }
+                .scope_boxed()
+            })
+            .await?;
+        Ok(team)
+    }
+
+    /// Delete a team from a game. Host only
+    async fn delete_team(&self, ctx: &Context<'_>, team_id: i64) -> Result<Team> {
+        let pool = ctx.data::<DBPool>().map_err(|e| {
+            async_graphql::Error::new(format!("Cannot get DBPool from context: {:?}", e))
+        })?;
+        let mut conn = pool
+            .get()
+            .await
+            .map_err(|e| async_graphql::Error::new(format!("Failed to get connection: {}", e)))?;
+
+        let team = Team::find_by_id(&mut conn, team_id).await?;
+        require_host(ctx, &mut conn, team.game_id).await?;
+
+        let team = Team::delete(&mut conn, team_id).await?;
+        Ok(team)
+    }
+
+    /// Add a registered user or a named guest to a team. Host only
+    async fn add_team_member(
+        &self,
+        ctx: &Context<'_>,
+        input: AddTeamMemberInput,
+    ) -> Result<TeamMember> {
+        input.validated()?;
+
+        let pool = ctx.data::<DBPool>().map_err(|e| {
+            async_graphql::Error::new(format!("Cannot get DBPool from context: {:?}", e))
+        })?;
+        let mut conn = pool
+            .get()
+            .await
+            .map_err(|e| async_graphql::Error::new(format!("Failed to get connection: {}", e)))?;
+
+        let team = match Team::find_by_id(&mut conn, input.team_id).await {
+            Ok(team) => team,
+            Err(diesel::NotFound) => return Err(async_graphql::Error::new("Team not found")),
+            Err(e) => {
+                return Err(async_graphql::Error::new(format!(
+                    "Database error: {:?}",
+                    e
+                )))
+            }
+        };
+        require_host(ctx, &mut conn, team.game_id).await?;
+
+        let member_name = match input.user_id {
+            Some(user_id) => {
+                let Some(user) = User::find_by_id(&mut conn, user_id).await? else {
+                    return Err(async_graphql::Error::new("User not found"));
+                };
+                let already_playing = TeamMember::fetch_by_game_id(&mut conn, team.game_id)
+                    .await?
+                    .iter()
+                    .any(|m| m.user_id == Some(user_id));
+                if already_playing {
+                    return Err(async_graphql::Error::new(
+                        "User is already on a team in this game",
+                    ));
+                }
+                input.member_name.unwrap_or(user.username)
+            }
+            None => input.member_name.unwrap_or_default(),
+        };
+
+        let new_member = NewTeamMember {
+            team_id: team.id,
+            user_id: input.user_id,
+            member_name,
+        };
+        match TeamMember::create(&mut conn, new_member).await {
+            Ok(member) => Ok(member),
+            Err(diesel::result::Error::DatabaseError(
+                diesel::result::DatabaseErrorKind::UniqueViolation,
+                _,
+            )) => Err(async_graphql::Error::new(
+                "A member with this name is already on the team",
+            )),
+            Err(e) => Err(async_graphql::Error::new(format!(
+                "Database error: {:?}",
+                e
+            ))),
+        }
+    }
+
+    /// Take a member off their team. Host only
+    async fn remove_team_member(&self, ctx: &Context<'_>, member_id: i64) -> Result<TeamMember> {
+        let pool = ctx.data::<DBPool>().map_err(|e| {
+            async_graphql::Error::new(format!("Cannot get DBPool from context: {:?}", e))
+        })?;
+        let mut conn = pool
+            .get()
+            .await
+            .map_err(|e| async_graphql::Error::new(format!("Failed to get connection: {}", e)))?;
+
+        let member = TeamMember::find_by_id(&mut conn, member_id).await?;
+        let team = Team::find_by_id(&mut conn, member.team_id).await?;
+        require_host(ctx, &mut conn, team.game_id).await?;
+
+        let member = TeamMember::delete(&mut conn, member_id).await?;
+        Ok(member)
+    }
+
+    /// Buzz a team in on a game question, as a member sending their token or
+    /// as the host. Each team may buzz once per question
+    async fn buzz_in(
+        &self,
+        ctx: &Context<'_>,
+        game_question_id: i64,
+        team_id: i64,
+        member_id: Option<i64>,
+        member_token: Option<String>,
+    ) -> Result<TeamBuzz> {
+        let pool = ctx.data::<DBPool>().map_err(|e| {
+            async_graphql::Error::new(format!("Cannot get DBPool from context: {:?}", e))
+        })?;
+        let hub = ctx.data::<TimerHub>().map_err(|e| {
+            async_graphql::Error::new(format!("Cannot get TimerHub from context: {:?}", e))
+        })?;
+        let mut conn = pool
+            .get()
+            .await
+            .map_err(|e| async_graphql::Error::new(format!("Failed to get connection: {}", e)))?;
+
+        let (game_question, _) =
+            load_team_for_question(&mut conn, game_question_id, team_id, member_id).await?;
+        match (&member_token, member_id) {
+            (Some(token), Some(member_id))
+                if *token
+                    == TeamMember::find_by_id(&mut conn, member_id)
+                        .await?
+                        .member_token => {}
+            (Some(_), _) => return Err(async_graphql::Error::new("Invalid member token")),
+            (None, _) => require_host(ctx, &mut conn, game_question.game_id).await?,
+        }
+        if !question_is_open(&mut conn, game_question_id).await? {
+            return Err(async_graphql::Error::new(
+                "Game question has already been answered",
+            ));
+        }
+        if Game::find_by_id(&mut conn, game_question.game_id)
+            .await?
+            .paused_at
+            .is_some()
+        {
+            return Err(async_graphql::Error::new("Game is paused"));
+        }
+        if !buzzing_open(&mut conn, hub, game_question.game_id, game_question_id).await? {
+            return Err(async_graphql::Error::new("Buzzing is not open"));
+        }
+
+        let new_buzz = NewTeamBuzz {
+            game_question_id,
+            team_id,
+            member_id,
+        };
+        let game_id = game_question.game_id;
+        let buzzed = conn
+            .transaction::<_, diesel::result::Error, _>(|conn| {
+                async move {
+                    let buzz = TeamBuzz::create(conn, new_buzz).await?;
+                    let timer = on_buzz(conn, game_id, game_question_id).await?;
+                    GameQuestion::mark_played(conn, game_question_id).await?;
+                    GameEvent::record(
+                        conn,
+                        game_id,
+                        GameEventType::Buzz,
+                        GameEventData {
+                            game_question_id: Some(game_question_id),
+                            team_id: Some(team_id),
+                            member_id,
+                            ..Default::default()
+                        },
+                    )
+                    .await?;
+                    Ok((buzz, timer))
+                }
+                .scope_boxed()
+            })
+            .await;
+        match buzzed {
+            Ok((buzz, timer)) => {
+                if let Some(timer) = &timer {
+                    hub.publish(timer);
+                }
+                Ok(buzz)
+            }
+            Err(diesel::result::Error::DatabaseError(
+                diesel::result::DatabaseErrorKind::UniqueViolation,
+                _,
+            )) => Err(async_graphql::Error::new(
+                "Team has already buzzed in on this game question",
+            )),
+            Err(e) => Err(async_graphql::Error::new(format!(
+                "Database error: {:?}",
+                e
+            ))),
+        }
+    }
+
+    /// Judge a buzzing team's answer, crediting it to a member (by default the
+    /// one who buzzed) and updating the team's score. A correct answer gives
+    /// the team control of the board
+    async fn judge_team_answer(
+        &self,
+        ctx: &Context<'_>,
+        game_question_id: i64,
+        team_id: i64,
+        correct: bool,
+        member_id: Option<i64>,
+    ) -> Result<TeamRuling> {
+        let pool = ctx.data::<DBPool>().map_err(|e| {
+            async_graphql::Error::new(format!("Cannot get DBPool from context: {:?}", e))
+        })?;
+        let hub = ctx.data::<TimerHub>().map_err(|e| {
+            async_graphql::Error::new(format!("Cannot get TimerHub from context: {:?}", e))
+        })?;
+        let control_hub = ctx.data::<ControlHub>().map_err(|e| {
+            async_graphql::Error::new(format!("Cannot get ControlHub from context: {:?}", e))
+        })?;
+        let mut conn = pool
+            .get()
+            .await
+            .map_err(|e| async_graphql::Error::new(format!("Failed to get connection: {}", e)))?;
+
+        let (game_question, _) =
+            load_team_for_question(&mut conn, game_question_id, team_id, member_id).await?;
+        require_host(ctx, &mut conn, game_question.game_id).await?;
+        let buzz = match TeamBuzz::find(&mut conn, game_question_id, team_id).await {
+            Ok(buzz) => buzz,
+            Err(diesel::NotFound) => {
+                return Err(async_graphql::Error::new(
+                    "Team has not buzzed in on this game question",
+                ))
+            }
+            Err(e) => {
+                return Err(async_graphql::Error::new(format!(
+                    "Database error: {:?}",
+                    e
+                )))
+            }
+        };
+        if !question_is_open(&mut conn, game_question_id).await? {
+            return Err(async_graphql::Error::new(
+                "Game question has already been answered",
+            ));
+        }
+
+        let new_answer = NewTeamAnswer {
+            game_question_id,
+            team_id,
+            member_id: member_id.or(buzz.member_id),
+            correct,
+            points_awarded: if correct {
+                game_question.points
+            } else {
+                -game_question.points
+            },
+        };
+        let game_id = game_question.game_id;
+        let judged = conn
+            .transaction::<_, diesel::result::Error, _>(|conn| {
+                async move {
+                    let ruling = judge_team_answer(conn, new_answer).await?;
+                    let timer = on_judged(conn, game_id, game_question_id, correct).await?;
+                    GameQuestion::mark_played(conn, game_question_id).await?;
+                    GameEvent::record(
+                        conn,
+                        game_id,
+                        GameEventType::Judged,
+                        GameEventData {
+                            game_question_id: Some(game_question_id),
+                            team_id: Some(team_id),
+                            member_id: ruling.answer.member_id,
+                            correct: Some(correct),
+                            points: Some(ruling.answer.points_awarded),
+                            score: Some(ruling.team.score),
+                            ..Default::default()
+                        },
+                    )
+                    .await?;
+                    let control = if correct {
+                        hand_control(conn, game_id, None, Some(team_id)).await?
+                    } else {
+                        None
+                    };
+                    Ok((ruling, timer, control))
+                }
+                .scope_boxed()
+            })
+            .await;
+        match judged {
+            Ok((ruling, timer, control)) => {
+                if let Some(timer) = &timer {
+                    hub.publish(timer);
+                }
+                if let Some(control) = &control {
+                    control_hub.publish(control);
+                }
+                Ok(ruling)
+            }
+            Err(diesel::result::Error::DatabaseError(
+                diesel::result::DatabaseErrorKind::UniqueViolation,
+                _,
+            )) => Err(async_graphql::Error::new(
+                "Team has already been judged on this game question",
+            )),
+            Err(e) => Err(async_graphql::Error::new(format!(
+                "Database error: {:?}",
+                e
+            ))),
+        }
+    }
+}
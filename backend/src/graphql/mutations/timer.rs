@@ -0,0 +1,202 @@
+// graphql/mutations/timer.rs
+
+use crate::auth::firebase_auth::{require_owner, require_user};
+use crate::db::pool::DBPool;
+use crate::graphql::validation::{Validate, Validator};
+use crate::models::game::Game;
+use crate::models::game_event::{GameEvent, GameEventData, GameEventType};
+use crate::models::game_question::GameQuestion;
+use crate::models::game_timer::{GamePhase, GameTimer, UpdateTimerSettings};
+use crate::services::game_timers::{
+    enter_phase, TimerHub, TimerState, MAX_PHASE_SECONDS, MIN_PHASE_SECONDS,
+};
+use async_graphql::{Context, InputObject, Object, Result};
+use chrono::Utc;
+use diesel_async::scoped_futures::ScopedFutureExt;
+use diesel_async::AsyncConnection;
+
+#[derive(InputObject)]
+pub struct TimerSettingsInput {
+    /// Seconds to read the clue before buzzing opens.
+    pub reading_seconds: Option<i32>,
+    /// Seconds contestants have to buzz in.
+    pub buzz_seconds: Option<i32>,
+    /// Seconds the contestant who buzzed has to answer.
+    pub answer_seconds: Option<i32>,
+    /// Seconds of Final Jeopardy think music.
+    pub final_seconds: Option<i32>,
+}
+
+impl Validate for TimerSettingsInput {
+    fn validate(&self, v: &mut Validator) {
+        for (field, value) in [
+            ("readingSeconds", self.reading_seconds),
+            ("buzzSeconds", self.buzz_seconds),
+            ("answerSeconds", self.answer_seconds),
+            ("finalSeconds", self.final_seconds),
+        ] {
+            if let Some(seconds) = value {
+                v.range(field, seconds, MIN_PHASE_SECONDS, MAX_PHASE_SECONDS);
+            }
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct TimerMutation;
+
+#[Object]
+impl TimerMutation {
+    /// Change how long each timed phase lasts in a game. Host only
+    async fn update_timer_settings(
+        &self,
+        ctx: &Context<'_>,
+        game_id: i64,
+        input: TimerSettingsInput,
+    ) -> Result<GameTimer> {
+        input.validated()?;
+
+        let pool = ctx.data::<DBPool>().map_err(|e| {
+            async_graphql::Error::new(format!("Cannot get DBPool from context: {:?}", e))
+        })?;
+        let mut conn = pool
+            .get()
+            .await
+            .map_err(|e| async_graphql::Error::new(format!("Failed to get connection: {}", e)))?;
+
+        let requestor = require_user(ctx, &mut conn).await?;
+        let game = Game::find_by_id(&mut conn, game_id).await?;
+        require_owner(&requestor, game.user_id, "game")?;
+
+        let settings = UpdateTimerSettings {
+            reading_seconds: input.reading_seconds,
+            buzz_seconds: input.buzz_seconds,
+            answer_seconds: input.answer_seconds,
+            final_seconds: input.final_seconds,
+        };
+        let timer = GameTimer::update_settings(&mut conn, game_id, settings).await?;
+        Ok(timer)
+    }
+
+    /// Start reading a cell; buzzing opens when the reading time runs out.
+    /// Host only
+    async fn start_question_timer(
+        &self,
+        ctx: &Context<'_>,
+        game_id: i64,
+        grid_row: i32,
+        grid_col: i32,
+    ) -> Result<TimerState> {
+        let mut v = Validator::default();
+        v.grid_row("gridRow", grid_row)
+            .grid_col("gridCol", grid_col);
+        v.finish()?;
+
+        let pool = ctx.data::<DBPool>().map_err(|e| {
+            async_graphql::Error::new(format!("Cannot get DBPool from context: {:?}", e))
+        })?;
+        let hub = ctx.data::<TimerHub>().map_err(|e| {
+            async_graphql::Error::new(format!("Cannot get TimerHub from context: {:?}", e))
+        })?;
+        let mut conn = pool
+            .get()
+            .await
+            .map_err(|e| async_graphql::Error::new(format!("Failed to get connection: {}", e)))?;
+
+        let requestor = require_user(ctx, &mut conn).await?;
+        let game = Game::find_by_id(&mut conn, game_id).await?;
+        require_owner(&requestor, game.user_id, "game")?;
+
+        let game_question =
+            match GameQuestion::find_by_cell(&mut conn, game_id, grid_row, grid_col).await {
+                Ok(game_question) => game_question,
+                Err(diesel::NotFound) => {
+                    return Err(async_graphql::Error::new(format!(
+                        "Cell [{},{}] of game {} is empty",
+                        grid_row, grid_col, game_id
+                    )))
+                }
+                Err(e) => {
+                    return Err(async_graphql::Error::new(format!(
+                        "Database error: {:?}",
+                        e
+                    )))
+                }
+            };
+
+        let timer = conn
+            .transaction::<_, diesel::result::Error, _>(|conn| {
+                async move {
+                    let timer =
+                        enter_phase(conn, game_id, GamePhase::Reading, Some(game_question.id))
+                            .await?;
+                    GameQuestion::mark_played(conn, game_question.id).await?;
+                    GameEvent::record(
+                        conn,
+                        game_id,
+                        GameEventType::CellSelected,
+                        GameEventData {
+                            game_question_id: Some(game_question.id),
+                            grid_row: Some(grid_row),
+                            grid_col: Some(grid_col),
+                            ..Default::default()
+                        },
+                    )
+                    .await?;
+                    Ok(timer)
+                }
+                .scope_boxed()
+            })
+            .await?;
+        hub.publish(&timer);
+        Ok(TimerState::of(&timer, Utc::now()))
+    }
+
+    /// Start the Final Jeopardy clock. Host only
+    async fn start_final_jeopardy_timer(
+        &self,
+        ctx: &Context<'_>,
+        game_id: i64,
+    ) -> Result<TimerState> {
+        let pool = ctx.data::<DBPool>().map_err(|e| {
+            async_graphql::Error::new(format!("Cannot get DBPool from context: {:?}", e))
+        })?;
+        let hub = ctx.data::<TimerHub>().map_err(|e| {
+            async_graphql::Error::new(format!("Cannot get TimerHub from context: {:?}", e))
+        })?;
+        let mut conn = pool
+            .get()
+            .await
+            .map_err(|e| async_graphql::Error::new(format!("Failed to get connection: {}", e)))?;
+
+        let requestor = require_user(ctx, &mut conn).await?;
+        let game = Game::find_by_id(&mut conn, game_id).await?;
+        require_owner(&requestor, game.user_id, "game")?;
+
+        let timer = enter_phase(&mut conn, game_id, GamePhase::FinalJeopardy, None).await?;
+        hub.publish(&timer);
+        Ok(TimerState::of(&timer, Utc::now()))
+    }
+
+    /// Stop the game's timer. Host only
+    async fn stop_timer(&self, ctx: &Context<'_>, game_id: i64) -> Result<TimerState> {
+        let pool = ctx.data::<DBPool>().map_err(|e| {
+            async_graphql::Error::new(format!("Cannot get DBPool from context: {:?}", e))
+        })?;
+        let hub = ctx.data::<TimerHub>().map_err(|e| {
+            async_graphql::Error::new(format!("Cannot get TimerHub from context: {:?}", e))
+        })?;
+        let mut conn = pool
+            .get()
+            .await
+            .map_err(|e| async_graphql::Error::new(format!("Failed to get connection: {}", e)))?;
+
+        let requestor = require_user(ctx, &mut conn).await?;
+        let game = Game::find_by_id(&mut conn, game_id).await?;
+        require_owner(&requestor, game.user_id, "game")?;
+
+        let timer = enter_phase(&mut conn, game_id, GamePhase::Idle, None).await?;
+        hub.publish(&timer);
+        Ok(TimerState::of(&timer, Utc::now()))
+    }
+}
@@ -0,0 +1,320 @@
+// graphql/mutations/translation.rs
+
+use crate::auth::firebase_auth::{require_owner, require_user};
+use crate::db::pool::DBPool;
+use crate::graphql::validation::{
+    Validate, Validator, GRID_COLS, MAX_ANSWER_LENGTH, MAX_CATEGORY_LENGTH, MAX_QUESTION_LENGTH,
+    MAX_TITLE_LENGTH,
+};
+use crate::models::game_board_translation::{GameBoardTranslation, NewGameBoardTranslation};
+use crate::models::question::Question;
+use crate::models::question_translation::{NewQuestionTranslation, QuestionTranslation};
+use crate::models::GBQMapping;
+use crate::services::board_access::{require_board_access, BoardAccess};
+use crate::services::localization::{
+    import_translations, normalize_locale, TranslationBundle, TranslationImportSummary,
+};
+use crate::services::rich_text::sanitize_markdown;
+use async_graphql::{Context, InputObject, Object, Result};
+use std::collections::HashSet;
+
+#[derive(InputObject)]
+pub struct QuestionTranslationInput {
+    pub question_id: i64,
+    pub locale: String,
+    /// Markdown, like the question itself.
+    pub question: String,
+    /// Leave out to keep the original answer.
+    pub answer: Option<String>,
+}
+
+#[derive(InputObject)]
+pub struct GameBoardTranslationInput {
+    pub game_board_id: i64,
+    pub locale: String,
+    pub title: String,
+    /// One entry per column; a null entry keeps the original category.
+    pub categories: Option<Vec<Option<String>>>,
+}
+
+/// A translated clue follows the same rules as the clue itself.
+fn validate_question_translation(
+    v: &mut Validator,
+    prefix: &str,
+    question: &str,
+    answer: Option<&str>,
+) {
+    let field = format!("{}question", prefix);
+    v.non_blank(&field, question)
+        .max_length(&field, question, MAX_QUESTION_LENGTH)
+        .markdown(&field, question);
+    if let Some(answer) = answer {
+        let field = format!("{}answer", prefix);
+        v.non_blank(&field, answer)
+            .max_length(&field, answer, MAX_ANSWER_LENGTH)
+            .markdown(&field, answer);
+    }
+}
+
+/// A translated title and categories follow the same rules as the board's.
+fn validate_board_translation(
+    v: &mut Validator,
+    prefix: &str,
+    title: &str,
+    categories: &[Option<String>],
+) {
+    let field = format!("{}title", prefix);
+    v.non_blank(&field, title)
+        .max_length(&field, title, MAX_TITLE_LENGTH);
+    let field = format!("{}categories", prefix);
+    if categories.len() > GRID_COLS as usize {
+        v.add(
+            &field,
+            format!("must have at most {} categories", GRID_COLS),
+        );
+    }
+    v.each(&field, categories, |v, field, category| {
+        if let Some(category) = category {
+            v.non_blank(field, category)
+                .max_length(field, category, MAX_CATEGORY_LENGTH);
+        }
+    });
+}
+
+impl Validate for QuestionTranslationInput {
+    fn validate(&self, v: &mut Validator) {
+        v.id("questionId", self.question_id);
+        v.locale("locale", &self.locale);
+        validate_question_translation(v, "", &self.question, self.answer.as_deref());
+    }
+}
+
+impl Validate for GameBoardTranslationInput {
+    fn validate(&self, v: &mut Validator) {
+        v.id("gameBoardId", self.game_board_id);
+        v.locale("locale", &self.locale);
+        validate_board_translation(
+            v,
+            "",
+            &self.title,
+            self.categories.as_deref().unwrap_or_default(),
+        );
+    }
+}
+
+impl Validate for TranslationBundle {
+    fn validate(&self, v: &mut Validator) {
+        v.locale("locale", &self.locale);
+        if let Some(title) = &self.game_board.title {
+            validate_board_translation(v, "gameBoard.", title, &self.game_board.categories);
+        }
+        for (i, entry) in self.questions.iter().enumerate() {
+            if let Some(question) = &entry.question {
+                let prefix = format!("questions[{}].", i);
+                validate_question_translation(v, &prefix, question, entry.answer.as_deref());
+            }
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct TranslationMutation;
+
+#[Object]
+impl TranslationMutation {
+    /// Translate a question into another locale, replacing any earlier translation
+    async fn set_question_translation(
+        &self,
+        ctx: &Context<'_>,
+        mut input: QuestionTranslationInput,
+    ) -> Result<QuestionTranslation> {
+        input.question = sanitize_markdown(&input.question);
+        input.answer = input.answer.as_deref().map(sanitize_markdown);
+        input.validated()?;
+
+        let pool = ctx.data::<DBPool>().map_err(|e| {
+            async_graphql::Error::new(format!("Cannot get DBPool from context: {:?}", e))
+        })?;
+        let mut conn = pool
+            .get()
+            .await
+            .map_err(|e| async_graphql::Error::new(format!("Failed to get connection: {}", e)))?;
+
+        let requestor = require_user(ctx, &mut conn).await?;
+        let question = Question::find_by_id(&mut conn, input.question_id).await?;
+        require_owner(&requestor, question.user_id, "question")?;
+
+        let locale = normalize_locale(&input.locale).unwrap_or(input.locale);
+        if locale == question.locale {
+            return Err(async_graphql::Error::new(format!(
+                "The question is written in {}; edit the question instead",
+                locale
+            )));
+        }
+
+        let translation = QuestionTranslation::upsert(
+            &mut conn,
+            NewQuestionTranslation {
+                question_id: question.id,
+                locale,
+                question: input.question,
+                answer: input.answer,
+            },
+        )
+        .await?;
+        Ok(translation)
+    }
+
+    /// Remove a question's translation into a locale
+    async fn delete_question_translation(
+        &self,
+        ctx: &Context<'_>,
+        question_id: i64,
+        locale: String,
+    ) -> Result<bool> {
+        let pool = ctx.data::<DBPool>().map_err(|e| {
+            async_graphql::Error::new(format!("Cannot get DBPool from context: {:?}", e))
+        })?;
+        let mut conn = pool
+            .get()
+            .await
+            .map_err(|e| async_graphql::Error::new(format!("Failed to get connection: {}", e)))?;
+
+        let requestor = require_user(ctx, &mut conn).await?;
+        let question = Question::find_by_id(&mut conn, question_id).await?;
+        require_owner(&requestor, question.user_id, "question")?;
+
+        let locale = normalize_locale(&locale).unwrap_or(locale);
+        let rows_deleted = QuestionTranslation::delete(&mut conn, question_id, &locale).await?;
+        Ok(rows_deleted > 0)
+    }
+
+    /// Translate a gameboard's title and categories into another locale
+    async fn set_game_board_translation(
+        &self,
+        ctx: &Context<'_>,
+        input: GameBoardTranslationInput,
+    ) -> Result<GameBoardTranslation> {
+        input.validated()?;
+
+        let pool = ctx.data::<DBPool>().map_err(|e| {
+            async_graphql::Error::new(format!("Cannot get DBPool from context: {:?}", e))
+        })?;
+        let mut conn = pool
+            .get()
+            .await
+            .map_err(|e| async_graphql::Error::new(format!("Failed to get connection: {}", e)))?;
+
+        // Owner or an invited editor
+        let (_, board) =
+            require_board_access(ctx, &mut conn, input.game_board_id, BoardAccess::Edit).await?;
+
+        let locale = normalize_locale(&input.locale).unwrap_or(input.locale);
+        if locale == board.locale {
+            return Err(async_graphql::Error::new(format!(
+                "The gameboard is written in {}; edit the gameboard instead",
+                locale
+            )));
+        }
+
+        let translation = GameBoardTranslation::upsert(
+            &mut conn,
+            NewGameBoardTranslation {
+                game_board_id: board.id,
+                locale,
+                title: input.title,
+                categories: input.categories.unwrap_or_default(),
+            },
+        )
+        .await?;
+        Ok(translation)
+    }
+
+    /// Remove a gameboard's translation into a locale
+    async fn delete_game_board_translation(
+        &self,
+        ctx: &Context<'_>,
+        game_board_id: i64,
+        locale: String,
+    ) -> Result<bool> {
+        let pool = ctx.data::<DBPool>().map_err(|e| {
+            async_graphql::Error::new(format!("Cannot get DBPool from context: {:?}", e))
+        })?;
+        let mut conn = pool
+            .get()
+            .await
+            .map_err(|e| async_graphql::Error::new(format!("Failed to get connection: {}", e)))?;
+
+        require_board_access(ctx, &mut conn, game_board_id, BoardAccess::Edit).await?;
+
+        let locale = normalize_locale(&locale).unwrap_or(locale);
+        let rows_deleted = GameBoardTranslation::delete(&mut conn, game_board_id, &locale).await?;
+        Ok(rows_deleted > 0)
+    }
+
+    /// Import a gameboard's translations from a file made by exportTranslations
+    async fn import_translations(
+        &self,
+        ctx: &Context<'_>,
+        game_board_id: i64,
+        data: String,
+    ) -> Result<TranslationImportSummary> {
+        let mut bundle: TranslationBundle = serde_json::from_str(&data)
+            .map_err(|e| async_graphql::Error::new(format!("Invalid translation file: {}", e)))?;
+        if bundle.game_board.id != game_board_id {
+            return Err(async_graphql::Error::new(format!(
+                "The translation file is for gameboard {}",
+                bundle.game_board.id
+            )));
+        }
+        for entry in bundle.questions.iter_mut() {
+            entry.question = entry.question.as_deref().map(sanitize_markdown);
+            entry.answer = entry.answer.as_deref().map(sanitize_markdown);
+        }
+        bundle.validated()?;
+        bundle.locale = normalize_locale(&bundle.locale).unwrap_or(bundle.locale);
+
+        let pool = ctx.data::<DBPool>().map_err(|e| {
+            async_graphql::Error::new(format!("Cannot get DBPool from context: {:?}", e))
+        })?;
+        let mut conn = pool
+            .get()
+            .await
+            .map_err(|e| async_graphql::Error::new(format!("Failed to get connection: {}", e)))?;
+
+        let (requestor, board) =
+            require_board_access(ctx, &mut conn, game_board_id, BoardAccess::Edit).await?;
+        if bundle.game_board.title.is_some() && bundle.locale == board.locale {
+            return Err(async_graphql::Error::new(format!(
+                "The gameboard is written in {}; edit the gameboard instead",
+                bundle.locale
+            )));
+        }
+
+        // Only the board's own questions, and only those the requestor wrote
+        let on_board: HashSet<i64> = GBQMapping::fetch_mappings_by_board_id(&mut conn, board.id)
+            .await?
+            .into_iter()
+            .map(|mapping| mapping.question_id)
+            .collect();
+        for entry in bundle.questions.iter().filter(|e| e.question.is_some()) {
+            if !on_board.contains(&entry.id) {
+                return Err(async_graphql::Error::new(format!(
+                    "Question {} is not on this gameboard",
+                    entry.id
+                )));
+            }
+            let question = Question::find_by_id(&mut conn, entry.id).await?;
+            require_owner(&requestor, question.user_id, "question")?;
+            if question.locale == bundle.locale {
+                return Err(async_graphql::Error::new(format!(
+                    "Question {} is written in {}; edit the question instead",
+                    entry.id, bundle.locale
+                )));
+            }
+        }
+
+        let summary = import_translations(&mut conn, bundle).await?;
+        Ok(summary)
+    }
+}
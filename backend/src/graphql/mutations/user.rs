@@ -1,6 +1,8 @@
 // graphql/mutations/user.rs
 
+use crate::auth::firebase_auth::require_user;
 use crate::db::pool::DBPool;
+use crate::graphql::validation::{Validate, Validator};
 use crate::models::user::User;
 use async_graphql::{Context, InputObject, Object, Result};
 
@@ -10,6 +12,13 @@ pub struct CreateUserInput {
     pub firebase_uid: String,
 }
 
+impl Validate for CreateUserInput {
+    fn validate(&self, v: &mut Validator) {
+        v.display_name("username", &self.username);
+        v.non_blank("firebaseUid", &self.firebase_uid);
+    }
+}
+
 // Define the mutation root for User
 #[derive(Default)]
 pub struct UserMutation;
@@ -17,6 +26,8 @@ pub struct UserMutation;
 #[Object]
 impl UserMutation {
     async fn create_user(&self, ctx: &Context<'_>, input: CreateUserInput) -> Result<User> {
+        input.validated()?;
+
         let pool = ctx.data::<DBPool>().map_err(|e| {
             async_graphql::Error::new(format!("Cannot get DBPool from context: {:?}", e))
         })?;
@@ -30,4 +41,19 @@ impl UserMutation {
             .map_err(|e| async_graphql::Error::new(e.to_string()))?;
         Ok(user)
     }
+
+    /// Permanently delete the authenticated user and everything they own
+    async fn delete_user(&self, ctx: &Context<'_>) -> Result<bool> {
+        let pool = ctx.data::<DBPool>().map_err(|e| {
+            async_graphql::Error::new(format!("Cannot get DBPool from context: {:?}", e))
+        })?;
+        let mut conn = pool
+            .get()
+            .await
+            .map_err(|e| async_graphql::Error::new(format!("Failed to get connection: {}", e)))?;
+
+        let requestor = require_user(ctx, &mut conn).await?;
+        let rows_deleted = User::delete(&mut conn, requestor.id).await?;
+        Ok(rows_deleted > 0)
+    }
 }
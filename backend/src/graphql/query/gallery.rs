@@ -0,0 +1,85 @@
+// graphql/query/gallery.rs
+
+use crate::auth::firebase_auth::{require_moderator, require_user};
+use crate::db::pool::DBPool;
+use crate::graphql::validation::Validator;
+use crate::models::game_board::GameBoard;
+use crate::services::gallery::{
+    fetch_reported_boards, search_gallery, GalleryPage, GallerySort, ReportedBoard,
+    DEFAULT_PAGE_SIZE, MAX_PAGE_SIZE,
+};
+use async_graphql::{Context, Object, Result};
+
+#[derive(Default)]
+pub struct GalleryQuery;
+
+#[Object]
+impl GalleryQuery {
+    /// Browse public gameboards. Searches titles, authors and categories
+    async fn fetch_gallery(
+        &self,
+        ctx: &Context<'_>,
+        search: Option<String>,
+        sort: Option<GallerySort>,
+        limit: Option<i32>,
+        offset: Option<i32>,
+    ) -> Result<GalleryPage> {
+        let limit = limit.unwrap_or(DEFAULT_PAGE_SIZE);
+        let offset = offset.unwrap_or(0);
+        let mut v = Validator::default();
+        v.range("limit", limit, 1, MAX_PAGE_SIZE);
+        v.range("offset", offset, 0, i32::MAX);
+        v.finish()?;
+
+        let pool = ctx.data::<DBPool>().map_err(|e| {
+            async_graphql::Error::new(format!("Cannot get DBPool from context: {:?}", e))
+        })?;
+        let mut conn = pool
+            .get()
+            .await
+            .map_err(|e| async_graphql::Error::new(format!("Failed to get connection: {}", e)))?;
+        let page = search_gallery(
+            &mut conn,
+            search.as_deref(),
+            sort.unwrap_or_default(),
+            limit,
+            offset,
+        )
+        .await?;
+        Ok(page)
+    }
+
+    /// Fetch the live gameboards a user marked as favourites
+    async fn fetch_favourite_game_boards(
+        &self,
+        ctx: &Context<'_>,
+        user_id: i64,
+    ) -> Result<Vec<GameBoard>> {
+        let pool = ctx.data::<DBPool>().map_err(|e| {
+            async_graphql::Error::new(format!("Cannot get DBPool from context: {:?}", e))
+        })?;
+        let mut conn = pool
+            .get()
+            .await
+            .map_err(|e| async_graphql::Error::new(format!("Failed to get connection: {}", e)))?;
+        let game_boards = GameBoard::fetch_favourites_of_user(&mut conn, user_id).await?;
+        Ok(game_boards)
+    }
+
+    /// Fetch gameboards with open reports. Moderators only
+    async fn fetch_reported_game_boards(&self, ctx: &Context<'_>) -> Result<Vec<ReportedBoard>> {
+        let pool = ctx.data::<DBPool>().map_err(|e| {
+            async_graphql::Error::new(format!("Cannot get DBPool from context: {:?}", e))
+        })?;
+        let mut conn = pool
+            .get()
+            .await
+            .map_err(|e| async_graphql::Error::new(format!("Failed to get connection: {}", e)))?;
+
+        let requestor = require_user(ctx, &mut conn).await?;
+        require_moderator(&requestor)?;
+
+        let reported = fetch_reported_boards(&mut conn).await?;
+        Ok(reported)
+    }
+}
@@ -1,8 +1,46 @@
 // graphql/query/game.rs
 
+use crate::auth::firebase_auth::{get_user_from_context, require_owner, require_user};
 use crate::db::pool::DBPool;
+use crate::graphql::validation::Validator;
 use crate::models::game::Game;
+use crate::models::game_event::GameEvent;
+use crate::models::game_question::GameQuestion;
+use crate::services::answer_matching::{match_answer, AnswerMatch};
+use crate::services::board_control::{current_control, BoardControl};
+use crate::services::game_replay::{export_timeline, replay_game, ReplayState};
+use crate::services::game_timers::TimerHub;
+use crate::services::live_games::{game_snapshot, GameSnapshot};
 use async_graphql::{Context, Object, Result};
+use diesel_async::AsyncPgConnection;
+
+/// Whether the requestor hosts `game`; anonymous callers never do
+async fn is_host(ctx: &Context<'_>, conn: &mut AsyncPgConnection, game: &Game) -> Result<bool> {
+    match get_user_from_context(ctx) {
+        Some(_) => Ok(require_user(ctx, conn).await?.id == game.user_id),
+        None => Ok(false),
+    }
+}
+
+/// Blank out the answers of cells that are unplayed or still open, unless
+/// the requestor hosts the game
+async fn hide_answers_from_players(
+    ctx: &Context<'_>,
+    conn: &mut AsyncPgConnection,
+    game_id: i64,
+    game_questions: &mut [GameQuestion],
+) -> Result<()> {
+    let game = Game::find_by_id(conn, game_id).await?;
+    if is_host(ctx, conn, &game).await? {
+        return Ok(());
+    }
+    for game_question in game_questions {
+        if game_question.played_at.is_none() || game.open_question_id == Some(game_question.id) {
+            game_question.hide_answers();
+        }
+    }
+    Ok(())
+}
 
 #[derive(Default)]
 pub struct GameQuery;
@@ -36,4 +74,202 @@ impl GameQuery {
         let games = Game::fetch_by_user(&mut conn, user_id).await?;
         Ok(games)
     }
+
+    /// Fetch all archived games from user
+    async fn fetch_archived_games_from_user(
+        &self,
+        ctx: &Context<'_>,
+        user_id: i64,
+    ) -> Result<Vec<Game>> {
+        let pool = ctx.data::<DBPool>().map_err(|e| {
+            async_graphql::Error::new(format!("Cannot get DBPool from context: {:?}", e))
+        })?;
+        let mut conn = pool
+            .get()
+            .await
+            .map_err(|e| async_graphql::Error::new(format!("Failed to get connection: {}", e)))?;
+        let games = Game::fetch_archived_by_user(&mut conn, user_id).await?;
+        Ok(games)
+    }
+
+    /// Fetch the questions of a game as they were when the game was created.
+    /// Only the host sees the answers of cells that are unplayed or still open
+    async fn fetch_game_questions(
+        &self,
+        ctx: &Context<'_>,
+        game_id: i64,
+    ) -> Result<Vec<GameQuestion>> {
+        let pool = ctx.data::<DBPool>().map_err(|e| {
+            async_graphql::Error::new(format!("Cannot get DBPool from context: {:?}", e))
+        })?;
+        let mut conn = pool
+            .get()
+            .await
+            .map_err(|e| async_graphql::Error::new(format!("Failed to get connection: {}", e)))?;
+        let mut questions = GameQuestion::fetch_by_game_id(&mut conn, game_id).await?;
+        hide_answers_from_players(ctx, &mut conn, game_id, &mut questions).await?;
+        Ok(questions)
+    }
+
+    /// Find the question of a game at a grid position. Only the host sees
+    /// its answer before it has been played
+    async fn find_game_question(
+        &self,
+        ctx: &Context<'_>,
+        game_id: i64,
+        grid_row: i32,
+        grid_col: i32,
+    ) -> Result<GameQuestion> {
+        let pool = ctx.data::<DBPool>().map_err(|e| {
+            async_graphql::Error::new(format!("Cannot get DBPool from context: {:?}", e))
+        })?;
+        let mut conn = pool
+            .get()
+            .await
+            .map_err(|e| async_graphql::Error::new(format!("Failed to get connection: {}", e)))?;
+        match GameQuestion::find_by_cell(&mut conn, game_id, grid_row, grid_col).await {
+            Ok(mut question) => {
+                hide_answers_from_players(
+                    ctx,
+                    &mut conn,
+                    game_id,
+                    std::slice::from_mut(&mut question),
+                )
+                .await?;
+                Ok(question)
+            }
+            Err(diesel::NotFound) => Err(async_graphql::Error::new(format!(
+                "Cell [{},{}] of game {} is empty",
+                grid_row, grid_col, game_id
+            ))),
+            Err(e) => Err(async_graphql::Error::new(format!(
+                "Database error: {:?}",
+                e
+            ))),
+        }
+    }
+
+    /// Compare a response with a game question's answer as frozen for the game. Host only
+    async fn check_game_answer(
+        &self,
+        ctx: &Context<'_>,
+        game_question_id: i64,
+        response: String,
+    ) -> Result<AnswerMatch> {
+        let pool = ctx.data::<DBPool>().map_err(|e| {
+            async_graphql::Error::new(format!("Cannot get DBPool from context: {:?}", e))
+        })?;
+        let mut conn = pool
+            .get()
+            .await
+            .map_err(|e| async_graphql::Error::new(format!("Failed to get connection: {}", e)))?;
+
+        let requestor = require_user(ctx, &mut conn).await?;
+        let game_question = GameQuestion::find_by_id(&mut conn, game_question_id).await?;
+        let game = Game::find_by_id(&mut conn, game_question.game_id).await?;
+        require_owner(&requestor, game.user_id, "game")?;
+        Ok(match_answer(
+            &response,
+            &game_question.answer,
+            &game_question.alternate_answers,
+        ))
+    }
+
+    /// Fetch the event log of a game in the order the events happened
+    async fn fetch_game_events(&self, ctx: &Context<'_>, game_id: i64) -> Result<Vec<GameEvent>> {
+        let pool = ctx.data::<DBPool>().map_err(|e| {
+            async_graphql::Error::new(format!("Cannot get DBPool from context: {:?}", e))
+        })?;
+        let mut conn = pool
+            .get()
+            .await
+            .map_err(|e| async_graphql::Error::new(format!("Failed to get connection: {}", e)))?;
+
+        let events = GameEvent::fetch_by_game_id(&mut conn, game_id).await?;
+        Ok(events)
+    }
+
+    /// Rebuild a game as it was right after the event at `eventIndex`, or
+    /// after its last event
+    async fn replay_game(
+        &self,
+        ctx: &Context<'_>,
+        game_id: i64,
+        event_index: Option<i32>,
+    ) -> Result<ReplayState> {
+        let mut v = Validator::default();
+        if event_index.is_some_and(|index| index < 0) {
+            v.add("eventIndex", "must not be negative");
+        }
+        v.finish()?;
+
+        let pool = ctx.data::<DBPool>().map_err(|e| {
+            async_graphql::Error::new(format!("Cannot get DBPool from context: {:?}", e))
+        })?;
+        let mut conn = pool
+            .get()
+            .await
+            .map_err(|e| async_graphql::Error::new(format!("Failed to get connection: {}", e)))?;
+
+        let game = Game::find_by_id(&mut conn, game_id).await?;
+        match replay_game(&mut conn, game.id, event_index).await? {
+            Some(state) => Ok(state),
+            None => Err(async_graphql::Error::new(format!(
+                "Game {} has no event {}",
+                game_id,
+                event_index.unwrap_or_default()
+            ))),
+        }
+    }
+
+    /// Export the whole timeline of a game as JSON
+    async fn export_game_timeline(&self, ctx: &Context<'_>, game_id: i64) -> Result<String> {
+        let pool = ctx.data::<DBPool>().map_err(|e| {
+            async_graphql::Error::new(format!("Cannot get DBPool from context: {:?}", e))
+        })?;
+        let mut conn = pool
+            .get()
+            .await
+            .map_err(|e| async_graphql::Error::new(format!("Failed to get connection: {}", e)))?;
+
+        let timeline = export_timeline(&mut conn, game_id).await?;
+        Ok(timeline)
+    }
+
+    /// Snapshot a game in play: its board, scores, board control, clock and
+    /// whether it is paused. Clients call this to resync after they lost
+    /// their connection or the server restarted. Only the host sees the
+    /// answers of cells that are unplayed or still in play
+    async fn resume_game(&self, ctx: &Context<'_>, game_id: i64) -> Result<GameSnapshot> {
+        let pool = ctx.data::<DBPool>().map_err(|e| {
+            async_graphql::Error::new(format!("Cannot get DBPool from context: {:?}", e))
+        })?;
+        let hub = ctx.data::<TimerHub>().map_err(|e| {
+            async_graphql::Error::new(format!("Cannot get TimerHub from context: {:?}", e))
+        })?;
+        let mut conn = pool
+            .get()
+            .await
+            .map_err(|e| async_graphql::Error::new(format!("Failed to get connection: {}", e)))?;
+
+        let mut snapshot = game_snapshot(&mut conn, hub, game_id).await?;
+        if !is_host(ctx, &mut conn, &snapshot.game).await? {
+            snapshot.hide_answers();
+        }
+        Ok(snapshot)
+    }
+
+    /// Find out who has control of a game's board
+    async fn fetch_board_control(&self, ctx: &Context<'_>, game_id: i64) -> Result<BoardControl> {
+        let pool = ctx.data::<DBPool>().map_err(|e| {
+            async_graphql::Error::new(format!("Cannot get DBPool from context: {:?}", e))
+        })?;
+        let mut conn = pool
+            .get()
+            .await
+            .map_err(|e| async_graphql::Error::new(format!("Failed to get connection: {}", e)))?;
+
+        let control = current_control(&mut conn, game_id).await?;
+        Ok(control)
+    }
 }
@@ -1,16 +1,117 @@
 // graphql/query/game_board.rs
 
+use crate::auth::firebase_auth::{get_user_from_context, require_user};
 use crate::db::pool::DBPool;
 use crate::models::game_board::GameBoard;
+use crate::models::game_board_revision::{diff_snapshots, GameBoardRevision, RevisionChange};
+use crate::services::board_access::{
+    board_access, board_sharing, require_board_access, BoardAccess, BoardSharing,
+};
+use crate::services::board_validation::{validate_game_board, BoardValidationReport};
+use crate::services::localization::{localize_game_board, localize_game_boards, requested_locale};
 use async_graphql::{Context, Object, Result};
+use diesel_async::AsyncPgConnection;
+
+/// Returns an error unless the requestor is `user_id`, whose boards are being listed
+async fn require_self(ctx: &Context<'_>, conn: &mut AsyncPgConnection, user_id: i64) -> Result<()> {
+    let requestor = require_user(ctx, conn).await?;
+    if requestor.id != user_id {
+        return Err(async_graphql::Error::new(
+            "Requestor cannot list another user's game boards",
+        ));
+    }
+    Ok(())
+}
 
 #[derive(Default)]
 pub struct GameBoardQuery;
 
 #[Object]
 impl GameBoardQuery {
-    /// Find a single gameboard by id
-    async fn find_game_board(&self, ctx: &Context<'_>, game_board_id: i64) -> Result<GameBoard> {
+    /// Find a single gameboard by id, in `locale` if translated
+    async fn find_game_board(
+        &self,
+        ctx: &Context<'_>,
+        game_board_id: i64,
+        locale: Option<String>,
+    ) -> Result<GameBoard> {
+        let locale = requested_locale(locale.as_deref())?;
+        let pool = ctx.data::<DBPool>().map_err(|e| {
+            async_graphql::Error::new(format!("Cannot get DBPool from context: {:?}", e))
+        })?;
+        let mut conn = pool
+            .get()
+            .await
+            .map_err(|e| async_graphql::Error::new(format!("Failed to get connection: {}", e)))?;
+        let (_, game_board) =
+            require_board_access(ctx, &mut conn, game_board_id, BoardAccess::View).await?;
+        let game_board = localize_game_board(&mut conn, game_board, locale.as_deref()).await?;
+        Ok(game_board)
+    }
+
+    /// Fetch all gameboards associated with a specific user, in `locale` where translated
+    async fn fetch_game_boards_from_user(
+        &self,
+        ctx: &Context<'_>,
+        user_id: i64,
+        locale: Option<String>,
+    ) -> Result<Vec<GameBoard>> {
+        let locale = requested_locale(locale.as_deref())?;
+        let pool = ctx.data::<DBPool>().map_err(|e| {
+            async_graphql::Error::new(format!("Cannot get DBPool from context: {:?}", e))
+        })?;
+        let mut conn = pool
+            .get()
+            .await
+            .map_err(|e| async_graphql::Error::new(format!("Failed to get connection: {}", e)))?;
+        require_self(ctx, &mut conn, user_id).await?;
+        let game_boards = GameBoard::fetch_by_user(&mut conn, user_id).await?;
+        let game_boards = localize_game_boards(&mut conn, game_boards, locale.as_deref()).await?;
+        Ok(game_boards)
+    }
+
+    /// Fetch every gameboard the requestor can see: their own, shared with them and public
+    async fn fetch_all_game_boards(&self, ctx: &Context<'_>) -> Result<Vec<GameBoard>> {
+        let pool = ctx.data::<DBPool>().map_err(|e| {
+            async_graphql::Error::new(format!("Cannot get DBPool from context: {:?}", e))
+        })?;
+        let mut conn = pool
+            .get()
+            .await
+            .map_err(|e| async_graphql::Error::new(format!("Failed to get connection: {}", e)))?;
+        // Anonymous callers only see public boards
+        let requestor = match get_user_from_context(ctx) {
+            Some(_) => Some(require_user(ctx, &mut conn).await?.id),
+            None => None,
+        };
+        let game_boards = GameBoard::fetch_visible_to(&mut conn, requestor).await?;
+        Ok(game_boards)
+    }
+
+    /// Fetch all archived gameboards from user
+    async fn fetch_archived_game_boards_from_user(
+        &self,
+        ctx: &Context<'_>,
+        user_id: i64,
+    ) -> Result<Vec<GameBoard>> {
+        let pool = ctx.data::<DBPool>().map_err(|e| {
+            async_graphql::Error::new(format!("Cannot get DBPool from context: {:?}", e))
+        })?;
+        let mut conn = pool
+            .get()
+            .await
+            .map_err(|e| async_graphql::Error::new(format!("Failed to get connection: {}", e)))?;
+        require_self(ctx, &mut conn, user_id).await?;
+        let game_boards = GameBoard::fetch_archived_by_user(&mut conn, user_id).await?;
+        Ok(game_boards)
+    }
+
+    /// Check whether a gameboard is complete and ready to play
+    async fn validate_game_board(
+        &self,
+        ctx: &Context<'_>,
+        game_board_id: i64,
+    ) -> Result<BoardValidationReport> {
         let pool = ctx.data::<DBPool>().map_err(|e| {
             async_graphql::Error::new(format!("Cannot get DBPool from context: {:?}", e))
         })?;
@@ -18,19 +119,92 @@ impl GameBoardQuery {
             .get()
             .await
             .map_err(|e| async_graphql::Error::new(format!("Failed to get connection: {}", e)))?;
-        let game_board: GameBoard = GameBoard::find_by_id(&mut conn, game_board_id)
+        require_board_access(ctx, &mut conn, game_board_id, BoardAccess::View).await?;
+        let report = validate_game_board(&mut conn, game_board_id)
             .await
             .map_err(|e| {
                 async_graphql::Error::new(format!(
-                    "Failed to fetch game board with ID {}: {}",
+                    "Failed to validate game board with ID {}: {}",
                     game_board_id, e
                 ))
             })?;
-        Ok(game_board)
+        Ok(report)
     }
 
-    /// Fetch all gameboards associated with a specific user
-    async fn fetch_game_boards_from_user(
+    /// Fetch every gameboard the requestor can see that uses a question, to show the impact of editing it
+    async fn fetch_game_boards_using_question(
+        &self,
+        ctx: &Context<'_>,
+        question_id: i64,
+    ) -> Result<Vec<GameBoard>> {
+        let pool = ctx.data::<DBPool>().map_err(|e| {
+            async_graphql::Error::new(format!("Cannot get DBPool from context: {:?}", e))
+        })?;
+        let mut conn = pool
+            .get()
+            .await
+            .map_err(|e| async_graphql::Error::new(format!("Failed to get connection: {}", e)))?;
+        let requestor = require_user(ctx, &mut conn).await?;
+        let mut visible = Vec::new();
+        for game_board in GameBoard::fetch_by_question_id(&mut conn, question_id).await? {
+            if board_access(&mut conn, &game_board, requestor.id)
+                .await?
+                .is_some()
+            {
+                visible.push(game_board);
+            }
+        }
+        Ok(visible)
+    }
+
+    /// Fetch the revision history of a gameboard, newest first
+    async fn game_board_revisions(
+        &self,
+        ctx: &Context<'_>,
+        board_id: i64,
+    ) -> Result<Vec<GameBoardRevision>> {
+        let pool = ctx.data::<DBPool>().map_err(|e| {
+            async_graphql::Error::new(format!("Cannot get DBPool from context: {:?}", e))
+        })?;
+        let mut conn = pool
+            .get()
+            .await
+            .map_err(|e| async_graphql::Error::new(format!("Failed to get connection: {}", e)))?;
+        require_board_access(ctx, &mut conn, board_id, BoardAccess::View).await?;
+        let revisions = GameBoardRevision::fetch_by_board_id(&mut conn, board_id).await?;
+        Ok(revisions)
+    }
+
+    /// List what changed between two revisions of the same gameboard
+    async fn diff_game_board_revisions(
+        &self,
+        ctx: &Context<'_>,
+        from_revision_id: i64,
+        to_revision_id: i64,
+    ) -> Result<Vec<RevisionChange>> {
+        let pool = ctx.data::<DBPool>().map_err(|e| {
+            async_graphql::Error::new(format!("Cannot get DBPool from context: {:?}", e))
+        })?;
+        let mut conn = pool
+            .get()
+            .await
+            .map_err(|e| async_graphql::Error::new(format!("Failed to get connection: {}", e)))?;
+        let from = GameBoardRevision::find_by_id(&mut conn, from_revision_id).await?;
+        let to = GameBoardRevision::find_by_id(&mut conn, to_revision_id).await?;
+        if from.board_id != to.board_id {
+            return Err(async_graphql::Error::new(
+                "Revisions belong to different game boards",
+            ));
+        }
+        require_board_access(ctx, &mut conn, from.board_id, BoardAccess::View).await?;
+        Ok(diff_snapshots(
+            &from.board_snapshot()?,
+            &to.board_snapshot()?,
+        ))
+    }
+
+    /// Fetch the live gameboards a user was invited to collaborate on
+    async fn fetch_game_boards_shared_with_user(
         &self,
         ctx: &Context<'_>,
         user_id: i64,
@@ -42,12 +216,19 @@ impl GameBoardQuery {
             .get()
             .await
             .map_err(|e| async_graphql::Error::new(format!("Failed to get connection: {}", e)))?;
-        let game_boards = GameBoard::fetch_by_user(&mut conn, user_id).await?;
+        require_self(ctx, &mut conn, user_id).await?;
+        let game_boards = GameBoard::fetch_shared_with_user(&mut conn, user_id).await?;
         Ok(game_boards)
     }
 
-    /// Fetch all gameboards in the database
-    async fn fetch_all_game_boards(&self, ctx: &Context<'_>) -> Result<Vec<GameBoard>> {
+    /// Open an unlisted or public gameboard from its share link, in `locale` if translated
+    async fn find_game_board_by_share_token(
+        &self,
+        ctx: &Context<'_>,
+        share_token: String,
+        locale: Option<String>,
+    ) -> Result<GameBoard> {
+        let locale = requested_locale(locale.as_deref())?;
         let pool = ctx.data::<DBPool>().map_err(|e| {
             async_graphql::Error::new(format!("Cannot get DBPool from context: {:?}", e))
         })?;
@@ -55,7 +236,31 @@ impl GameBoardQuery {
             .get()
             .await
             .map_err(|e| async_graphql::Error::new(format!("Failed to get connection: {}", e)))?;
-        let game_boards = GameBoard::all(&mut conn).await?;
-        Ok(game_boards)
+        match GameBoard::find_by_share_token(&mut conn, &share_token).await {
+            Ok(game_board) => {
+                Ok(localize_game_board(&mut conn, game_board, locale.as_deref()).await?)
+            }
+            Err(diesel::NotFound) => Err(async_graphql::Error::new(
+                "This share link is invalid or no longer shared",
+            )),
+            Err(e) => Err(async_graphql::Error::new(format!(
+                "Database error: {:?}",
+                e
+            ))),
+        }
+    }
+
+    /// Fetch a gameboard's visibility, share token and collaborators. Owner only
+    async fn fetch_board_sharing(&self, ctx: &Context<'_>, board_id: i64) -> Result<BoardSharing> {
+        let pool = ctx.data::<DBPool>().map_err(|e| {
+            async_graphql::Error::new(format!("Cannot get DBPool from context: {:?}", e))
+        })?;
+        let mut conn = pool
+            .get()
+            .await
+            .map_err(|e| async_graphql::Error::new(format!("Failed to get connection: {}", e)))?;
+        let (_, game_board) =
+            require_board_access(ctx, &mut conn, board_id, BoardAccess::Own).await?;
+        Ok(board_sharing(&mut conn, game_board).await?)
     }
 }
@@ -3,6 +3,10 @@
 use crate::db::pool::DBPool;
 use crate::models::question::Question;
 use crate::models::{GBQMapping, GBQ};
+use crate::services::board_access::{require_board_access, require_share_token, BoardAccess};
+use crate::services::localization::{
+    localize_board_questions, localize_question, requested_locale,
+};
 use async_graphql::{Context, Object, Result};
 
 #[derive(Default)]
@@ -10,13 +14,15 @@ pub struct GameBoardQuestionQuery;
 
 #[Object]
 impl GameBoardQuestionQuery {
-    /// Find GameBoardQuestion from game_board_id and question_id
+    /// Find GameBoardQuestion from game_board_id and question_id, in `locale` if translated
     async fn find_game_board_question(
         &self,
         ctx: &Context<'_>,
         game_board_id: i64,
         question_id: i64,
+        locale: Option<String>,
     ) -> Result<GBQ> {
+        let locale = requested_locale(locale.as_deref())?;
         let pool = ctx.data::<DBPool>().map_err(|e| {
             async_graphql::Error::new(format!("Cannot get DBPool from context: {:?}", e))
         })?;
@@ -24,22 +30,28 @@ impl GameBoardQuestionQuery {
             .get()
             .await
             .map_err(|e| async_graphql::Error::new(format!("Failed to get connection: {}", e)))?;
+        require_board_access(ctx, &mut conn, game_board_id, BoardAccess::View).await?;
         let mapping: GBQMapping =
             GBQMapping::find_mapping_by_board_and_question(&mut conn, game_board_id, question_id)
                 .await?;
         let question: Question = Question::find_by_id(&mut conn, question_id).await?;
+        let question = localize_question(&mut conn, question, locale.as_deref()).await?;
 
         let game_board_question: GBQ = GBQ { mapping, question };
 
         Ok(game_board_question)
     }
 
-    /// Fetch all GameBoardQuestions from board id
+    /// Fetch all GameBoardQuestions from board id, in `locale` where translated. `shareToken`
+    /// opens an unlisted board
     async fn fetch_game_board_questions(
         &self,
         ctx: &Context<'_>,
         game_board_id: i64,
+        locale: Option<String>,
+        share_token: Option<String>,
     ) -> Result<Vec<GBQ>> {
+        let locale = requested_locale(locale.as_deref())?;
         let pool = ctx.data::<DBPool>().map_err(|e| {
             async_graphql::Error::new(format!("Cannot get DBPool from context: {:?}", e))
         })?;
@@ -48,8 +60,18 @@ impl GameBoardQuestionQuery {
             .await
             .map_err(|e| async_graphql::Error::new(format!("Failed to get connection: {}", e)))?;
 
+        match share_token {
+            Some(share_token) => {
+                require_share_token(&mut conn, game_board_id, &share_token).await?;
+            }
+            None => {
+                require_board_access(ctx, &mut conn, game_board_id, BoardAccess::View).await?;
+            }
+        }
+
         let questions =
             GBQ::fetch_all_game_board_questions_by_board_id(&mut conn, game_board_id).await?;
+        let questions = localize_board_questions(&mut conn, questions, locale.as_deref()).await?;
 
         Ok(questions)
     }
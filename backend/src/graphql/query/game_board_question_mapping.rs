@@ -2,6 +2,7 @@
 
 use crate::db::pool::DBPool;
 use crate::models::GBQMapping;
+use crate::services::board_access::{require_board_access, BoardAccess};
 use async_graphql::{Context, Object, Result};
 
 #[derive(Default)]
@@ -23,7 +24,7 @@ impl GameBoardMappingQuery {
             .get()
             .await
             .map_err(|e| async_graphql::Error::new(format!("Failed to get connection: {}", e)))?;
-
+        require_board_access(ctx, &mut conn, game_board_id, BoardAccess::View).await?;
         let mapping =
             GBQMapping::find_mapping_by_board_and_question(&mut conn, game_board_id, question_id)
                 .await?;
@@ -43,7 +44,7 @@ impl GameBoardMappingQuery {
             .get()
             .await
             .map_err(|e| async_graphql::Error::new(format!("Failed to get connection: {}", e)))?;
-
+        require_board_access(ctx, &mut conn, game_board_id, BoardAccess::View).await?;
         let mappings = GBQMapping::fetch_mappings_by_board_id(&mut conn, game_board_id).await?;
 
         Ok(mappings)
@@ -1,8 +1,13 @@
 // Query modules
+pub mod gallery;
 pub mod game;
 pub mod game_board;
 pub mod game_board_question;
 pub mod game_board_question_mapping;
 pub mod player;
 pub mod question;
+pub mod response;
+pub mod team;
+pub mod timer;
+pub mod translation;
 pub mod user;
@@ -1,6 +1,8 @@
 // graphql/query/player.rs
 
+use crate::auth::firebase_auth::{require_owner, require_user};
 use crate::db::pool::DBPool;
+use crate::models::game::Game;
 use crate::models::player::Player;
 use async_graphql::{Context, Object, Result};
 
@@ -39,4 +41,21 @@ impl PlayerQuery {
         let players = Player::fetch_by_game_id(&mut conn, game_id).await?;
         Ok(players)
     }
+
+    /// Fetch the token a remote contestant sends with their responses. Host only
+    async fn fetch_player_token(&self, ctx: &Context<'_>, player_id: i64) -> Result<String> {
+        let pool = ctx.data::<DBPool>().map_err(|e| {
+            async_graphql::Error::new(format!("Cannot get DBPool from context: {:?}", e))
+        })?;
+        let mut conn = pool
+            .get()
+            .await
+            .map_err(|e| async_graphql::Error::new(format!("Failed to get connection: {}", e)))?;
+
+        let requestor = require_user(ctx, &mut conn).await?;
+        let player = Player::find_by_id(&mut conn, player_id).await?;
+        let game = Game::find_by_id(&mut conn, player.game_id).await?;
+        require_owner(&requestor, game.user_id, "game")?;
+        Ok(player.player_token)
+    }
 }
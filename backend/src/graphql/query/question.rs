@@ -2,6 +2,8 @@
 
 use crate::db::pool::DBPool;
 use crate::models::question::Question;
+use crate::services::answer_matching::{match_answer, AnswerMatch};
+use crate::services::localization::{localize_question, localize_questions, requested_locale};
 use async_graphql::{Context, Object, Result};
 
 #[derive(Default)]
@@ -9,8 +11,14 @@ pub struct QuestionQuery;
 
 #[Object]
 impl QuestionQuery {
-    /// Find a single question by id
-    async fn find_question(&self, ctx: &Context<'_>, question_id: i64) -> Result<Question> {
+    /// Find a single question by id, in `locale` if translated
+    async fn find_question(
+        &self,
+        ctx: &Context<'_>,
+        question_id: i64,
+        locale: Option<String>,
+    ) -> Result<Question> {
+        let locale = requested_locale(locale.as_deref())?;
         let pool = ctx.data::<DBPool>().map_err(|e| {
             async_graphql::Error::new(format!("Cannot get DBPool from context: {:?}", e))
         })?;
@@ -29,15 +37,18 @@ impl QuestionQuery {
         // }
 
         let question: Question = Question::find_by_id(&mut conn, question_id).await?;
+        let question = localize_question(&mut conn, question, locale.as_deref()).await?;
         Ok(question)
     }
 
-    /// Fetch all questions from a user
+    /// Fetch all questions from a user, in `locale` where translated
     async fn fetch_questions_from_user(
         &self,
         ctx: &Context<'_>,
         user_id: i64,
+        locale: Option<String>,
     ) -> Result<Vec<Question>> {
+        let locale = requested_locale(locale.as_deref())?;
         let pool = ctx.data::<DBPool>().map_err(|e| {
             async_graphql::Error::new(format!("Cannot get DBPool from context: {:?}", e))
         })?;
@@ -46,6 +57,7 @@ impl QuestionQuery {
             .await
             .map_err(|e| async_graphql::Error::new(format!("Failed to get connection: {}", e)))?;
         let questions: Vec<Question> = Question::fetch_by_user(&mut conn, user_id).await?;
+        let questions = localize_questions(&mut conn, questions, locale.as_deref()).await?;
         Ok(questions)
     }
 
@@ -62,12 +74,14 @@ impl QuestionQuery {
         Ok(questions)
     }
 
-    /// Fetch questions from list of ids
+    /// Fetch questions from list of ids, in `locale` where translated
     async fn fetch_questions_from_ids(
         &self,
         ctx: &Context<'_>,
         question_ids: Vec<i64>,
+        locale: Option<String>,
     ) -> Result<Vec<Question>> {
+        let locale = requested_locale(locale.as_deref())?;
         let pool = ctx.data::<DBPool>().map_err(|e| {
             async_graphql::Error::new(format!("Cannot get DBPool from context: {:?}", e))
         })?;
@@ -76,6 +90,48 @@ impl QuestionQuery {
             .await
             .map_err(|e| async_graphql::Error::new(format!("Failed to get connection: {}", e)))?;
         let questions: Vec<Question> = Question::fetch_by_ids(&mut conn, question_ids).await?;
+        let questions = localize_questions(&mut conn, questions, locale.as_deref()).await?;
         Ok(questions)
     }
+
+    /// Fetch all archived questions from user
+    async fn fetch_archived_questions_from_user(
+        &self,
+        ctx: &Context<'_>,
+        user_id: i64,
+    ) -> Result<Vec<Question>> {
+        let pool = ctx.data::<DBPool>().map_err(|e| {
+            async_graphql::Error::new(format!("Cannot get DBPool from context: {:?}", e))
+        })?;
+        let mut conn = pool
+            .get()
+            .await
+            .map_err(|e| async_graphql::Error::new(format!("Failed to get connection: {}", e)))?;
+        let questions = Question::fetch_archived_by_user(&mut conn, user_id).await?;
+        Ok(questions)
+    }
+
+    /// Compare a response with a question's answer and alternate answers to
+    /// suggest a verdict to the host
+    async fn check_answer(
+        &self,
+        ctx: &Context<'_>,
+        question_id: i64,
+        response: String,
+    ) -> Result<AnswerMatch> {
+        let pool = ctx.data::<DBPool>().map_err(|e| {
+            async_graphql::Error::new(format!("Cannot get DBPool from context: {:?}", e))
+        })?;
+        let mut conn = pool
+            .get()
+            .await
+            .map_err(|e| async_graphql::Error::new(format!("Failed to get connection: {}", e)))?;
+
+        let question: Question = Question::find_by_id(&mut conn, question_id).await?;
+        Ok(match_answer(
+            &response,
+            &question.answer,
+            &question.alternate_answers,
+        ))
+    }
 }
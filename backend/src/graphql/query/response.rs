@@ -0,0 +1,39 @@
+// graphql/query/response.rs
+
+use crate::auth::firebase_auth::{require_owner, require_user};
+use crate::db::pool::DBPool;
+use crate::models::game::Game;
+use crate::models::game_question::GameQuestion;
+use crate::services::typed_responses::{fetch_responses, RankedResponse};
+use async_graphql::{Context, Object, Result};
+
+#[derive(Default)]
+pub struct ResponseQuery;
+
+#[Object]
+impl ResponseQuery {
+    /// Fetch the typed responses to a game cell, optionally ranked by how
+    /// closely they match the answer. Host only
+    async fn fetch_responses(
+        &self,
+        ctx: &Context<'_>,
+        game_question_id: i64,
+        ranked: Option<bool>,
+    ) -> Result<Vec<RankedResponse>> {
+        let pool = ctx.data::<DBPool>().map_err(|e| {
+            async_graphql::Error::new(format!("Cannot get DBPool from context: {:?}", e))
+        })?;
+        let mut conn = pool
+            .get()
+            .await
+            .map_err(|e| async_graphql::Error::new(format!("Failed to get connection: {}", e)))?;
+
+        let requestor = require_user(ctx, &mut conn).await?;
+        let game_question = GameQuestion::find_by_id(&mut conn, game_question_id).await?;
+        let game = Game::find_by_id(&mut conn, game_question.game_id).await?;
+        require_owner(&requestor, game.user_id, "game")?;
+
+        let responses = fetch_responses(&mut conn, &game_question, ranked.unwrap_or(false)).await?;
+        Ok(responses)
+    }
+}
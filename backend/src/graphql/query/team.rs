@@ -0,0 +1,97 @@
+// graphql/query/team.rs
+
+use crate::auth::firebase_auth::{require_owner, require_user};
+use crate::db::pool::DBPool;
+use crate::models::game::Game;
+use crate::models::team::{Team, TeamMember};
+use crate::models::team_answer::TeamBuzz;
+use crate::services::team_play::{member_stats, MemberStats};
+use async_graphql::{Context, Object, Result};
+
+#[derive(Default)]
+pub struct TeamQuery;
+
+#[Object]
+impl TeamQuery {
+    /// Fetch all teams of a game
+    async fn fetch_teams(&self, ctx: &Context<'_>, game_id: i64) -> Result<Vec<Team>> {
+        let pool = ctx.data::<DBPool>().map_err(|e| {
+            async_graphql::Error::new(format!("Cannot get DBPool from context: {:?}", e))
+        })?;
+        let mut conn = pool
+            .get()
+            .await
+            .map_err(|e| async_graphql::Error::new(format!("Failed to get connection: {}", e)))?;
+
+        let teams = Team::fetch_by_game_id(&mut conn, game_id).await?;
+        Ok(teams)
+    }
+
+    /// Fetch all members of a team
+    async fn fetch_team_members(&self, ctx: &Context<'_>, team_id: i64) -> Result<Vec<TeamMember>> {
+        let pool = ctx.data::<DBPool>().map_err(|e| {
+            async_graphql::Error::new(format!("Cannot get DBPool from context: {:?}", e))
+        })?;
+        let mut conn = pool
+            .get()
+            .await
+            .map_err(|e| async_graphql::Error::new(format!("Failed to get connection: {}", e)))?;
+
+        let members = TeamMember::fetch_by_team_id(&mut conn, team_id).await?;
+        Ok(members)
+    }
+
+    /// Fetch the token a remote team member sends when buzzing in. Host only
+    async fn fetch_member_token(&self, ctx: &Context<'_>, member_id: i64) -> Result<String> {
+        let pool = ctx.data::<DBPool>().map_err(|e| {
+            async_graphql::Error::new(format!("Cannot get DBPool from context: {:?}", e))
+        })?;
+        let mut conn = pool
+            .get()
+            .await
+            .map_err(|e| async_graphql::Error::new(format!("Failed to get connection: {}", e)))?;
+
+        let requestor = require_user(ctx, &mut conn).await?;
+        let member = TeamMember::find_by_id(&mut conn, member_id).await?;
+        let team = Team::find_by_id(&mut conn, member.team_id).await?;
+        let game = Game::find_by_id(&mut conn, team.game_id).await?;
+        require_owner(&requestor, game.user_id, "game")?;
+        Ok(member.member_token)
+    }
+
+    /// Fetch the teams that buzzed in on a game question, first buzz first
+    async fn fetch_buzz_queue(
+        &self,
+        ctx: &Context<'_>,
+        game_question_id: i64,
+    ) -> Result<Vec<TeamBuzz>> {
+        let pool = ctx.data::<DBPool>().map_err(|e| {
+            async_graphql::Error::new(format!("Cannot get DBPool from context: {:?}", e))
+        })?;
+        let mut conn = pool
+            .get()
+            .await
+            .map_err(|e| async_graphql::Error::new(format!("Failed to get connection: {}", e)))?;
+
+        let buzzes = TeamBuzz::fetch_by_game_question_id(&mut conn, game_question_id).await?;
+        Ok(buzzes)
+    }
+
+    /// Fetch each team member's correct and incorrect answers in a game
+    async fn fetch_member_stats(
+        &self,
+        ctx: &Context<'_>,
+        game_id: i64,
+    ) -> Result<Vec<MemberStats>> {
+        let pool = ctx.data::<DBPool>().map_err(|e| {
+            async_graphql::Error::new(format!("Cannot get DBPool from context: {:?}", e))
+        })?;
+        let mut conn = pool
+            .get()
+            .await
+            .map_err(|e| async_graphql::Error::new(format!("Failed to get connection: {}", e)))?;
+
+        let stats = member_stats(&mut conn, game_id).await?;
+        Ok(stats)
+    }
+}
@@ -0,0 +1,28 @@
+// graphql/query/timer.rs
+
+use crate::db::pool::DBPool;
+use crate::services::game_timers::{current_state, TimerHub, TimerState};
+use async_graphql::{Context, Object, Result};
+
+#[derive(Default)]
+pub struct TimerQuery;
+
+#[Object]
+impl TimerQuery {
+    /// Fetch the current phase of a game's timer, stamped with the server time
+    async fn fetch_game_timer(&self, ctx: &Context<'_>, game_id: i64) -> Result<TimerState> {
+        let pool = ctx.data::<DBPool>().map_err(|e| {
+            async_graphql::Error::new(format!("Cannot get DBPool from context: {:?}", e))
+        })?;
+        let hub = ctx.data::<TimerHub>().map_err(|e| {
+            async_graphql::Error::new(format!("Cannot get TimerHub from context: {:?}", e))
+        })?;
+        let mut conn = pool
+            .get()
+            .await
+            .map_err(|e| async_graphql::Error::new(format!("Failed to get connection: {}", e)))?;
+
+        let state = current_state(&mut conn, hub, game_id).await?;
+        Ok(state)
+    }
+}
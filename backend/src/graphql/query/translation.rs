@@ -0,0 +1,36 @@
+// graphql/query/translation.rs
+
+use crate::db::pool::DBPool;
+use crate::services::board_access::{require_board_access, BoardAccess};
+use crate::services::localization::{export_translations, requested_locale};
+use async_graphql::{Context, Object, Result};
+
+#[derive(Default)]
+pub struct TranslationQuery;
+
+#[Object]
+impl TranslationQuery {
+    /// Export a gameboard's text and its translations into `locale` as JSON for translators
+    async fn export_translations(
+        &self,
+        ctx: &Context<'_>,
+        game_board_id: i64,
+        locale: String,
+    ) -> Result<String> {
+        let locale = requested_locale(Some(&locale))?.unwrap_or(locale);
+
+        let pool = ctx.data::<DBPool>().map_err(|e| {
+            async_graphql::Error::new(format!("Cannot get DBPool from context: {:?}", e))
+        })?;
+        let mut conn = pool
+            .get()
+            .await
+            .map_err(|e| async_graphql::Error::new(format!("Failed to get connection: {}", e)))?;
+
+        require_board_access(ctx, &mut conn, game_board_id, BoardAccess::View).await?;
+
+        let bundle = export_translations(&mut conn, game_board_id, &locale).await?;
+        serde_json::to_string_pretty(&bundle)
+            .map_err(|e| async_graphql::Error::new(format!("Failed to export: {}", e)))
+    }
+}
@@ -1,19 +1,27 @@
 // src/graphql/schema.rs
 
 use crate::db::pool::DBPool;
-use async_graphql::{EmptySubscription, MergedObject, Schema};
+use crate::services::board_control::ControlHub;
+use crate::services::game_timers::TimerHub;
+use crate::storage::{LocalStorage, MediaStore};
+use async_graphql::{MergedObject, MergedSubscription, Schema};
+use std::sync::Arc;
 
 use super::{
     mutations::{
-        game::GameMutation, game_board::GameBoardMutation,
+        gallery::GalleryMutation, game::GameMutation, game_board::GameBoardMutation,
         game_board_question_mapping::GameBoardMappingMutation, player::PlayerMutation,
-        question::QuestionMutation, user::UserMutation,
+        question::QuestionMutation, response::ResponseMutation, team::TeamMutation,
+        timer::TimerMutation, translation::TranslationMutation, user::UserMutation,
     },
     query::{
-        game::GameQuery, game_board::GameBoardQuery, game_board_question::GameBoardQuestionQuery,
+        gallery::GalleryQuery, game::GameQuery, game_board::GameBoardQuery,
+        game_board_question::GameBoardQuestionQuery,
         game_board_question_mapping::GameBoardMappingQuery, player::PlayerQuery,
-        question::QuestionQuery, user::UserQuery,
+        question::QuestionQuery, response::ResponseQuery, team::TeamQuery, timer::TimerQuery,
+        translation::TranslationQuery, user::UserQuery,
     },
+    subscriptions::{board_control::BoardControlSubscription, timer::TimerSubscription},
 };
 
 #[derive(MergedObject, Default)]
@@ -25,6 +33,11 @@ pub struct RootQuery(
     GameBoardMappingQuery,
     GameQuery,
     PlayerQuery,
+    GalleryQuery,
+    TeamQuery,
+    ResponseQuery,
+    TimerQuery,
+    TranslationQuery,
 );
 
 #[derive(MergedObject, Default)]
@@ -35,16 +48,46 @@ pub struct RootMutation(
     GameBoardMappingMutation,
     GameMutation,
     PlayerMutation,
+    GalleryMutation,
+    TeamMutation,
+    ResponseMutation,
+    TimerMutation,
+    TranslationMutation,
 );
 
-pub type AppSchema = Schema<RootQuery, RootMutation, EmptySubscription>;
+#[derive(MergedSubscription, Default)]
+pub struct RootSubscription(TimerSubscription, BoardControlSubscription);
+
+pub type AppSchema = Schema<RootQuery, RootMutation, RootSubscription>;
 
 pub fn create_schema(pool: DBPool) -> AppSchema {
+    create_schema_with_timers(pool, TimerHub::new())
+}
+
+/// Build the schema around an existing `TimerHub`, so the server's timer
+/// loop and the GraphQL resolvers publish to the same subscribers.
+pub fn create_schema_with_timers(pool: DBPool, timers: TimerHub) -> AppSchema {
+    create_app_schema(pool, timers, temporary_media_store())
+}
+
+/// Media storage for schemas built without one: a scratch directory with a
+/// signing key that only lasts as long as the process.
+fn temporary_media_store() -> MediaStore {
+    let root = std::env::temp_dir().join(format!("jeopardy-media-{}", std::process::id()));
+    let secret = uuid::Uuid::new_v4();
+    Arc::new(LocalStorage::new(root, "/media", secret.as_bytes()))
+}
+
+/// Build the schema with the timer hub and media storage the server uses.
+pub fn create_app_schema(pool: DBPool, timers: TimerHub, media: MediaStore) -> AppSchema {
     Schema::build(
         RootQuery::default(),
         RootMutation::default(),
-        EmptySubscription,
+        RootSubscription::default(),
     )
     .data(pool.clone())
+    .data(timers)
+    .data(ControlHub::new())
+    .data(media)
     .finish()
 }
@@ -0,0 +1,46 @@
+// graphql/subscriptions/board_control.rs
+
+use crate::db::pool::DBPool;
+use crate::services::board_control::{current_control, BoardControl, ControlHub};
+use async_graphql::futures_util::stream::{self, Stream, StreamExt};
+use async_graphql::{Context, Result, Subscription};
+use tokio::sync::broadcast::error::RecvError;
+
+#[derive(Default)]
+pub struct BoardControlSubscription;
+
+#[Subscription]
+impl BoardControlSubscription {
+    /// Receive who has control of a game's board now and every time it changes
+    async fn board_control(
+        &self,
+        ctx: &Context<'_>,
+        game_id: i64,
+    ) -> Result<impl Stream<Item = BoardControl>> {
+        let pool = ctx.data::<DBPool>().map_err(|e| {
+            async_graphql::Error::new(format!("Cannot get DBPool from context: {:?}", e))
+        })?;
+        let hub = ctx.data::<ControlHub>().map_err(|e| {
+            async_graphql::Error::new(format!("Cannot get ControlHub from context: {:?}", e))
+        })?;
+        let mut conn = pool
+            .get()
+            .await
+            .map_err(|e| async_graphql::Error::new(format!("Failed to get connection: {}", e)))?;
+
+        // Subscribe before reading the current control so no change is missed
+        let receiver = hub.subscribe();
+        let initial = current_control(&mut conn, game_id).await?;
+
+        let updates = stream::unfold(receiver, move |mut receiver| async move {
+            loop {
+                match receiver.recv().await {
+                    Ok(control) if control.game_id == game_id => return Some((control, receiver)),
+                    Ok(_) | Err(RecvError::Lagged(_)) => continue,
+                    Err(RecvError::Closed) => return None,
+                }
+            }
+        });
+        Ok(stream::once(async move { initial }).chain(updates))
+    }
+}
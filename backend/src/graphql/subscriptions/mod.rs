@@ -0,0 +1,3 @@
+// Subscription modules
+pub mod board_control;
+pub mod timer;
@@ -0,0 +1,46 @@
+// graphql/subscriptions/timer.rs
+
+use crate::db::pool::DBPool;
+use crate::services::game_timers::{current_state, TimerHub, TimerState};
+use async_graphql::futures_util::stream::{self, Stream, StreamExt};
+use async_graphql::{Context, Result, Subscription};
+use tokio::sync::broadcast::error::RecvError;
+
+#[derive(Default)]
+pub struct TimerSubscription;
+
+#[Subscription]
+impl TimerSubscription {
+    /// Receive a game's timer state now and every time its phase changes
+    async fn game_timer(
+        &self,
+        ctx: &Context<'_>,
+        game_id: i64,
+    ) -> Result<impl Stream<Item = TimerState>> {
+        let pool = ctx.data::<DBPool>().map_err(|e| {
+            async_graphql::Error::new(format!("Cannot get DBPool from context: {:?}", e))
+        })?;
+        let hub = ctx.data::<TimerHub>().map_err(|e| {
+            async_graphql::Error::new(format!("Cannot get TimerHub from context: {:?}", e))
+        })?;
+        let mut conn = pool
+            .get()
+            .await
+            .map_err(|e| async_graphql::Error::new(format!("Failed to get connection: {}", e)))?;
+
+        // Subscribe before reading the current state so no change is missed
+        let receiver = hub.subscribe();
+        let initial = current_state(&mut conn, hub, game_id).await?;
+
+        let updates = stream::unfold(receiver, move |mut receiver| async move {
+            loop {
+                match receiver.recv().await {
+                    Ok(state) if state.game_id == game_id => return Some((state, receiver)),
+                    Ok(_) | Err(RecvError::Lagged(_)) => continue,
+                    Err(RecvError::Closed) => return None,
+                }
+            }
+        });
+        Ok(stream::once(async move { initial }).chain(updates))
+    }
+}
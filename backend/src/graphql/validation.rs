@@ -0,0 +1,229 @@
+// src/graphql/validation.rs
+//! Declarative validation for GraphQL mutation inputs.
+//!
+//! Each `InputObject` implements [`Validate`] by describing its rules against a
+//! [`Validator`]. Every rule is checked, so the client receives all violations
+//! at once, each tagged with the GraphQL field path that caused it.
+//!
+//! # Example
+//! ```rust, ignore
+//! impl Validate for CreateQuestionInput {
+//!     fn validate(&self, v: &mut Validator) {
+//!         v.non_blank("question", &self.question)
+//!             .max_length("question", &self.question, MAX_QUESTION_LENGTH);
+//!     }
+//! }
+//!
+//! input.validated()?; // -> "Validation failed" with a `violations` extension
+//! ```
+
+use crate::services::localization::normalize_locale;
+use crate::services::rich_text::markdown_problems;
+use async_graphql::{ErrorExtensions, Value};
+
+/// Number of category columns on a game board.
+pub const GRID_COLS: i32 = 5;
+/// Number of point rows on a game board.
+pub const GRID_ROWS: i32 = 5;
+
+pub const MAX_TITLE_LENGTH: usize = 100;
+pub const MAX_CATEGORY_LENGTH: usize = 50;
+pub const MAX_QUESTION_LENGTH: usize = 1000;
+pub const MAX_ANSWER_LENGTH: usize = 500;
+pub const MAX_ALTERNATE_ANSWERS: usize = 10;
+pub const MAX_DISPLAY_NAME_LENGTH: usize = 32;
+pub const MAX_POINTS: i32 = 10_000;
+pub const MAX_TAGS: usize = 10;
+pub const MAX_TAG_LENGTH: usize = 30;
+/// Question difficulty runs from 1 (easiest) to this.
+pub const MAX_DIFFICULTY: i32 = 5;
+
+/// Words rejected in user-facing display names (usernames, player names).
+const BLOCKED_WORDS: &[&str] = &[
+    "arse", "asshole", "bastard", "bitch", "bollocks", "cock", "cunt", "dick", "fuck", "piss",
+    "prick", "shit", "slut", "twat", "wanker", "whore",
+];
+
+/// A single failed rule.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldViolation {
+    /// Path to the offending field, e.g. `title` or `categories[2]`.
+    pub field: String,
+    /// Human readable description of the problem.
+    pub message: String,
+}
+
+/// All violations collected while validating an input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidationErrors(pub Vec<FieldViolation>);
+
+impl ValidationErrors {
+    pub fn violations(&self) -> &[FieldViolation] {
+        &self.0
+    }
+
+    /// Returns true if any violation was reported against `field`.
+    pub fn has_field(&self, field: &str) -> bool {
+        self.0.iter().any(|v| v.field == field)
+    }
+}
+
+impl From<ValidationErrors> for async_graphql::Error {
+    fn from(errors: ValidationErrors) -> Self {
+        let violations: Vec<Value> = errors
+            .0
+            .iter()
+            .map(|v| {
+                let mut obj = async_graphql::indexmap::IndexMap::new();
+                obj.insert(
+                    async_graphql::Name::new("field"),
+                    Value::from(v.field.as_str()),
+                );
+                obj.insert(
+                    async_graphql::Name::new("message"),
+                    Value::from(v.message.as_str()),
+                );
+                Value::Object(obj)
+            })
+            .collect();
+
+        async_graphql::Error::new("Validation failed").extend_with(|_, e| {
+            e.set("code", "VALIDATION_FAILED");
+            e.set("violations", Value::List(violations));
+        })
+    }
+}
+
+/// Collects rule violations for an input.
+#[derive(Debug, Default)]
+pub struct Validator {
+    violations: Vec<FieldViolation>,
+}
+
+impl Validator {
+    /// Record a violation against `field`.
+    pub fn add(&mut self, field: impl Into<String>, message: impl Into<String>) -> &mut Self {
+        self.violations.push(FieldViolation {
+            field: field.into(),
+            message: message.into(),
+        });
+        self
+    }
+
+    /// The value must contain at least one non-whitespace character.
+    pub fn non_blank(&mut self, field: &str, value: &str) -> &mut Self {
+        if value.trim().is_empty() {
+            self.add(field, "must not be blank");
+        }
+        self
+    }
+
+    /// The value must be at most `max` characters long.
+    pub fn max_length(&mut self, field: &str, value: &str, max: usize) -> &mut Self {
+        if value.chars().count() > max {
+            self.add(field, format!("must be at most {} characters", max));
+        }
+        self
+    }
+
+    /// The value must lie within `min..=max`.
+    pub fn range(&mut self, field: &str, value: i32, min: i32, max: i32) -> &mut Self {
+        if !(min..=max).contains(&value) {
+            self.add(field, format!("must be between {} and {}", min, max));
+        }
+        self
+    }
+
+    /// The identifier must be a positive database id.
+    pub fn id(&mut self, field: &str, value: i64) -> &mut Self {
+        if value <= 0 {
+            self.add(field, "must be a positive id");
+        }
+        self
+    }
+
+    /// The row must be within the board's rows.
+    pub fn grid_row(&mut self, field: &str, row: i32) -> &mut Self {
+        self.range(field, row, 0, GRID_ROWS - 1)
+    }
+
+    /// The column must be within the board's category columns.
+    pub fn grid_col(&mut self, field: &str, col: i32) -> &mut Self {
+        self.range(field, col, 0, GRID_COLS - 1)
+    }
+
+    /// The value must be a question difficulty.
+    pub fn difficulty(&mut self, field: &str, value: i32) -> &mut Self {
+        self.range(field, value, 1, MAX_DIFFICULTY)
+    }
+
+    /// A name shown to other people: non-blank, bounded and free of profanity.
+    pub fn display_name(&mut self, field: &str, value: &str) -> &mut Self {
+        self.non_blank(field, value)
+            .max_length(field, value, MAX_DISPLAY_NAME_LENGTH);
+        if contains_profanity(value) {
+            self.add(field, "contains inappropriate language");
+        }
+        self
+    }
+
+    /// The value must be well-formed rich text, with all formatting closed.
+    pub fn markdown(&mut self, field: &str, value: &str) -> &mut Self {
+        for problem in markdown_problems(value) {
+            self.add(field, problem);
+        }
+        self
+    }
+
+    /// The value must be a locale tag such as `en` or `es-MX`.
+    pub fn locale(&mut self, field: &str, value: &str) -> &mut Self {
+        if normalize_locale(value).is_none() {
+            self.add(field, "must be a locale such as en or es-MX");
+        }
+        self
+    }
+
+    /// Validate each element of a list, reporting paths as `field[index]`.
+    pub fn each<T>(
+        &mut self,
+        field: &str,
+        values: &[T],
+        mut rule: impl FnMut(&mut Self, &str, &T),
+    ) -> &mut Self {
+        for (i, value) in values.iter().enumerate() {
+            rule(self, &format!("{}[{}]", field, i), value);
+        }
+        self
+    }
+
+    /// Consume the validator, failing if any rule was violated.
+    pub fn finish(self) -> Result<(), ValidationErrors> {
+        if self.violations.is_empty() {
+            Ok(())
+        } else {
+            Err(ValidationErrors(self.violations))
+        }
+    }
+}
+
+/// Implemented by GraphQL inputs to declare their validation rules.
+pub trait Validate {
+    /// Describe this input's rules against `v`.
+    fn validate(&self, v: &mut Validator);
+
+    /// Run all rules and return every violation found.
+    fn validated(&self) -> Result<(), ValidationErrors> {
+        let mut v = Validator::default();
+        self.validate(&mut v);
+        v.finish()
+    }
+}
+
+/// Returns true if any word in `value` is on the block list.
+pub fn contains_profanity(value: &str) -> bool {
+    value
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(|word| word.to_lowercase())
+        .any(|word| BLOCKED_WORDS.contains(&word.as_str()))
+}
@@ -4,3 +4,5 @@ pub mod auth;
 pub mod db;
 pub mod graphql;
 pub mod models;
+pub mod services;
+pub mod storage;
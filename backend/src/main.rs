@@ -1,17 +1,32 @@
 use async_graphql::http::{playground_source, GraphQLPlaygroundConfig};
-use async_graphql_axum::{GraphQLRequest, GraphQLResponse};
-use axum::extract::FromRequestParts;
+use async_graphql_axum::{GraphQLRequest, GraphQLResponse, GraphQLSubscription};
+use axum::extract::{FromRequestParts, Path, Query};
 use axum::response::IntoResponse;
 use axum::{extract::Extension, response::Html, routing::get, Router};
+use backend::auth::firebase_auth::ensure_firebase_keys;
 use backend::auth::firebase_auth::AuthenticatedUser;
-use backend::db::pool::create_app_pool;
-use backend::graphql::schema::{create_schema, AppSchema};
+use backend::db::migrations::{run_pending_migrations, schema_status};
+use backend::db::pool::{create_app_pool, DBPool};
+use backend::graphql::schema::{create_app_schema, AppSchema};
+use backend::models::game::Game;
+use backend::models::user::User;
+use backend::services::archive_import::import_archive_files;
+use backend::services::game_timers::{spawn_timer_loop, TimerHub};
+use backend::services::health::Readiness;
+use backend::services::media::{content_type_for_key, MAX_UPLOAD_BYTES};
+use backend::services::metrics::{Metrics, ACTIVE_GAME_MINUTES};
+use backend::storage::{LocalStorage, MediaStore, S3Storage};
 use dotenvy::dotenv;
 use http::header::{AUTHORIZATION, CONTENT_TYPE};
 use http::{HeaderValue, Method, StatusCode};
+use serde::Deserialize;
 use std::env;
 use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 use tower_http::cors::CorsLayer;
+use tower_http::limit::RequestBodyLimitLayer;
 use tracing::error;
 use tracing_subscriber::EnvFilter;
 
@@ -22,13 +37,69 @@ async fn root_handler() -> impl IntoResponse {
     )
 }
 
+/// How long the probes wait for a database connection.
+const PROBE_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Alive as long as the process can answer.
+async fn healthz_handler() -> impl IntoResponse {
+    (StatusCode::OK, "ok")
+}
+
+/// Ready for traffic once the database schema matches this build, a
+/// connection can be checked out, and the auth keys can be loaded.
+async fn readyz_handler(
+    readiness: Extension<Readiness>,
+    pool: Extension<DBPool>,
+) -> impl IntoResponse {
+    let mut problems = Vec::new();
+    if !readiness.is_migrated() {
+        problems.push("migrations pending".to_string());
+    }
+    match tokio::time::timeout(PROBE_TIMEOUT, pool.get()).await {
+        Ok(Ok(_)) => {}
+        Ok(Err(err)) => problems.push(format!("database: {}", err)),
+        Err(_) => problems.push("database: timed out waiting for a connection".to_string()),
+    }
+    if let Err(err) = ensure_firebase_keys().await.map_err(|err| err.to_string()) {
+        problems.push(format!("auth keys: {}", err));
+    }
+
+    if problems.is_empty() {
+        (StatusCode::OK, "ready".to_string())
+    } else {
+        (StatusCode::SERVICE_UNAVAILABLE, problems.join("\n"))
+    }
+}
+
+/// Metrics for Prometheus to scrape.
+async fn metrics_handler(
+    metrics: Extension<Metrics>,
+    pool: Extension<DBPool>,
+) -> impl IntoResponse {
+    let active_since = chrono::Utc::now() - chrono::Duration::minutes(ACTIVE_GAME_MINUTES);
+    let active_games = match tokio::time::timeout(PROBE_TIMEOUT, pool.get()).await {
+        Ok(Ok(mut conn)) => Game::count_active(&mut conn, active_since)
+            .await
+            .map_err(|err| tracing::warn!("Could not count active games: {:?}", err))
+            .ok(),
+        _ => None,
+    };
+    (
+        [(CONTENT_TYPE, "text/plain; version=0.0.4")],
+        metrics.render(&pool, active_games),
+    )
+}
+
 async fn graphql_playground() -> Html<String> {
-    Html(playground_source(GraphQLPlaygroundConfig::new("/graphql")))
+    Html(playground_source(
+        GraphQLPlaygroundConfig::new("/graphql").subscription_endpoint("/graphql/ws"),
+    ))
 }
 
 #[axum::debug_handler]
 async fn graphql_handler(
     schema: Extension<AppSchema>,
+    metrics: Extension<Metrics>,
     auth_user: Extension<Option<AuthenticatedUser>>,
     req: GraphQLRequest,
 ) -> impl IntoResponse {
@@ -42,7 +113,35 @@ async fn graphql_handler(
         tracing::info!("Unauthenticated GraphQL request");
     }
 
-    GraphQLResponse::from(schema.execute(request).await)
+    let operation = request.operation_name.clone();
+    let started = Instant::now();
+    let response = schema.execute(request).await;
+    metrics.observe(operation.as_deref(), started.elapsed(), response.is_ok());
+    GraphQLResponse::from(response)
+}
+
+#[derive(Deserialize)]
+struct SignedMediaQuery {
+    expires: i64,
+    signature: String,
+}
+
+/// Serve a locally stored media file to anyone holding a valid signed URL.
+async fn media_handler(
+    storage: Extension<LocalStorage>,
+    Path(key): Path<String>,
+    Query(query): Query<SignedMediaQuery>,
+) -> impl IntoResponse {
+    let now = chrono::Utc::now().timestamp();
+    if !storage.verify(&key, query.expires, &query.signature, now) {
+        return Err(StatusCode::FORBIDDEN);
+    }
+    let path = storage.path_for(&key).ok_or(StatusCode::NOT_FOUND)?;
+    let bytes = tokio::fs::read(path)
+        .await
+        .map_err(|_| StatusCode::NOT_FOUND)?;
+    let content_type = content_type_for_key(&key).unwrap_or("application/octet-stream");
+    Ok(([(CONTENT_TYPE, content_type)], bytes))
 }
 
 async fn auth_middleware(
@@ -73,6 +172,19 @@ async fn auth_middleware(
 
 #[tokio::main]
 async fn main() {
+    // `backend import-archive ...` imports archive files instead of serving
+    let args: Vec<String> = env::args().skip(1).collect();
+    if args
+        .first()
+        .is_some_and(|command| command == "import-archive")
+    {
+        if let Err(err) = import_archive(&args[1..]).await {
+            eprintln!("Import failed: {}", err);
+            std::process::exit(1);
+        }
+        return;
+    }
+
     // Wrap the application logic in run() to allow graceful error handling.
     if let Err(err) = run().await {
         error!("Application error: {}", err);
@@ -80,6 +192,38 @@ async fn main() {
     }
 }
 
+/// Import J-Archive TSV dumps and saved game pages into a user's library.
+///
+/// Usage: `backend import-archive --user-id <id> <file>...`
+async fn import_archive(args: &[String]) -> Result<(), Box<dyn std::error::Error>> {
+    const USAGE: &str = "Usage: backend import-archive --user-id <id> <file>...";
+    dotenv().ok();
+
+    let mut user_id: Option<i64> = None;
+    let mut paths: Vec<PathBuf> = Vec::new();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--user-id" => user_id = Some(args.next().ok_or(USAGE)?.parse()?),
+            path => paths.push(PathBuf::from(path)),
+        }
+    }
+    let user_id = user_id.ok_or(USAGE)?;
+    if paths.is_empty() {
+        return Err(USAGE.into());
+    }
+
+    let pool = create_app_pool()?;
+    let mut conn = pool.get().await?;
+    User::find_by_id(&mut conn, user_id)
+        .await?
+        .ok_or_else(|| format!("No user with id {}", user_id))?;
+
+    let summary = import_archive_files(&mut conn, user_id, &paths).await?;
+    println!("Imported {}", summary);
+    Ok(())
+}
+
 async fn run() -> Result<(), Box<dyn std::error::Error>> {
     // Load environment variables from .env file
     dotenv().ok();
@@ -105,8 +249,74 @@ async fn run() -> Result<(), Box<dyn std::error::Error>> {
     // Try to get a connection from our DBPool
     let pool = create_app_pool()?;
 
-    // Create graphql schema
-    let schema = create_schema(pool);
+    // Refuse to run against a schema that is ahead of this build
+    let database_url = env::var("DATABASE_URL")?;
+    let status = {
+        let database_url = database_url.clone();
+        tokio::task::spawn_blocking(move || schema_status(&database_url)).await?
+    }
+    .map_err(|err| format!("Could not read the schema version: {}", err))?;
+    if status.is_ahead() {
+        return Err(format!(
+            "The database has migrations this build does not know about ({}); deploy a newer build",
+            status.unknown.join(", ")
+        )
+        .into());
+    }
+    let run_migrations = env::var("RUN_MIGRATIONS").is_ok_and(|v| v == "true" || v == "1");
+    if !run_migrations && !status.pending.is_empty() {
+        tracing::warn!(
+            "Migrations pending: {}; staying unready. Run `jeopardy-admin migrate` and restart, or set RUN_MIGRATIONS=true",
+            status.pending.join(", ")
+        );
+    }
+
+    // Store media in S3 when configured to, otherwise on local disk
+    let local_media = match env::var("MEDIA_STORAGE").as_deref() {
+        Ok("s3") => None,
+        _ => Some(LocalStorage::from_env()?),
+    };
+    let media: MediaStore = match local_media {
+        Some(ref local) => Arc::new(local.clone()),
+        None => Arc::new(S3Storage::from_env()?),
+    };
+
+    // Create graphql schema, sharing the timer hub with the timer loop
+    let timers = TimerHub::new();
+    let schema = create_app_schema(pool.clone(), timers.clone(), media);
+
+    // Apply pending migrations in the background so /readyz can report on
+    // them; the timer loop needs the current schema, so it waits too
+    let readiness = Readiness::new();
+    let probe_pool = pool.clone();
+    let startup_readiness = readiness.clone();
+    tokio::spawn(async move {
+        if !status.pending.is_empty() {
+            if !run_migrations {
+                // Stay unready until the migrations are applied and the
+                // server restarted, rather than serve an outdated schema
+                return;
+            }
+            tracing::info!("Applying migrations: {}", status.pending.join(", "));
+            let applied =
+                tokio::task::spawn_blocking(move || run_pending_migrations(&database_url))
+                    .await
+                    .map_err(|err| err.to_string())
+                    .and_then(|result| result.map_err(|err| err.to_string()));
+            match applied {
+                Ok(applied) => tracing::info!("Applied migrations: {}", applied.join(", ")),
+                Err(err) => {
+                    // Stay unready rather than serve an outdated schema
+                    tracing::error!("Migrations failed: {}", err);
+                    return;
+                }
+            }
+        }
+        startup_readiness.mark_migrated();
+
+        // Advance game timers on the server as their phases run out
+        spawn_timer_loop(pool, timers);
+    });
 
     let default_origin = "http://localhost:3000".to_string();
     let allowed_origins: Vec<HeaderValue> = match env::var("ALLOWED_ORIGINS") {
@@ -132,15 +342,43 @@ async fn run() -> Result<(), Box<dyn std::error::Error>> {
     // Add Firebase project ID to app state
     let app_state = firebase_project_id;
 
-    let app = Router::new()
+    let mut app = Router::new()
         .route("/", get(root_handler))
-        .route("/graphql", get(graphql_playground).post(graphql_handler))
+        .route(
+            "/graphql",
+            get(graphql_playground)
+                .post(graphql_handler)
+                // Leave room for the largest media upload and its form fields
+                .layer(RequestBodyLimitLayer::new(MAX_UPLOAD_BYTES + 1024 * 1024)),
+        )
+        .route_service("/graphql/ws", GraphQLSubscription::new(schema.clone()));
+
+    // Locally stored media is served by the backend itself
+    if let Some(local) = local_media {
+        app = app
+            .route("/media/{*key}", get(media_handler))
+            .layer(Extension(local));
+    }
+
+    let metrics = Metrics::new();
+    let app = app
         .layer(Extension(schema))
+        .layer(Extension(metrics.clone()))
         .layer(Extension(app_state))
         .layer(axum::middleware::from_fn(auth_middleware)) // Overrides with Some(user) if exists
         .layer(Extension(None::<AuthenticatedUser>)) // Default empty user
         .layer(cors);
 
+    // Probes and scrapes skip authentication and CORS
+    let probes = Router::new()
+        .route("/healthz", get(healthz_handler))
+        .route("/readyz", get(readyz_handler))
+        .route("/metrics", get(metrics_handler))
+        .layer(Extension(readiness))
+        .layer(Extension(metrics))
+        .layer(Extension(probe_pool));
+    let app = app.merge(probes);
+
     let port = env::var("PORT")
         .unwrap_or_else(|_| "8080".to_string())
         .parse::<u16>()
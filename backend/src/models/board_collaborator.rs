@@ -0,0 +1,158 @@
+// models/board_collaborator.rs
+//! This module contains the `BoardCollaborator` struct and related logic.
+//!
+//! A collaborator is a user, other than the owner, who was invited to a game
+//! board as an editor or a viewer.
+
+use crate::db::schema::board_collaborators;
+use crate::models::game_board::GameBoard;
+use crate::models::user::User;
+use async_graphql::{Enum, SimpleObject};
+use chrono::{DateTime, Utc};
+use diesel::deserialize::{self, FromSql, FromSqlRow};
+use diesel::expression::AsExpression;
+use diesel::pg::{Pg, PgValue};
+use diesel::prelude::*;
+use diesel::serialize::{self, Output, ToSql};
+use diesel::sql_types::Text;
+use diesel::upsert::excluded;
+use diesel_async::{AsyncPgConnection, RunQueryDsl};
+
+/// What an invited collaborator may do with a game board.
+#[derive(Enum, Copy, Clone, Debug, PartialEq, Eq, AsExpression, FromSqlRow)]
+#[diesel(sql_type = Text)]
+pub enum CollaboratorRole {
+    /// May change the board's title, categories and cells.
+    Editor,
+    /// May see and clone the board.
+    Viewer,
+}
+
+impl CollaboratorRole {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CollaboratorRole::Editor => "editor",
+            CollaboratorRole::Viewer => "viewer",
+        }
+    }
+}
+
+impl ToSql<Text, Pg> for CollaboratorRole {
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Pg>) -> serialize::Result {
+        <str as ToSql<Text, Pg>>::to_sql(self.as_str(), out)
+    }
+}
+
+impl FromSql<Text, Pg> for CollaboratorRole {
+    fn from_sql(bytes: PgValue<'_>) -> deserialize::Result<Self> {
+        match <String as FromSql<Text, Pg>>::from_sql(bytes)?.as_str() {
+            "editor" => Ok(CollaboratorRole::Editor),
+            "viewer" => Ok(CollaboratorRole::Viewer),
+            other => Err(format!("Unknown collaborator role: {}", other).into()),
+        }
+    }
+}
+
+/// Represents a user invited to a game board.
+#[derive(Identifiable, Associations, Queryable, Selectable, Debug, SimpleObject, Clone)]
+#[diesel(primary_key(board_id, user_id))]
+#[diesel(table_name = board_collaborators)]
+#[diesel(belongs_to(GameBoard, foreign_key = board_id))]
+#[diesel(belongs_to(User))]
+pub struct BoardCollaborator {
+    pub board_id: i64,
+    pub user_id: i64,
+    pub role: CollaboratorRole,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Represents a new collaborator to be inserted into the database.
+#[derive(Debug, Insertable)]
+#[diesel(table_name = board_collaborators)]
+pub struct NewBoardCollaborator {
+    pub board_id: i64,
+    pub user_id: i64,
+    pub role: CollaboratorRole,
+}
+
+impl BoardCollaborator {
+    /// Find a user's collaboration on a board.
+    ///
+    /// # Arguments
+    /// * `conn` - A mutable reference to an async PostgreSQL connection.
+    /// * `board_id` - The unique identifier of the game board.
+    /// * `user_id` - The unique identifier of the collaborator.
+    ///
+    /// # Returns
+    /// A `Result` containing the collaborator or a Diesel error.
+    pub async fn find(
+        conn: &mut AsyncPgConnection,
+        board_id: i64,
+        user_id: i64,
+    ) -> Result<Self, diesel::result::Error> {
+        board_collaborators::table
+            .find((board_id, user_id))
+            .first(conn)
+            .await
+    }
+
+    /// Fetch every collaborator of a board.
+    ///
+    /// # Arguments
+    /// * `conn` - A mutable reference to an async PostgreSQL connection.
+    /// * `board_id` - The unique identifier of the game board.
+    ///
+    /// # Returns
+    /// A `Result` containing a vector of collaborators or a Diesel error.
+    pub async fn fetch_by_board_id(
+        conn: &mut AsyncPgConnection,
+        board_id: i64,
+    ) -> Result<Vec<Self>, diesel::result::Error> {
+        board_collaborators::table
+            .filter(board_collaborators::board_id.eq(board_id))
+            .order(board_collaborators::created_at)
+            .load::<Self>(conn)
+            .await
+    }
+
+    /// Invite a user to a board, or change the role of an existing
+    /// collaborator.
+    ///
+    /// # Arguments
+    /// * `conn` - A mutable reference to an async PostgreSQL connection.
+    /// * `new_collaborator` - The board, user and role to grant.
+    ///
+    /// # Returns
+    /// A `Result` containing the collaborator or a Diesel error.
+    pub async fn upsert(
+        conn: &mut AsyncPgConnection,
+        new_collaborator: NewBoardCollaborator,
+    ) -> Result<Self, diesel::result::Error> {
+        diesel::insert_into(board_collaborators::table)
+            .values(&new_collaborator)
+            .on_conflict((board_collaborators::board_id, board_collaborators::user_id))
+            .do_update()
+            .set(board_collaborators::role.eq(excluded(board_collaborators::role)))
+            .get_result(conn)
+            .await
+    }
+
+    /// Remove a user from a board's collaborators.
+    ///
+    /// # Arguments
+    /// * `conn` - A mutable reference to an async PostgreSQL connection.
+    /// * `board_id` - The unique identifier of the game board.
+    /// * `user_id` - The unique identifier of the collaborator.
+    ///
+    /// # Returns
+    /// A `Result` containing the number of rows affected or a Diesel error.
+    pub async fn remove(
+        conn: &mut AsyncPgConnection,
+        board_id: i64,
+        user_id: i64,
+    ) -> Result<usize, diesel::result::Error> {
+        diesel::delete(board_collaborators::table.find((board_id, user_id)))
+            .execute(conn)
+            .await
+    }
+}
@@ -0,0 +1,142 @@
+// models/board_rating.rs
+//! This module contains the `BoardRating` and `BoardFavourite` structs and
+//! related logic, used to rank boards in the public gallery.
+
+use crate::db::schema::{board_favourites, board_ratings};
+use crate::models::game_board::GameBoard;
+use crate::models::user::User;
+use async_graphql::SimpleObject;
+use chrono::{DateTime, Utc};
+use diesel::prelude::*;
+use diesel::upsert::excluded;
+use diesel_async::{AsyncPgConnection, RunQueryDsl};
+
+/// Lowest star rating a user can give a board.
+pub const MIN_RATING: i32 = 1;
+/// Highest star rating a user can give a board.
+pub const MAX_RATING: i32 = 5;
+
+/// Represents a user's star rating of a game board.
+#[derive(Identifiable, Associations, Queryable, Selectable, Debug, SimpleObject, Clone)]
+#[diesel(primary_key(board_id, user_id))]
+#[diesel(table_name = board_ratings)]
+#[diesel(belongs_to(GameBoard, foreign_key = board_id))]
+#[diesel(belongs_to(User))]
+pub struct BoardRating {
+    pub board_id: i64,
+    pub user_id: i64,
+    pub rating: i32,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Represents a game board a user marked as a favourite.
+#[derive(Identifiable, Associations, Queryable, Selectable, Debug, SimpleObject, Clone)]
+#[diesel(primary_key(board_id, user_id))]
+#[diesel(table_name = board_favourites)]
+#[diesel(belongs_to(GameBoard, foreign_key = board_id))]
+#[diesel(belongs_to(User))]
+pub struct BoardFavourite {
+    pub board_id: i64,
+    pub user_id: i64,
+    pub created_at: DateTime<Utc>,
+}
+
+impl BoardRating {
+    /// Rate a board, replacing the user's previous rating if any.
+    ///
+    /// # Arguments
+    /// * `conn` - A mutable reference to an async PostgreSQL connection.
+    /// * `board_id` - The unique identifier of the game board.
+    /// * `user_id` - The unique identifier of the rating user.
+    /// * `rating` - The star rating, from `MIN_RATING` to `MAX_RATING`.
+    ///
+    /// # Returns
+    /// A `Result` containing the rating or a Diesel error.
+    pub async fn rate(
+        conn: &mut AsyncPgConnection,
+        board_id: i64,
+        user_id: i64,
+        rating: i32,
+    ) -> Result<Self, diesel::result::Error> {
+        diesel::insert_into(board_ratings::table)
+            .values((
+                board_ratings::board_id.eq(board_id),
+                board_ratings::user_id.eq(user_id),
+                board_ratings::rating.eq(rating),
+            ))
+            .on_conflict((board_ratings::board_id, board_ratings::user_id))
+            .do_update()
+            .set((
+                board_ratings::rating.eq(excluded(board_ratings::rating)),
+                board_ratings::updated_at.eq(Utc::now()),
+            ))
+            .get_result(conn)
+            .await
+    }
+
+    /// Remove a user's rating of a board.
+    ///
+    /// # Arguments
+    /// * `conn` - A mutable reference to an async PostgreSQL connection.
+    /// * `board_id` - The unique identifier of the game board.
+    /// * `user_id` - The unique identifier of the rating user.
+    ///
+    /// # Returns
+    /// A `Result` containing the number of rows affected or a Diesel error.
+    pub async fn remove(
+        conn: &mut AsyncPgConnection,
+        board_id: i64,
+        user_id: i64,
+    ) -> Result<usize, diesel::result::Error> {
+        diesel::delete(board_ratings::table.find((board_id, user_id)))
+            .execute(conn)
+            .await
+    }
+}
+
+impl BoardFavourite {
+    /// Mark a board as one of the user's favourites. Does nothing if it
+    /// already is.
+    ///
+    /// # Arguments
+    /// * `conn` - A mutable reference to an async PostgreSQL connection.
+    /// * `board_id` - The unique identifier of the game board.
+    /// * `user_id` - The unique identifier of the user.
+    ///
+    /// # Returns
+    /// A `Result` containing the number of rows inserted or a Diesel error.
+    pub async fn add(
+        conn: &mut AsyncPgConnection,
+        board_id: i64,
+        user_id: i64,
+    ) -> Result<usize, diesel::result::Error> {
+        diesel::insert_into(board_favourites::table)
+            .values((
+                board_favourites::board_id.eq(board_id),
+                board_favourites::user_id.eq(user_id),
+            ))
+            .on_conflict_do_nothing()
+            .execute(conn)
+            .await
+    }
+
+    /// Remove a board from the user's favourites.
+    ///
+    /// # Arguments
+    /// * `conn` - A mutable reference to an async PostgreSQL connection.
+    /// * `board_id` - The unique identifier of the game board.
+    /// * `user_id` - The unique identifier of the user.
+    ///
+    /// # Returns
+    /// A `Result` containing the number of rows affected or a Diesel error.
+    pub async fn remove(
+        conn: &mut AsyncPgConnection,
+        board_id: i64,
+        user_id: i64,
+    ) -> Result<usize, diesel::result::Error> {
+        diesel::delete(board_favourites::table.find((board_id, user_id)))
+            .execute(conn)
+            .await
+    }
+}
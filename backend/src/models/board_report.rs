@@ -0,0 +1,121 @@
+// models/board_report.rs
+//! This module contains the `BoardReport` struct and related logic.
+//!
+//! Users report public boards with inappropriate content. Reports stay open
+//! until a moderator resolves them.
+
+use crate::db::schema::board_reports;
+use crate::models::game_board::GameBoard;
+use crate::models::user::User;
+use async_graphql::SimpleObject;
+use chrono::{DateTime, Utc};
+use derive_builder::Builder;
+use diesel::prelude::*;
+use diesel_async::{AsyncPgConnection, RunQueryDsl};
+
+/// Represents a user's report of a game board.
+#[derive(
+    Identifiable, Associations, Queryable, Selectable, Debug, SimpleObject, Builder, Clone,
+)]
+#[diesel(table_name = board_reports)]
+#[diesel(belongs_to(GameBoard, foreign_key = board_id))]
+#[diesel(belongs_to(User))]
+pub struct BoardReport {
+    pub id: i64,
+    pub created_at: DateTime<Utc>,
+    pub board_id: i64,
+    pub user_id: i64,
+    /// Why the user reported the board.
+    pub reason: String,
+    /// The timestamp when a moderator dealt with the report, if one has.
+    pub resolved_at: Option<DateTime<Utc>>,
+}
+
+/// Represents a new report to be inserted into the database.
+#[derive(Debug, Insertable, Builder)]
+#[diesel(table_name = board_reports)]
+pub struct NewBoardReport {
+    pub board_id: i64,
+    pub user_id: i64,
+    pub reason: String,
+}
+
+impl BoardReport {
+    /// Create a new report.
+    ///
+    /// # Arguments
+    /// * `conn` - A mutable reference to an async PostgreSQL connection.
+    /// * `new_report` - A `NewBoardReport` instance containing the report's data.
+    ///
+    /// # Returns
+    /// A `Result` containing the new report, or a unique violation if the
+    /// user already has an open report on the board.
+    pub async fn create(
+        conn: &mut AsyncPgConnection,
+        new_report: NewBoardReport,
+    ) -> Result<Self, diesel::result::Error> {
+        diesel::insert_into(board_reports::table)
+            .values(&new_report)
+            .get_result(conn)
+            .await
+    }
+
+    /// Fetch the open reports of every board, oldest first.
+    ///
+    /// # Arguments
+    /// * `conn` - A mutable reference to an async PostgreSQL connection.
+    ///
+    /// # Returns
+    /// A `Result` containing a vector of reports or a Diesel error.
+    pub async fn fetch_open(
+        conn: &mut AsyncPgConnection,
+    ) -> Result<Vec<Self>, diesel::result::Error> {
+        board_reports::table
+            .filter(board_reports::resolved_at.is_null())
+            .order(board_reports::created_at)
+            .load::<Self>(conn)
+            .await
+    }
+
+    /// Count the open reports of a board.
+    ///
+    /// # Arguments
+    /// * `conn` - A mutable reference to an async PostgreSQL connection.
+    /// * `board_id` - The unique identifier of the game board.
+    ///
+    /// # Returns
+    /// A `Result` containing the number of open reports or a Diesel error.
+    pub async fn count_open(
+        conn: &mut AsyncPgConnection,
+        board_id: i64,
+    ) -> Result<i64, diesel::result::Error> {
+        board_reports::table
+            .filter(board_reports::board_id.eq(board_id))
+            .filter(board_reports::resolved_at.is_null())
+            .count()
+            .get_result(conn)
+            .await
+    }
+
+    /// Resolve every open report of a board.
+    ///
+    /// # Arguments
+    /// * `conn` - A mutable reference to an async PostgreSQL connection.
+    /// * `board_id` - The unique identifier of the game board.
+    ///
+    /// # Returns
+    /// A `Result` containing the number of reports resolved or a Diesel error.
+    pub async fn resolve_all(
+        conn: &mut AsyncPgConnection,
+        board_id: i64,
+    ) -> Result<usize, diesel::result::Error> {
+        diesel::update(
+            board_reports::table
+                .filter(board_reports::board_id.eq(board_id))
+                .filter(board_reports::resolved_at.is_null()),
+        )
+        .set(board_reports::resolved_at.eq(Some(Utc::now())))
+        .execute(conn)
+        .await
+    }
+}
@@ -1,12 +1,16 @@
 // models/game.rs
 
-use crate::db::schema::games;
+use crate::db::schema::{game_events, games};
+use crate::models::game_board::GameBoard;
+use crate::models::game_board_revision::GameBoardRevision;
+use crate::models::game_question::GameQuestion;
 use crate::models::user::User;
 use async_graphql::SimpleObject;
 use chrono::{DateTime, Utc};
 use derive_builder::Builder;
 use diesel::prelude::*;
-use diesel_async::{AsyncPgConnection, RunQueryDsl};
+use diesel_async::scoped_futures::ScopedFutureExt;
+use diesel_async::{AsyncConnection, AsyncPgConnection, RunQueryDsl};
 
 /// Represents a game in the application.
 ///
@@ -24,6 +28,26 @@ pub struct Game {
     pub updated_at: DateTime<Utc>,
     pub user_id: i64,
     pub game_board_id: i64,
+    /// The timestamp when the game was archived, if it has been.
+    pub archived_at: Option<DateTime<Utc>>,
+    /// The board revision this game was created from.
+    pub board_revision_id: Option<i64>,
+    /// The board title, frozen when the game was created.
+    pub title: String,
+    /// The board categories, frozen when the game was created.
+    pub categories: Vec<Option<String>>,
+    /// The game question currently open for typed responses.
+    pub open_question_id: Option<i64>,
+    /// When the open question stops accepting responses.
+    pub response_deadline: Option<DateTime<Utc>>,
+    /// The round being played, starting at 1.
+    pub round: i32,
+    /// When the host paused the game, if it is paused.
+    pub paused_at: Option<DateTime<Utc>>,
+    /// The player who picks the next cell.
+    pub control_player_id: Option<i64>,
+    /// The team that picks the next cell, when teams are playing.
+    pub control_team_id: Option<i64>,
 }
 
 /// Represents a new game to be inserted into the database.
@@ -32,6 +56,9 @@ pub struct Game {
 pub struct NewGame {
     pub user_id: i64,
     pub game_board_id: i64,
+    pub board_revision_id: Option<i64>,
+    pub title: String,
+    pub categories: Vec<Option<String>>,
 }
 
 impl Game {
@@ -50,7 +77,7 @@ impl Game {
         games::table.find(game_id).first(conn).await
     }
 
-    /// Fetch all games from the database.
+    /// Fetch all live (non-archived) games from the database.
     ///
     /// # Arguments
     /// * `conn` - A mutable reference to an async PostgreSQL connection.
@@ -58,10 +85,13 @@ impl Game {
     /// # Returns
     /// A `Result` containing a vector of games or a Diesel error.
     pub async fn all(conn: &mut AsyncPgConnection) -> Result<Vec<Self>, diesel::result::Error> {
-        games::table.load::<Self>(conn).await
+        games::table
+            .filter(games::archived_at.is_null())
+            .load::<Self>(conn)
+            .await
     }
 
-    /// Fetch all games created by a specific user.
+    /// Fetch all live (non-archived) games created by a specific user.
     ///
     /// # Arguments
     /// * `conn` - A mutable reference to an async PostgreSQL connection.
@@ -75,10 +105,58 @@ impl Game {
     ) -> Result<Vec<Self>, diesel::result::Error> {
         games::table
             .filter(games::user_id.eq(user_id))
+            .filter(games::archived_at.is_null())
             .load::<Self>(conn)
             .await
     }
 
+    /// Fetch all archived games created by a specific user.
+    ///
+    /// # Arguments
+    /// * `conn` - A mutable reference to an async PostgreSQL connection.
+    /// * `user_id` - The unique identifier of the user.
+    ///
+    /// # Returns
+    /// A `Result` containing a vector of archived games or a Diesel error.
+    pub async fn fetch_archived_by_user(
+        conn: &mut AsyncPgConnection,
+        user_id: i64,
+    ) -> Result<Vec<Self>, diesel::result::Error> {
+        games::table
+            .filter(games::user_id.eq(user_id))
+            .filter(games::archived_at.is_not_null())
+            .load::<Self>(conn)
+            .await
+    }
+
+    /// Count live (non-archived) games that something has happened in since
+    /// `active_since`.
+    ///
+    /// # Arguments
+    /// * `conn` - A mutable reference to an async PostgreSQL connection.
+    /// * `active_since` - Games quiet since before this are not counted.
+    ///
+    /// # Returns
+    /// A `Result` containing the number of active games or a Diesel error.
+    pub async fn count_active(
+        conn: &mut AsyncPgConnection,
+        active_since: DateTime<Utc>,
+    ) -> Result<i64, diesel::result::Error> {
+        let recent_event = game_events::table
+            .filter(game_events::game_id.eq(games::id))
+            .filter(game_events::created_at.ge(active_since));
+        games::table
+            .filter(games::archived_at.is_null())
+            .filter(
+                games::updated_at
+                    .ge(active_since)
+                    .or(diesel::dsl::exists(recent_event)),
+            )
+            .count()
+            .get_result(conn)
+            .await
+    }
+
     /// Create a new game in the database.
     ///
     /// # Arguments
@@ -96,4 +174,252 @@ impl Game {
             .get_result(conn)
             .await
     }
+
+    /// Start a new game on a board.
+    ///
+    /// The board's title, categories and cells are copied into the game so
+    /// later edits to the board do not affect it, and the game is pinned to
+    /// the board revision it was started from. Games started on a board that
+    /// has not changed since its latest revision share that revision rather
+    /// than recording a duplicate.
+    ///
+    /// # Arguments
+    /// * `conn` - A mutable reference to an async PostgreSQL connection.
+    /// * `user_id` - The unique identifier of the user hosting the game.
+    /// * `game_board_id` - The unique identifier of the game board to play.
+    ///
+    /// # Returns
+    /// A `Result` containing the newly created game or a Diesel error.
+    pub async fn start(
+        conn: &mut AsyncPgConnection,
+        user_id: i64,
+        game_board_id: i64,
+    ) -> Result<Self, diesel::result::Error> {
+        conn.transaction::<_, diesel::result::Error, _>(|conn| {
+            async move {
+                let board = GameBoard::find_by_id(conn, game_board_id).await?;
+                let revision = GameBoardRevision::record(conn, game_board_id).await?;
+
+                let game = Game::create(
+                    conn,
+                    NewGame {
+                        user_id,
+                        game_board_id,
+                        board_revision_id: Some(revision.id),
+                        title: board.title,
+                        categories: board.categories,
+                    },
+                )
+                .await?;
+                GameQuestion::freeze_board(conn, game.id, game_board_id).await?;
+
+                Ok(game)
+            }
+            .scope_boxed()
+        })
+        .await
+    }
+
+    /// Archive (soft-delete) a game. Its players and scores are kept.
+    ///
+    /// # Arguments
+    /// * `conn` - A mutable reference to an async PostgreSQL connection.
+    /// * `game_id` - The unique identifier of the game to archive.
+    ///
+    /// # Returns
+    /// A `Result` containing the archived game or a Diesel error.
+    pub async fn archive(
+        conn: &mut AsyncPgConnection,
+        game_id: i64,
+    ) -> Result<Self, diesel::result::Error> {
+        diesel::update(games::table.find(game_id))
+            .set(games::archived_at.eq(Some(Utc::now())))
+            .get_result(conn)
+            .await
+    }
+
+    /// Restore a previously archived game.
+    ///
+    /// # Arguments
+    /// * `conn` - A mutable reference to an async PostgreSQL connection.
+    /// * `game_id` - The unique identifier of the game to restore.
+    ///
+    /// # Returns
+    /// A `Result` containing the restored game or a Diesel error.
+    pub async fn restore(
+        conn: &mut AsyncPgConnection,
+        game_id: i64,
+    ) -> Result<Self, diesel::result::Error> {
+        diesel::update(games::table.find(game_id))
+            .set(games::archived_at.eq(None::<DateTime<Utc>>))
+            .get_result(conn)
+            .await
+    }
+
+    /// Permanently delete a game and, by cascade, its players.
+    ///
+    /// # Arguments
+    /// * `conn` - A mutable reference to an async PostgreSQL connection.
+    /// * `game_id` - The unique identifier of the game to purge.
+    ///
+    /// # Returns
+    /// A `Result` containing the number of rows affected or a Diesel error.
+    pub async fn purge(
+        conn: &mut AsyncPgConnection,
+        game_id: i64,
+    ) -> Result<usize, diesel::result::Error> {
+        diesel::delete(games::table.find(game_id))
+            .execute(conn)
+            .await
+    }
+
+    /// Open a game question for responses until `deadline`, replacing any
+    /// question that was open before.
+    ///
+    /// # Arguments
+    /// * `conn` - A mutable reference to an async PostgreSQL connection.
+    /// * `game_id` - The unique identifier of the game.
+    /// * `game_question_id` - The unique identifier of the game question to open.
+    /// * `deadline` - When the question stops accepting responses.
+    ///
+    /// # Returns
+    /// A `Result` containing the updated game or a Diesel error.
+    pub async fn open_question(
+        conn: &mut AsyncPgConnection,
+        game_id: i64,
+        game_question_id: i64,
+        deadline: DateTime<Utc>,
+    ) -> Result<Self, diesel::result::Error> {
+        diesel::update(games::table.find(game_id))
+            .set((
+                games::open_question_id.eq(Some(game_question_id)),
+                games::response_deadline.eq(Some(deadline)),
+                games::updated_at.eq(Utc::now()),
+            ))
+            .get_result(conn)
+            .await
+    }
+
+    /// Close the open game question, if any.
+    ///
+    /// # Arguments
+    /// * `conn` - A mutable reference to an async PostgreSQL connection.
+    /// * `game_id` - The unique identifier of the game.
+    ///
+    /// # Returns
+    /// A `Result` containing the updated game or a Diesel error.
+    pub async fn close_question(
+        conn: &mut AsyncPgConnection,
+        game_id: i64,
+    ) -> Result<Self, diesel::result::Error> {
+        diesel::update(games::table.find(game_id))
+            .set((
+                games::open_question_id.eq(None::<i64>),
+                games::response_deadline.eq(None::<DateTime<Utc>>),
+                games::updated_at.eq(Utc::now()),
+            ))
+            .get_result(conn)
+            .await
+    }
+
+    /// Move a game on to its next round.
+    ///
+    /// # Arguments
+    /// * `conn` - A mutable reference to an async PostgreSQL connection.
+    /// * `game_id` - The unique identifier of the game.
+    ///
+    /// # Returns
+    /// A `Result` containing the updated game or a Diesel error.
+    pub async fn advance_round(
+        conn: &mut AsyncPgConnection,
+        game_id: i64,
+    ) -> Result<Self, diesel::result::Error> {
+        diesel::update(games::table.find(game_id))
+            .set((
+                games::round.eq(games::round + 1),
+                games::updated_at.eq(Utc::now()),
+            ))
+            .get_result(conn)
+            .await
+    }
+
+    /// Pause a game. Pausing a paused game keeps the original pause time.
+    ///
+    /// # Arguments
+    /// * `conn` - A mutable reference to an async PostgreSQL connection.
+    /// * `game_id` - The unique identifier of the game.
+    /// * `now` - When the game is paused.
+    ///
+    /// # Returns
+    /// A `Result` containing the updated game or a Diesel error.
+    pub async fn pause(
+        conn: &mut AsyncPgConnection,
+        game_id: i64,
+        now: DateTime<Utc>,
+    ) -> Result<Self, diesel::result::Error> {
+        let game = Self::find_by_id(conn, game_id).await?;
+        if game.paused_at.is_some() {
+            return Ok(game);
+        }
+        diesel::update(games::table.find(game_id))
+            .set((games::paused_at.eq(Some(now)), games::updated_at.eq(now)))
+            .get_result(conn)
+            .await
+    }
+
+    /// Unpause a game, moving the deadline of an open question back by how
+    /// long the game was paused so contestants do not lose that time.
+    ///
+    /// # Arguments
+    /// * `conn` - A mutable reference to an async PostgreSQL connection.
+    /// * `game_id` - The unique identifier of the game.
+    /// * `now` - When the game is unpaused.
+    ///
+    /// # Returns
+    /// A `Result` containing the updated game or a Diesel error.
+    pub async fn unpause(
+        conn: &mut AsyncPgConnection,
+        game_id: i64,
+        now: DateTime<Utc>,
+    ) -> Result<Self, diesel::result::Error> {
+        let game = Self::find_by_id(conn, game_id).await?;
+        let Some(paused_at) = game.paused_at else {
+            return Ok(game);
+        };
+        let paused_for = (now - paused_at).max(chrono::Duration::zero());
+        diesel::update(games::table.find(game_id))
+            .set((
+                games::paused_at.eq(None::<DateTime<Utc>>),
+                games::response_deadline.eq(game.response_deadline.map(|d| d + paused_for)),
+                games::updated_at.eq(now),
+            ))
+            .get_result(conn)
+            .await
+    }
+
+    /// Give control of the board to a player or a team, or to nobody.
+    ///
+    /// # Arguments
+    /// * `conn` - A mutable reference to an async PostgreSQL connection.
+    /// * `game_id` - The unique identifier of the game.
+    /// * `player_id` - The player who picks the next cell.
+    /// * `team_id` - The team that picks the next cell.
+    ///
+    /// # Returns
+    /// A `Result` containing the updated game or a Diesel error.
+    pub async fn set_control(
+        conn: &mut AsyncPgConnection,
+        game_id: i64,
+        player_id: Option<i64>,
+        team_id: Option<i64>,
+    ) -> Result<Self, diesel::result::Error> {
+        diesel::update(games::table.find(game_id))
+            .set((
+                games::control_player_id.eq(player_id),
+                games::control_team_id.eq(team_id),
+                games::updated_at.eq(Utc::now()),
+            ))
+            .get_result(conn)
+            .await
+    }
 }
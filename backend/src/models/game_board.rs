@@ -1,12 +1,66 @@
 // models/game_board.rs
 
+use crate::db::pool::DBPool;
+use crate::db::schema::board_collaborators;
+use crate::db::schema::board_favourites;
+use crate::db::schema::game_board_question_mappings;
 use crate::db::schema::game_boards;
+use crate::models::game_board_question_mapping::{
+    defer_unique_cells, GameBoardQuestionMapping, NewGameBoardQuestionMapping,
+};
+use crate::models::game_board_translation::GameBoardTranslation;
+use crate::models::question::{NewQuestion, Question};
 use crate::models::user::User;
-use async_graphql::SimpleObject;
+use async_graphql::{ComplexObject, Context, Enum, SimpleObject};
 use chrono::{DateTime, Utc};
 use derive_builder::Builder;
+use diesel::deserialize::{self, FromSql, FromSqlRow};
+use diesel::expression::AsExpression;
+use diesel::pg::{Pg, PgValue};
 use diesel::prelude::*;
-use diesel_async::{AsyncPgConnection, RunQueryDsl};
+use diesel::serialize::{self, Output, ToSql};
+use diesel::sql_types::Text;
+use diesel_async::scoped_futures::ScopedFutureExt;
+use diesel_async::{AsyncConnection, AsyncPgConnection, RunQueryDsl};
+
+/// Who can see a game board besides its owner and collaborators.
+#[derive(Enum, Copy, Clone, Debug, PartialEq, Eq, AsExpression, FromSqlRow)]
+#[diesel(sql_type = Text)]
+pub enum BoardVisibility {
+    /// Only the owner and invited collaborators.
+    Private,
+    /// Anyone holding the share link.
+    Unlisted,
+    /// Anyone holding the share link, and listed publicly.
+    Public,
+}
+
+impl BoardVisibility {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BoardVisibility::Private => "private",
+            BoardVisibility::Unlisted => "unlisted",
+            BoardVisibility::Public => "public",
+        }
+    }
+}
+
+impl ToSql<Text, Pg> for BoardVisibility {
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Pg>) -> serialize::Result {
+        <str as ToSql<Text, Pg>>::to_sql(self.as_str(), out)
+    }
+}
+
+impl FromSql<Text, Pg> for BoardVisibility {
+    fn from_sql(bytes: PgValue<'_>) -> deserialize::Result<Self> {
+        match <String as FromSql<Text, Pg>>::from_sql(bytes)?.as_str() {
+            "private" => Ok(BoardVisibility::Private),
+            "unlisted" => Ok(BoardVisibility::Unlisted),
+            "public" => Ok(BoardVisibility::Public),
+            other => Err(format!("Unknown board visibility: {}", other).into()),
+        }
+    }
+}
 
 /// Represents a game board in the application.
 ///
@@ -17,6 +71,7 @@ use diesel_async::{AsyncPgConnection, RunQueryDsl};
 #[derive(
     Identifiable, Associations, Queryable, Selectable, Debug, SimpleObject, Builder, Clone,
 )]
+#[graphql(complex)]
 #[diesel(table_name = game_boards)]
 #[diesel(belongs_to(User))]
 pub struct GameBoard {
@@ -33,6 +88,42 @@ pub struct GameBoard {
     /// A vector of category names for the game board. Each category
     /// is an optional string, allowing for empty or missing categories.
     pub categories: Vec<Option<String>>,
+    /// The timestamp when the game board was archived, if it has been.
+    /// Archived boards are hidden from listings but can be restored.
+    pub archived_at: Option<DateTime<Utc>>,
+    /// Who can see the game board besides its owner and collaborators.
+    pub visibility: BoardVisibility,
+    /// The secret used in share links. Only shown to the owner, through
+    /// `fetchBoardSharing`.
+    #[graphql(skip)]
+    pub share_token: Option<String>,
+    /// The timestamp when the game board was hidden from the public gallery
+    /// for moderation, if it has been.
+    pub flagged_at: Option<DateTime<Utc>>,
+    /// The locale the game board is written in. When a board is fetched for
+    /// another locale and has a translation into it, this is the
+    /// translation's locale.
+    pub locale: String,
+}
+
+#[ComplexObject]
+impl GameBoard {
+    /// Translations of the title and categories into other locales
+    async fn translations(
+        &self,
+        ctx: &Context<'_>,
+    ) -> async_graphql::Result<Vec<GameBoardTranslation>> {
+        let pool = ctx.data::<DBPool>().map_err(|e| {
+            async_graphql::Error::new(format!("Cannot get DBPool from context: {:?}", e))
+        })?;
+        let mut conn = pool
+            .get()
+            .await
+            .map_err(|e| async_graphql::Error::new(format!("Failed to get connection: {}", e)))?;
+
+        let translations = GameBoardTranslation::fetch_by_game_board_id(&mut conn, self.id).await?;
+        Ok(translations)
+    }
 }
 
 /// Represents a new game board to be inserted into the database.
@@ -49,6 +140,7 @@ pub struct NewGameBoard {
 pub struct UpdateGameBoard {
     pub title: Option<String>,
     pub categories: Option<Vec<String>>,
+    pub locale: Option<String>,
 }
 
 impl GameBoard {
@@ -67,7 +159,7 @@ impl GameBoard {
         game_boards::table.find(game_board_id).first(conn).await
     }
 
-    /// Fetch all game boards created by a specific user.
+    /// Fetch all live (non-archived) game boards created by a specific user.
     ///
     /// # Arguments
     /// * `conn` - A mutable reference to an async PostgreSQL connection.
@@ -81,11 +173,188 @@ impl GameBoard {
     ) -> Result<Vec<Self>, diesel::result::Error> {
         game_boards::table
             .filter(game_boards::user_id.eq(user_id))
+            .filter(game_boards::archived_at.is_null())
+            .load::<Self>(conn)
+            .await
+    }
+
+    /// Fetch all archived game boards created by a specific user.
+    ///
+    /// # Arguments
+    /// * `conn` - A mutable reference to an async PostgreSQL connection.
+    /// * `user_id` - The unique identifier of the user.
+    ///
+    /// # Returns
+    /// A `Result` containing a vector of archived game boards or a Diesel error.
+    pub async fn fetch_archived_by_user(
+        conn: &mut AsyncPgConnection,
+        user_id: i64,
+    ) -> Result<Vec<Self>, diesel::result::Error> {
+        game_boards::table
+            .filter(game_boards::user_id.eq(user_id))
+            .filter(game_boards::archived_at.is_not_null())
+            .load::<Self>(conn)
+            .await
+    }
+
+    /// Fetch every game board that places a specific question on its grid,
+    /// including archived boards.
+    ///
+    /// # Arguments
+    /// * `conn` - A mutable reference to an async PostgreSQL connection.
+    /// * `question_id` - The unique identifier of the question.
+    ///
+    /// # Returns
+    /// A `Result` containing a vector of game boards or a Diesel error.
+    pub async fn fetch_by_question_id(
+        conn: &mut AsyncPgConnection,
+        question_id: i64,
+    ) -> Result<Vec<Self>, diesel::result::Error> {
+        game_boards::table
+            .inner_join(game_board_question_mappings::table)
+            .filter(game_board_question_mappings::question_id.eq(question_id))
+            .select(GameBoard::as_select())
+            .load::<Self>(conn)
+            .await
+    }
+
+    /// Fetch all live (non-archived) game boards a user was invited to
+    /// collaborate on.
+    ///
+    /// # Arguments
+    /// * `conn` - A mutable reference to an async PostgreSQL connection.
+    /// * `user_id` - The unique identifier of the collaborator.
+    ///
+    /// # Returns
+    /// A `Result` containing a vector of game boards or a Diesel error.
+    pub async fn fetch_shared_with_user(
+        conn: &mut AsyncPgConnection,
+        user_id: i64,
+    ) -> Result<Vec<Self>, diesel::result::Error> {
+        game_boards::table
+            .inner_join(board_collaborators::table)
+            .filter(board_collaborators::user_id.eq(user_id))
+            .filter(game_boards::archived_at.is_null())
+            .select(GameBoard::as_select())
+            .load::<Self>(conn)
+            .await
+    }
+
+    /// Find a shared (unlisted or public) game board by its share token.
+    ///
+    /// # Arguments
+    /// * `conn` - A mutable reference to an async PostgreSQL connection.
+    /// * `share_token` - The token from the board's share link.
+    ///
+    /// # Returns
+    /// A `Result` containing the game board, or `NotFound` if no live board
+    /// is shared under that token.
+    pub async fn find_by_share_token(
+        conn: &mut AsyncPgConnection,
+        share_token: &str,
+    ) -> Result<Self, diesel::result::Error> {
+        game_boards::table
+            .filter(game_boards::share_token.eq(share_token))
+            .filter(game_boards::visibility.ne(BoardVisibility::Private))
+            .filter(game_boards::archived_at.is_null())
+            .first(conn)
+            .await
+    }
+
+    /// Change who can see a game board. A share token is generated the first
+    /// time a board is made unlisted or public, and kept afterwards so
+    /// existing links work again if the board is shared once more.
+    ///
+    /// # Arguments
+    /// * `conn` - A mutable reference to an async PostgreSQL connection.
+    /// * `game_board_id` - The unique identifier of the game board.
+    /// * `visibility` - The new visibility.
+    ///
+    /// # Returns
+    /// A `Result` containing the updated game board or a Diesel error.
+    pub async fn set_visibility(
+        conn: &mut AsyncPgConnection,
+        game_board_id: i64,
+        visibility: BoardVisibility,
+    ) -> Result<Self, diesel::result::Error> {
+        let board = GameBoard::find_by_id(conn, game_board_id).await?;
+        let share_token = match board.share_token {
+            Some(token) => Some(token),
+            None if visibility != BoardVisibility::Private => Some(new_share_token()),
+            None => None,
+        };
+
+        diesel::update(game_boards::table.find(game_board_id))
+            .set((
+                game_boards::visibility.eq(visibility),
+                game_boards::share_token.eq(share_token),
+            ))
+            .get_result(conn)
+            .await
+    }
+
+    /// Replace a game board's share token, invalidating old share links.
+    ///
+    /// # Arguments
+    /// * `conn` - A mutable reference to an async PostgreSQL connection.
+    /// * `game_board_id` - The unique identifier of the game board.
+    ///
+    /// # Returns
+    /// A `Result` containing the updated game board or a Diesel error.
+    pub async fn regenerate_share_token(
+        conn: &mut AsyncPgConnection,
+        game_board_id: i64,
+    ) -> Result<Self, diesel::result::Error> {
+        diesel::update(game_boards::table.find(game_board_id))
+            .set(game_boards::share_token.eq(Some(new_share_token())))
+            .get_result(conn)
+            .await
+    }
+
+    /// Fetch all live (non-archived) game boards a user marked as favourites.
+    ///
+    /// # Arguments
+    /// * `conn` - A mutable reference to an async PostgreSQL connection.
+    /// * `user_id` - The unique identifier of the user.
+    ///
+    /// # Returns
+    /// A `Result` containing a vector of game boards or a Diesel error.
+    pub async fn fetch_favourites_of_user(
+        conn: &mut AsyncPgConnection,
+        user_id: i64,
+    ) -> Result<Vec<Self>, diesel::result::Error> {
+        game_boards::table
+            .inner_join(board_favourites::table)
+            .filter(board_favourites::user_id.eq(user_id))
+            .filter(game_boards::archived_at.is_null())
+            .order(board_favourites::created_at.desc())
+            .select(GameBoard::as_select())
             .load::<Self>(conn)
             .await
     }
 
-    /// Fetch all game boards from the database.
+    /// Hide a game board from the public gallery for moderation, or show it
+    /// again.
+    ///
+    /// # Arguments
+    /// * `conn` - A mutable reference to an async PostgreSQL connection.
+    /// * `game_board_id` - The unique identifier of the game board.
+    /// * `flagged` - Whether the board should be hidden.
+    ///
+    /// # Returns
+    /// A `Result` containing the updated game board or a Diesel error.
+    pub async fn set_flagged(
+        conn: &mut AsyncPgConnection,
+        game_board_id: i64,
+        flagged: bool,
+    ) -> Result<Self, diesel::result::Error> {
+        diesel::update(game_boards::table.find(game_board_id))
+            .set(game_boards::flagged_at.eq(flagged.then(Utc::now)))
+            .get_result(conn)
+            .await
+    }
+
+    /// Fetch all live (non-archived) game boards from the database.
     ///
     /// # Arguments
     /// * `conn` - A mutable reference to an async PostgreSQL connection.
@@ -93,7 +362,45 @@ impl GameBoard {
     /// # Returns
     /// A `Result` containing a vector of game boards or a Diesel error.
     pub async fn all(conn: &mut AsyncPgConnection) -> Result<Vec<Self>, diesel::result::Error> {
-        game_boards::table.load::<Self>(conn).await
+        game_boards::table
+            .filter(game_boards::archived_at.is_null())
+            .load::<Self>(conn)
+            .await
+    }
+
+    /// Fetch all live game boards a user may see: their own, those they
+    /// collaborate on and public ones. Unlisted boards are only reachable
+    /// through their share link.
+    ///
+    /// # Arguments
+    /// * `conn` - A mutable reference to an async PostgreSQL connection.
+    /// * `user_id` - The requestor, or `None` for anonymous callers, who
+    ///   only see public boards.
+    ///
+    /// # Returns
+    /// A `Result` containing a vector of game boards or a Diesel error.
+    pub async fn fetch_visible_to(
+        conn: &mut AsyncPgConnection,
+        user_id: Option<i64>,
+    ) -> Result<Vec<Self>, diesel::result::Error> {
+        let public = game_boards::visibility.eq(BoardVisibility::Public);
+        let mut query = game_boards::table
+            .filter(game_boards::archived_at.is_null())
+            .into_boxed();
+        query = match user_id {
+            Some(user_id) => {
+                let collaborations = board_collaborators::table
+                    .filter(board_collaborators::user_id.eq(user_id))
+                    .select(board_collaborators::board_id);
+                query.filter(
+                    public
+                        .or(game_boards::user_id.eq(user_id))
+                        .or(game_boards::id.eq_any(collaborations)),
+                )
+            }
+            None => query.filter(public),
+        };
+        query.order(game_boards::id).load::<Self>(conn).await
     }
 
     /// Create a new game board in the database.
@@ -162,4 +469,288 @@ impl GameBoard {
             .get_result::<Self>(conn)
             .await
     }
+
+    /// Archive (soft-delete) a game board. Its mappings and games are kept.
+    ///
+    /// # Arguments
+    /// * `conn` - A mutable reference to an async PostgreSQL connection.
+    /// * `game_board_id` - The unique identifier of the game board to archive.
+    ///
+    /// # Returns
+    /// A `Result` containing the archived game board or a Diesel error.
+    pub async fn archive(
+        conn: &mut AsyncPgConnection,
+        game_board_id: i64,
+    ) -> Result<Self, diesel::result::Error> {
+        diesel::update(game_boards::table.find(game_board_id))
+            .set(game_boards::archived_at.eq(Some(Utc::now())))
+            .get_result(conn)
+            .await
+    }
+
+    /// Restore a previously archived game board.
+    ///
+    /// # Arguments
+    /// * `conn` - A mutable reference to an async PostgreSQL connection.
+    /// * `game_board_id` - The unique identifier of the game board to restore.
+    ///
+    /// # Returns
+    /// A `Result` containing the restored game board or a Diesel error.
+    pub async fn restore(
+        conn: &mut AsyncPgConnection,
+        game_board_id: i64,
+    ) -> Result<Self, diesel::result::Error> {
+        diesel::update(game_boards::table.find(game_board_id))
+            .set(game_boards::archived_at.eq(None::<DateTime<Utc>>))
+            .get_result(conn)
+            .await
+    }
+
+    /// Permanently delete a game board.
+    ///
+    /// Its question mappings and every game played on it are removed by
+    /// cascade. The questions themselves stay in the author's library.
+    ///
+    /// # Arguments
+    /// * `conn` - A mutable reference to an async PostgreSQL connection.
+    /// * `game_board_id` - The unique identifier of the game board to purge.
+    ///
+    /// # Returns
+    /// A `Result` containing the number of rows affected or a Diesel error.
+    pub async fn purge(
+        conn: &mut AsyncPgConnection,
+        game_board_id: i64,
+    ) -> Result<usize, diesel::result::Error> {
+        diesel::delete(game_boards::table.find(game_board_id))
+            .execute(conn)
+            .await
+    }
+
+    /// Copy a game board, its categories and all of its question mappings
+    /// into a new board owned by `user_id`, in a single transaction.
+    ///
+    /// # Arguments
+    /// * `conn` - A mutable reference to an async PostgreSQL connection.
+    /// * `game_board_id` - The unique identifier of the game board to copy.
+    /// * `user_id` - The unique identifier of the user who will own the copy.
+    /// * `title` - The title of the new game board.
+    /// * `deep_copy_questions` - When true, every question is duplicated into
+    ///   `user_id`'s library. Otherwise the copy shares the existing questions.
+    ///
+    /// # Returns
+    /// A `Result` containing the new game board or a Diesel error.
+    pub async fn clone_board(
+        conn: &mut AsyncPgConnection,
+        game_board_id: i64,
+        user_id: i64,
+        title: String,
+        deep_copy_questions: bool,
+    ) -> Result<Self, diesel::result::Error> {
+        conn.transaction::<_, diesel::result::Error, _>(|conn| {
+            async move {
+                let source = GameBoard::find_by_id(conn, game_board_id).await?;
+
+                let copy: GameBoard = diesel::insert_into(game_boards::table)
+                    .values((
+                        game_boards::user_id.eq(user_id),
+                        game_boards::title.eq(title),
+                        game_boards::categories.eq(&source.categories),
+                    ))
+                    .get_result(conn)
+                    .await?;
+
+                let mappings =
+                    GameBoardQuestionMapping::fetch_mappings_by_board_id(conn, source.id).await?;
+                for mapping in mappings {
+                    let question_id = if deep_copy_questions {
+                        let question = Question::find_by_id(conn, mapping.question_id).await?;
+                        let duplicate = Question::create(
+                            conn,
+                            NewQuestion {
+                                user_id,
+                                question: question.question,
+                                answer: question.answer,
+                                alternate_answers: question.alternate_answers,
+                                tags: question.tags,
+                                difficulty: question.difficulty,
+                            },
+                        )
+                        .await?;
+                        duplicate.id
+                    } else {
+                        mapping.question_id
+                    };
+
+                    GameBoardQuestionMapping::create_mapping(
+                        conn,
+                        NewGameBoardQuestionMapping {
+                            board_id: copy.id,
+                            question_id,
+                            daily_double: mapping.daily_double,
+                            points: mapping.points,
+                            grid_row: mapping.grid_row,
+                            grid_col: mapping.grid_col,
+                        },
+                    )
+                    .await?;
+                }
+
+                Ok(copy)
+            }
+            .scope_boxed()
+        })
+        .await
+    }
+
+    /// Hand a game board over to another user, in a single transaction.
+    ///
+    /// Questions on the board that the new owner did not write are copied
+    /// into their library, so they can edit every cell; the originals stay
+    /// with their authors. If the new owner was a collaborator, they stop
+    /// being one.
+    ///
+    /// # Arguments
+    /// * `conn` - A mutable reference to an async PostgreSQL connection.
+    /// * `game_board_id` - The unique identifier of the game board.
+    /// * `new_owner_id` - The unique identifier of the user taking it over.
+    ///
+    /// # Returns
+    /// A `Result` containing the updated game board or a Diesel error.
+    pub async fn transfer_ownership(
+        conn: &mut AsyncPgConnection,
+        game_board_id: i64,
+        new_owner_id: i64,
+    ) -> Result<Self, diesel::result::Error> {
+        conn.transaction::<_, diesel::result::Error, _>(|conn| {
+            async move {
+                let board: GameBoard = diesel::update(game_boards::table.find(game_board_id))
+                    .set(game_boards::user_id.eq(new_owner_id))
+                    .get_result(conn)
+                    .await?;
+                diesel::delete(
+                    board_collaborators::table
+                        .filter(board_collaborators::board_id.eq(game_board_id))
+                        .filter(board_collaborators::user_id.eq(new_owner_id)),
+                )
+                .execute(conn)
+                .await?;
+
+                let mappings =
+                    GameBoardQuestionMapping::fetch_mappings_by_board_id(conn, game_board_id)
+                        .await?;
+                for mapping in mappings {
+                    let question = Question::find_by_id(conn, mapping.question_id).await?;
+                    if question.user_id == new_owner_id {
+                        continue;
+                    }
+                    let copy = Question::create(
+                        conn,
+                        NewQuestion {
+                            user_id: new_owner_id,
+                            question: question.question,
+                            answer: question.answer,
+                            alternate_answers: question.alternate_answers,
+                            tags: question.tags,
+                            difficulty: question.difficulty,
+                        },
+                    )
+                    .await?;
+                    GameBoardQuestionMapping::replace_question(
+                        conn,
+                        game_board_id,
+                        mapping.grid_row,
+                        mapping.grid_col,
+                        copy.id,
+                    )
+                    .await?;
+                }
+
+                Ok(board)
+            }
+            .scope_boxed()
+        })
+        .await
+    }
+
+    /// Move a category column, together with its questions, to a new index.
+    ///
+    /// The columns in between shift over by one, like moving an item in a list.
+    ///
+    /// # Arguments
+    /// * `conn` - A mutable reference to an async PostgreSQL connection.
+    /// * `game_board_id` - The unique identifier of the game board.
+    /// * `from_col` - The current index of the column.
+    /// * `to_col` - The index the column should end up at.
+    ///
+    /// # Returns
+    /// A `Result` containing the updated game board or a Diesel error. Moving
+    /// from or to a column the board has no category for is an error.
+    pub async fn move_column(
+        conn: &mut AsyncPgConnection,
+        game_board_id: i64,
+        from_col: i32,
+        to_col: i32,
+    ) -> Result<Self, diesel::result::Error> {
+        conn.transaction::<_, diesel::result::Error, _>(|conn| {
+            async move {
+                defer_unique_cells(conn).await?;
+
+                let mut game_board = GameBoard::find_by_id(conn, game_board_id).await?;
+                let columns = 0..game_board.categories.len() as i32;
+                if !columns.contains(&from_col) || !columns.contains(&to_col) {
+                    return Err(diesel::result::Error::QueryBuilderError(
+                        format!(
+                            "Game board {} only has {} columns",
+                            game_board_id, columns.end
+                        )
+                        .into(),
+                    ));
+                }
+                let category = game_board.categories.remove(from_col as usize);
+                game_board.categories.insert(to_col as usize, category);
+
+                let mappings =
+                    GameBoardQuestionMapping::fetch_mappings_by_board_id(conn, game_board_id)
+                        .await?;
+                for mapping in mappings {
+                    let new_col = moved_index(mapping.grid_col, from_col, to_col);
+                    if new_col == mapping.grid_col {
+                        continue;
+                    }
+                    diesel::update(
+                        game_board_question_mappings::table
+                            .find((mapping.board_id, mapping.question_id)),
+                    )
+                    .set(game_board_question_mappings::grid_col.eq(new_col))
+                    .execute(conn)
+                    .await?;
+                }
+
+                diesel::update(game_boards::table.find(game_board_id))
+                    .set(game_boards::categories.eq(game_board.categories))
+                    .get_result::<Self>(conn)
+                    .await
+            }
+            .scope_boxed()
+        })
+        .await
+    }
+}
+
+/// A fresh unguessable token for share links.
+fn new_share_token() -> String {
+    uuid::Uuid::new_v4().simple().to_string()
+}
+
+/// Where `index` ends up after the item at `from` is moved to `to`.
+fn moved_index(index: i32, from: i32, to: i32) -> i32 {
+    if index == from {
+        to
+    } else if from < to && (from + 1..=to).contains(&index) {
+        index - 1
+    } else if to < from && (to..from).contains(&index) {
+        index + 1
+    } else {
+        index
+    }
 }
@@ -8,7 +8,8 @@ use crate::models::question::Question;
 use async_graphql::SimpleObject;
 use derive_builder::Builder;
 use diesel::prelude::*;
-use diesel_async::{AsyncPgConnection, RunQueryDsl};
+use diesel_async::scoped_futures::ScopedFutureExt;
+use diesel_async::{AsyncConnection, AsyncPgConnection, RunQueryDsl};
 
 /// `GBQMapping` is alias for `GameBoardQuestionMapping`.
 /// Represents the mapping between a game board and a question.
@@ -183,4 +184,245 @@ impl GameBoardQuestionMapping {
             .get_result(conn)
             .await
     }
+
+    /// Swap the contents of two cells on a board in a single transaction.
+    ///
+    /// Questions trade places and take the other cell's point value, so each
+    /// row keeps its value. If one cell is empty the question simply moves
+    /// there and keeps its points.
+    ///
+    /// # Arguments
+    /// * `conn` - A mutable reference to an async PostgreSQL connection.
+    /// * `board_id` - The ID of the game board.
+    /// * `first` - The `(row, col)` of the first cell.
+    /// * `second` - The `(row, col)` of the second cell.
+    ///
+    /// # Returns
+    /// A `Result` containing the moved mappings, or `NotFound` if both cells are empty.
+    pub async fn swap_cells(
+        conn: &mut AsyncPgConnection,
+        board_id: i64,
+        first: (i32, i32),
+        second: (i32, i32),
+    ) -> Result<Vec<Self>, diesel::result::Error> {
+        conn.transaction::<_, diesel::result::Error, _>(|conn| {
+            async move {
+                defer_unique_cells(conn).await?;
+
+                let a = Self::find_mapping_by_row_and_col(conn, board_id, first.0, first.1)
+                    .await
+                    .optional()?;
+                let b = Self::find_mapping_by_row_and_col(conn, board_id, second.0, second.1)
+                    .await
+                    .optional()?;
+
+                let moves = match (a, b) {
+                    (None, None) => return Err(diesel::NotFound),
+                    (Some(a), None) => vec![(a.question_id, second, a.points)],
+                    (None, Some(b)) => vec![(b.question_id, first, b.points)],
+                    (Some(a), Some(b)) => vec![
+                        (a.question_id, second, b.points),
+                        (b.question_id, first, a.points),
+                    ],
+                };
+
+                let mut moved = Vec::new();
+                for (question_id, (row, col), points) in moves {
+                    let updated = Self::update_mapping(
+                        conn,
+                        board_id,
+                        question_id,
+                        UpdateGameBoardQuestionMapping {
+                            daily_double: None,
+                            points: Some(points),
+                            grid_row: Some(row),
+                            grid_col: Some(col),
+                        },
+                    )
+                    .await?;
+                    moved.push(updated);
+                }
+                Ok(moved)
+            }
+            .scope_boxed()
+        })
+        .await
+    }
+
+    /// Reorder the rows of a board in a single transaction.
+    ///
+    /// `row_order[new_row]` is the row that should end up at `new_row`, so
+    /// `[4, 3, 2, 1, 0]` flips the board upside down. Points move with their
+    /// questions; use `assign_points_by_row` to re-value the rows afterwards.
+    ///
+    /// # Arguments
+    /// * `conn` - A mutable reference to an async PostgreSQL connection.
+    /// * `board_id` - The ID of the game board.
+    /// * `row_order` - A permutation of the board's row indices.
+    ///
+    /// # Returns
+    /// A `Result` containing all mappings of the board or a Diesel error.
+    pub async fn reorder_rows(
+        conn: &mut AsyncPgConnection,
+        board_id: i64,
+        row_order: Vec<i32>,
+    ) -> Result<Vec<Self>, diesel::result::Error> {
+        conn.transaction::<_, diesel::result::Error, _>(|conn| {
+            async move {
+                defer_unique_cells(conn).await?;
+
+                let mappings = Self::fetch_mappings_by_board_id(conn, board_id).await?;
+                for mapping in mappings {
+                    let Some(new_row) = row_order.iter().position(|r| *r == mapping.grid_row)
+                    else {
+                        continue;
+                    };
+                    diesel::update(
+                        game_board_question_mappings::table
+                            .find((mapping.board_id, mapping.question_id)),
+                    )
+                    .set(game_board_question_mappings::grid_row.eq(new_row as i32))
+                    .execute(conn)
+                    .await?;
+                }
+
+                Self::fetch_mappings_by_board_id(conn, board_id).await
+            }
+            .scope_boxed()
+        })
+        .await
+    }
+
+    /// Set every cell's points from its row: `base_points * (row + 1)`.
+    ///
+    /// # Arguments
+    /// * `conn` - A mutable reference to an async PostgreSQL connection.
+    /// * `board_id` - The ID of the game board.
+    /// * `base_points` - The value of the first row.
+    ///
+    /// # Returns
+    /// A `Result` containing all mappings of the board or a Diesel error.
+    pub async fn assign_points_by_row(
+        conn: &mut AsyncPgConnection,
+        board_id: i64,
+        base_points: i32,
+    ) -> Result<Vec<Self>, diesel::result::Error> {
+        diesel::update(
+            game_board_question_mappings::table
+                .filter(game_board_question_mappings::board_id.eq(board_id)),
+        )
+        .set(
+            game_board_question_mappings::points
+                .eq((game_board_question_mappings::grid_row + 1) * base_points),
+        )
+        .get_results(conn)
+        .await
+    }
+
+    /// Unlink a question from a board. The question itself is kept.
+    ///
+    /// # Arguments
+    /// * `conn` - A mutable reference to an async PostgreSQL connection.
+    /// * `board_id` - The ID of the game board.
+    /// * `question_id` - The ID of the question.
+    ///
+    /// # Returns
+    /// A `Result` containing the removed mapping or a Diesel error.
+    pub async fn delete_mapping(
+        conn: &mut AsyncPgConnection,
+        board_id: i64,
+        question_id: i64,
+    ) -> Result<Self, diesel::result::Error> {
+        diesel::delete(game_board_question_mappings::table.find((board_id, question_id)))
+            .get_result(conn)
+            .await
+    }
+
+    /// Empty the cell at `row`, `col` of a board. The question itself is kept.
+    ///
+    /// # Arguments
+    /// * `conn` - A mutable reference to an async PostgreSQL connection.
+    /// * `board_id` - The ID of the game board.
+    /// * `row` - The row position on the game board grid.
+    /// * `col` - The column position on the game board grid.
+    ///
+    /// # Returns
+    /// A `Result` containing the removed mapping, or `NotFound` if the cell was empty.
+    pub async fn clear_cell(
+        conn: &mut AsyncPgConnection,
+        board_id: i64,
+        row: i32,
+        col: i32,
+    ) -> Result<Self, diesel::result::Error> {
+        diesel::delete(
+            game_board_question_mappings::table
+                .filter(game_board_question_mappings::board_id.eq(board_id))
+                .filter(game_board_question_mappings::grid_row.eq(row))
+                .filter(game_board_question_mappings::grid_col.eq(col)),
+        )
+        .get_result(conn)
+        .await
+    }
+
+    /// Put a different question in an occupied cell, keeping the cell's
+    /// points and Daily Double flag. The old question itself is kept.
+    ///
+    /// # Arguments
+    /// * `conn` - A mutable reference to an async PostgreSQL connection.
+    /// * `board_id` - The ID of the game board.
+    /// * `row` - The row position on the game board grid.
+    /// * `col` - The column position on the game board grid.
+    /// * `question_id` - The ID of the question to place in the cell.
+    ///
+    /// # Returns
+    /// A `Result` containing the new mapping, or `NotFound` if the cell was empty.
+    pub async fn replace_question(
+        conn: &mut AsyncPgConnection,
+        board_id: i64,
+        row: i32,
+        col: i32,
+        question_id: i64,
+    ) -> Result<Self, diesel::result::Error> {
+        conn.transaction::<_, diesel::result::Error, _>(|conn| {
+            async move {
+                let old = Self::clear_cell(conn, board_id, row, col).await?;
+                Self::create_mapping(
+                    conn,
+                    NewGameBoardQuestionMapping {
+                        board_id,
+                        question_id,
+                        daily_double: old.daily_double,
+                        points: old.points,
+                        grid_row: row,
+                        grid_col: col,
+                    },
+                )
+                .await
+            }
+            .scope_boxed()
+        })
+        .await
+    }
+}
+
+/// Defer the one-question-per-cell check to the end of the current
+/// transaction, so cells can pass through each other while being moved.
+pub(crate) async fn defer_unique_cells(
+    conn: &mut AsyncPgConnection,
+) -> Result<(), diesel::result::Error> {
+    diesel::sql_query("SET CONSTRAINTS unique_board_cell DEFERRED")
+        .execute(conn)
+        .await?;
+    Ok(())
+}
+
+/// Returns true if `err` is the one-question-per-cell constraint.
+pub fn is_cell_collision(err: &diesel::result::Error) -> bool {
+    matches!(
+        err,
+        diesel::result::Error::DatabaseError(
+            diesel::result::DatabaseErrorKind::UniqueViolation,
+            info,
+        ) if info.constraint_name() == Some("unique_board_cell")
+    )
 }
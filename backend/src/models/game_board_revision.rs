@@ -0,0 +1,518 @@
+// models/game_board_revision.rs
+//! Revision history for game boards.
+//!
+//! Every edit to a board records a `GameBoardRevision` holding a JSON
+//! `BoardSnapshot` of its title, categories and cells (including question
+//! text). Revisions can be listed, diffed against each other and reverted to.
+
+use crate::db::schema::{game_board_question_mappings, game_board_revisions, game_boards};
+use crate::models::game_board::GameBoard;
+use crate::models::game_board_question_mapping::{
+    defer_unique_cells, GameBoardQuestionMapping, NewGameBoardQuestionMapping,
+};
+use crate::models::question::{NewQuestion, Question, UpdateQuestion};
+use crate::models::GBQ;
+use async_graphql::{ComplexObject, SimpleObject};
+use chrono::{DateTime, Utc};
+use diesel::prelude::*;
+use diesel_async::scoped_futures::{ScopedBoxFuture, ScopedFutureExt};
+use diesel_async::{AsyncConnection, AsyncPgConnection, RunQueryDsl};
+use serde::{Deserialize, Serialize};
+
+/// The state of a board at a point in time.
+#[derive(Serialize, Deserialize, SimpleObject, Clone, Debug, PartialEq)]
+pub struct BoardSnapshot {
+    pub title: String,
+    pub categories: Vec<Option<String>>,
+    pub cells: Vec<CellSnapshot>,
+}
+
+/// A single occupied cell within a `BoardSnapshot`.
+#[derive(Serialize, Deserialize, SimpleObject, Clone, Debug, PartialEq)]
+pub struct CellSnapshot {
+    pub question_id: i64,
+    pub question: String,
+    pub answer: String,
+    pub daily_double: bool,
+    pub points: i32,
+    pub grid_row: i32,
+    pub grid_col: i32,
+}
+
+/// One difference between two snapshots.
+#[derive(SimpleObject, Clone, Debug, PartialEq)]
+pub struct RevisionChange {
+    /// What changed, e.g. `title`, `categories[2]` or `cell[1,3].points`.
+    pub path: String,
+    pub before: Option<String>,
+    pub after: Option<String>,
+}
+
+/// Represents a saved revision of a game board.
+#[derive(Identifiable, Associations, Queryable, Selectable, Debug, SimpleObject, Clone)]
+#[graphql(complex)]
+#[diesel(table_name = game_board_revisions)]
+#[diesel(belongs_to(GameBoard, foreign_key = board_id))]
+pub struct GameBoardRevision {
+    pub id: i64,
+    pub created_at: DateTime<Utc>,
+    pub board_id: i64,
+    /// Increments by one for every revision of the same board.
+    pub revision_number: i32,
+    #[graphql(skip)]
+    pub snapshot: serde_json::Value,
+}
+
+#[ComplexObject]
+impl GameBoardRevision {
+    /// The board as it was at this revision
+    async fn snapshot(&self) -> async_graphql::Result<BoardSnapshot> {
+        Ok(self.board_snapshot()?)
+    }
+}
+
+/// Represents a new revision to be inserted into the database.
+#[derive(Debug, Insertable)]
+#[diesel(table_name = game_board_revisions)]
+pub struct NewGameBoardRevision {
+    pub board_id: i64,
+    pub revision_number: i32,
+    pub snapshot: serde_json::Value,
+}
+
+impl GameBoardRevision {
+    /// Decode the stored snapshot.
+    pub fn board_snapshot(&self) -> Result<BoardSnapshot, serde_json::Error> {
+        serde_json::from_value(self.snapshot.clone())
+    }
+
+    /// Find a revision by its unique ID.
+    ///
+    /// # Arguments
+    /// * `conn` - A mutable reference to an async PostgreSQL connection.
+    /// * `revision_id` - The unique identifier of the revision.
+    ///
+    /// # Returns
+    /// A `Result` containing the revision or a Diesel error.
+    pub async fn find_by_id(
+        conn: &mut AsyncPgConnection,
+        revision_id: i64,
+    ) -> Result<Self, diesel::result::Error> {
+        game_board_revisions::table
+            .find(revision_id)
+            .first(conn)
+            .await
+    }
+
+    /// Fetch all revisions of a board, newest first.
+    ///
+    /// # Arguments
+    /// * `conn` - A mutable reference to an async PostgreSQL connection.
+    /// * `board_id` - The unique identifier of the game board.
+    ///
+    /// # Returns
+    /// A `Result` containing a vector of revisions or a Diesel error.
+    pub async fn fetch_by_board_id(
+        conn: &mut AsyncPgConnection,
+        board_id: i64,
+    ) -> Result<Vec<Self>, diesel::result::Error> {
+        game_board_revisions::table
+            .filter(game_board_revisions::board_id.eq(board_id))
+            .order(game_board_revisions::revision_number.desc())
+            .load::<Self>(conn)
+            .await
+    }
+
+    /// Fetch the most recent revision of a board, if any.
+    ///
+    /// # Arguments
+    /// * `conn` - A mutable reference to an async PostgreSQL connection.
+    /// * `board_id` - The unique identifier of the game board.
+    ///
+    /// # Returns
+    /// A `Result` containing the latest revision, if there is one, or a Diesel error.
+    pub async fn latest(
+        conn: &mut AsyncPgConnection,
+        board_id: i64,
+    ) -> Result<Option<Self>, diesel::result::Error> {
+        game_board_revisions::table
+            .filter(game_board_revisions::board_id.eq(board_id))
+            .order(game_board_revisions::revision_number.desc())
+            .first(conn)
+            .await
+            .optional()
+    }
+
+    /// Capture the current state of a board.
+    ///
+    /// # Arguments
+    /// * `conn` - A mutable reference to an async PostgreSQL connection.
+    /// * `board_id` - The unique identifier of the game board.
+    ///
+    /// # Returns
+    /// A `Result` containing the snapshot or a Diesel error.
+    pub async fn capture(
+        conn: &mut AsyncPgConnection,
+        board_id: i64,
+    ) -> Result<BoardSnapshot, diesel::result::Error> {
+        let board = GameBoard::find_by_id(conn, board_id).await?;
+        let mut cells: Vec<CellSnapshot> =
+            GBQ::fetch_all_game_board_questions_by_board_id(conn, board_id)
+                .await?
+                .into_iter()
+                .map(|gbq| CellSnapshot {
+                    question_id: gbq.question.id,
+                    question: gbq.question.question,
+                    answer: gbq.question.answer,
+                    daily_double: gbq.mapping.daily_double,
+                    points: gbq.mapping.points,
+                    grid_row: gbq.mapping.grid_row,
+                    grid_col: gbq.mapping.grid_col,
+                })
+                .collect();
+        cells.sort_by_key(|c| (c.grid_col, c.grid_row));
+
+        Ok(BoardSnapshot {
+            title: board.title,
+            categories: board.categories,
+            cells,
+        })
+    }
+
+    /// Record the current state of a board as a new revision.
+    ///
+    /// If nothing changed since the latest revision, that revision is
+    /// returned instead of recording a duplicate. The board row is locked
+    /// while the next revision number is taken, so concurrent edits are
+    /// numbered without colliding.
+    ///
+    /// # Arguments
+    /// * `conn` - A mutable reference to an async PostgreSQL connection.
+    /// * `board_id` - The unique identifier of the game board.
+    ///
+    /// # Returns
+    /// A `Result` containing the revision describing the board's current state.
+    pub async fn record(
+        conn: &mut AsyncPgConnection,
+        board_id: i64,
+    ) -> Result<Self, diesel::result::Error> {
+        conn.transaction::<_, diesel::result::Error, _>(|conn| {
+            async move {
+                game_boards::table
+                    .find(board_id)
+                    .select(game_boards::id)
+                    .for_update()
+                    .first::<i64>(conn)
+                    .await?;
+                let snapshot = Self::capture(conn, board_id).await?;
+                let latest = Self::latest(conn, board_id).await?;
+
+                if let Some(latest) = latest.as_ref() {
+                    if latest.board_snapshot().ok().as_ref() == Some(&snapshot) {
+                        return Ok(latest.clone());
+                    }
+                }
+
+                let snapshot = serde_json::to_value(&snapshot)
+                    .map_err(|e| diesel::result::Error::SerializationError(Box::new(e)))?;
+                diesel::insert_into(game_board_revisions::table)
+                    .values(&NewGameBoardRevision {
+                        board_id,
+                        revision_number: latest.map_or(1, |r| r.revision_number + 1),
+                        snapshot,
+                    })
+                    .get_result(conn)
+                    .await
+            }
+            .scope_boxed()
+        })
+        .await
+    }
+
+    /// Make an edit to a board and record the resulting revision, in a
+    /// single transaction, so an edit is never kept without its revision.
+    ///
+    /// # Arguments
+    /// * `conn` - A mutable reference to an async PostgreSQL connection.
+    /// * `board_id` - The unique identifier of the game board being edited.
+    /// * `edit` - Makes the edit on the transaction's connection.
+    ///
+    /// # Returns
+    /// A `Result` containing whatever `edit` returned, or a Diesel error.
+    pub async fn with_revision<'a, T, F>(
+        conn: &mut AsyncPgConnection,
+        board_id: i64,
+        edit: F,
+    ) -> Result<T, diesel::result::Error>
+    where
+        F: for<'r> FnOnce(
+                &'r mut AsyncPgConnection,
+            ) -> ScopedBoxFuture<'a, 'r, Result<T, diesel::result::Error>>
+            + Send
+            + 'a,
+        T: Send + 'a,
+    {
+        conn.transaction::<_, diesel::result::Error, _>(|conn| {
+            async move {
+                let edited = edit(conn).await?;
+                Self::record(conn, board_id).await?;
+                Ok(edited)
+            }
+            .scope_boxed()
+        })
+        .await
+    }
+
+    /// Record a revision for every board that uses a question, e.g. after its
+    /// text was edited.
+    ///
+    /// # Arguments
+    /// * `conn` - A mutable reference to an async PostgreSQL connection.
+    /// * `question_id` - The unique identifier of the question.
+    ///
+    /// # Returns
+    /// A `Result` containing the recorded revisions or a Diesel error.
+    pub async fn record_for_question(
+        conn: &mut AsyncPgConnection,
+        question_id: i64,
+    ) -> Result<Vec<Self>, diesel::result::Error> {
+        let boards = GameBoard::fetch_by_question_id(conn, question_id).await?;
+        let mut revisions = Vec::new();
+        for board in boards {
+            revisions.push(Self::record(conn, board.id).await?);
+        }
+        Ok(revisions)
+    }
+
+    /// Whether a question is placed on any board other than `board_id`.
+    async fn used_elsewhere(
+        conn: &mut AsyncPgConnection,
+        question_id: i64,
+        board_id: i64,
+    ) -> Result<bool, diesel::result::Error> {
+        diesel::select(diesel::dsl::exists(
+            game_board_question_mappings::table
+                .filter(game_board_question_mappings::question_id.eq(question_id))
+                .filter(game_board_question_mappings::board_id.ne(board_id)),
+        ))
+        .get_result(conn)
+        .await
+    }
+
+    /// Restore a board to the state saved in one of its revisions.
+    ///
+    /// The title, categories and cells are replaced, and question text is
+    /// restored. Text is rewritten in place only for the board owner's own
+    /// questions that no other board uses; questions written by someone else,
+    /// shared with other boards, or since purged are restored as new questions
+    /// in the board owner's library. The result is recorded as a new revision.
+    ///
+    /// # Arguments
+    /// * `conn` - A mutable reference to an async PostgreSQL connection.
+    /// * `board_id` - The unique identifier of the game board.
+    /// * `revision_id` - The revision to restore. Must belong to `board_id`.
+    ///
+    /// # Returns
+    /// A `Result` containing the reverted board, or `NotFound` if the
+    /// revision does not belong to the board.
+    pub async fn revert(
+        conn: &mut AsyncPgConnection,
+        board_id: i64,
+        revision_id: i64,
+    ) -> Result<GameBoard, diesel::result::Error> {
+        conn.transaction::<_, diesel::result::Error, _>(|conn| {
+            async move {
+                let revision = Self::find_by_id(conn, revision_id).await?;
+                if revision.board_id != board_id {
+                    return Err(diesel::NotFound);
+                }
+                let snapshot = revision
+                    .board_snapshot()
+                    .map_err(|e| diesel::result::Error::DeserializationError(Box::new(e)))?;
+
+                let board: GameBoard = diesel::update(game_boards::table.find(board_id))
+                    .set((
+                        game_boards::title.eq(&snapshot.title),
+                        game_boards::categories.eq(&snapshot.categories),
+                    ))
+                    .get_result(conn)
+                    .await?;
+
+                defer_unique_cells(conn).await?;
+                diesel::delete(
+                    game_board_question_mappings::table
+                        .filter(game_board_question_mappings::board_id.eq(board_id)),
+                )
+                .execute(conn)
+                .await?;
+
+                for cell in snapshot.cells {
+                    let existing = Question::find_by_id(conn, cell.question_id)
+                        .await
+                        .optional()?;
+                    let question_id = match existing {
+                        Some(question)
+                            if question.question == cell.question
+                                && question.answer == cell.answer =>
+                        {
+                            question.id
+                        }
+                        // Rewrite the text only of the owner's own questions that
+                        // no other board uses; anything else gets a copy
+                        Some(question)
+                            if question.user_id == board.user_id
+                                && !Self::used_elsewhere(conn, question.id, board_id).await? =>
+                        {
+                            Question::update_question(
+                                conn,
+                                question.id,
+                                UpdateQuestion {
+                                    question: Some(cell.question),
+                                    answer: Some(cell.answer),
+                                    alternate_answers: None,
+                                    locale: None,
+                                    tags: None,
+                                    difficulty: None,
+                                },
+                            )
+                            .await?;
+                            question.id
+                        }
+                        Some(question) => {
+                            Question::create(
+                                conn,
+                                NewQuestion {
+                                    user_id: board.user_id,
+                                    question: cell.question,
+                                    answer: cell.answer,
+                                    alternate_answers: question.alternate_answers,
+                                    tags: question.tags,
+                                    difficulty: question.difficulty,
+                                },
+                            )
+                            .await?
+                            .id
+                        }
+                        None => {
+                            Question::create(
+                                conn,
+                                NewQuestion {
+                                    user_id: board.user_id,
+                                    question: cell.question,
+                                    answer: cell.answer,
+                                    alternate_answers: Vec::new(),
+                                    tags: Vec::new(),
+                                    difficulty: None,
+                                },
+                            )
+                            .await?
+                            .id
+                        }
+                    };
+
+                    GameBoardQuestionMapping::create_mapping(
+                        conn,
+                        NewGameBoardQuestionMapping {
+                            board_id,
+                            question_id,
+                            daily_double: cell.daily_double,
+                            points: cell.points,
+                            grid_row: cell.grid_row,
+                            grid_col: cell.grid_col,
+                        },
+                    )
+                    .await?;
+                }
+
+                Self::record(conn, board_id).await?;
+                Ok(board)
+            }
+            .scope_boxed()
+        })
+        .await
+    }
+}
+
+/// List the differences going from `before` to `after`.
+pub fn diff_snapshots(before: &BoardSnapshot, after: &BoardSnapshot) -> Vec<RevisionChange> {
+    let mut changes = Vec::new();
+
+    fn change(
+        changes: &mut Vec<RevisionChange>,
+        path: String,
+        before: Option<String>,
+        after: Option<String>,
+    ) {
+        if before != after {
+            changes.push(RevisionChange {
+                path,
+                before,
+                after,
+            });
+        }
+    }
+
+    change(
+        &mut changes,
+        "title".to_string(),
+        Some(before.title.clone()),
+        Some(after.title.clone()),
+    );
+
+    let category_count = before.categories.len().max(after.categories.len());
+    for i in 0..category_count {
+        change(
+            &mut changes,
+            format!("categories[{}]", i),
+            before.categories.get(i).cloned().flatten(),
+            after.categories.get(i).cloned().flatten(),
+        );
+    }
+
+    let mut positions: Vec<(i32, i32)> = before
+        .cells
+        .iter()
+        .chain(after.cells.iter())
+        .map(|c| (c.grid_row, c.grid_col))
+        .collect();
+    positions.sort_by_key(|(row, col)| (*col, *row));
+    positions.dedup();
+
+    for (row, col) in positions {
+        let find = |cells: &[CellSnapshot]| {
+            cells
+                .iter()
+                .find(|c| c.grid_row == row && c.grid_col == col)
+                .cloned()
+        };
+        let (old, new) = (find(&before.cells), find(&after.cells));
+        let path = |field: &str| format!("cell[{},{}].{}", row, col, field);
+
+        change(
+            &mut changes,
+            path("question"),
+            old.as_ref().map(|c| c.question.clone()),
+            new.as_ref().map(|c| c.question.clone()),
+        );
+        change(
+            &mut changes,
+            path("answer"),
+            old.as_ref().map(|c| c.answer.clone()),
+            new.as_ref().map(|c| c.answer.clone()),
+        );
+        change(
+            &mut changes,
+            path("points"),
+            old.as_ref().map(|c| c.points.to_string()),
+            new.as_ref().map(|c| c.points.to_string()),
+        );
+        change(
+            &mut changes,
+            path("dailyDouble"),
+            old.as_ref().map(|c| c.daily_double.to_string()),
+            new.as_ref().map(|c| c.daily_double.to_string()),
+        );
+    }
+
+    changes
+}
@@ -0,0 +1,133 @@
+// models/game_board_translation.rs
+//! This module contains the `GameBoardTranslation` struct and related logic.
+//!
+//! A game board is written in one locale (`GameBoard::locale`) and its title
+//! and categories can be translated into others, one translation per locale.
+
+use crate::db::schema::game_board_translations;
+use crate::models::game_board::GameBoard;
+use async_graphql::SimpleObject;
+use chrono::{DateTime, Utc};
+use diesel::prelude::*;
+use diesel::upsert::excluded;
+use diesel_async::{AsyncPgConnection, RunQueryDsl};
+
+/// Represents a game board's title and categories in another locale.
+#[derive(Identifiable, Associations, Queryable, Selectable, Debug, SimpleObject, Clone)]
+#[diesel(table_name = game_board_translations)]
+#[diesel(belongs_to(GameBoard))]
+pub struct GameBoardTranslation {
+    pub id: i64,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub game_board_id: i64,
+    pub locale: String,
+    pub title: String,
+    /// The translated categories, column by column. A missing entry keeps
+    /// the original category.
+    pub categories: Vec<Option<String>>,
+}
+
+/// Represents a translation to be inserted or replaced.
+#[derive(Debug, Insertable, Clone)]
+#[diesel(table_name = game_board_translations)]
+pub struct NewGameBoardTranslation {
+    pub game_board_id: i64,
+    pub locale: String,
+    pub title: String,
+    pub categories: Vec<Option<String>>,
+}
+
+impl GameBoardTranslation {
+    /// Fetch every translation of a game board.
+    ///
+    /// # Arguments
+    /// * `conn` - A mutable reference to an async PostgreSQL connection.
+    /// * `game_board_id` - The unique identifier of the game board.
+    ///
+    /// # Returns
+    /// A `Result` containing a vector of translations or a Diesel error.
+    pub async fn fetch_by_game_board_id(
+        conn: &mut AsyncPgConnection,
+        game_board_id: i64,
+    ) -> Result<Vec<Self>, diesel::result::Error> {
+        game_board_translations::table
+            .filter(game_board_translations::game_board_id.eq(game_board_id))
+            .order(game_board_translations::locale)
+            .load::<Self>(conn)
+            .await
+    }
+
+    /// Fetch the translations of several game boards into any of `locales`.
+    ///
+    /// # Arguments
+    /// * `conn` - A mutable reference to an async PostgreSQL connection.
+    /// * `game_board_ids` - The unique identifiers of the game boards.
+    /// * `locales` - The locales wanted.
+    ///
+    /// # Returns
+    /// A `Result` containing a vector of translations or a Diesel error.
+    pub async fn fetch_for_game_boards(
+        conn: &mut AsyncPgConnection,
+        game_board_ids: &[i64],
+        locales: &[String],
+    ) -> Result<Vec<Self>, diesel::result::Error> {
+        game_board_translations::table
+            .filter(game_board_translations::game_board_id.eq_any(game_board_ids))
+            .filter(game_board_translations::locale.eq_any(locales))
+            .load::<Self>(conn)
+            .await
+    }
+
+    /// Insert a translation, replacing any existing one for the same board
+    /// and locale.
+    ///
+    /// # Arguments
+    /// * `conn` - A mutable reference to an async PostgreSQL connection.
+    /// * `translation` - The translation to store.
+    ///
+    /// # Returns
+    /// A `Result` containing the stored translation or a Diesel error.
+    pub async fn upsert(
+        conn: &mut AsyncPgConnection,
+        translation: NewGameBoardTranslation,
+    ) -> Result<Self, diesel::result::Error> {
+        diesel::insert_into(game_board_translations::table)
+            .values(&translation)
+            .on_conflict((
+                game_board_translations::game_board_id,
+                game_board_translations::locale,
+            ))
+            .do_update()
+            .set((
+                game_board_translations::title.eq(excluded(game_board_translations::title)),
+                game_board_translations::categories
+                    .eq(excluded(game_board_translations::categories)),
+            ))
+            .get_result(conn)
+            .await
+    }
+
+    /// Delete a game board's translation into a locale.
+    ///
+    /// # Arguments
+    /// * `conn` - A mutable reference to an async PostgreSQL connection.
+    /// * `game_board_id` - The unique identifier of the game board.
+    /// * `locale` - The locale of the translation.
+    ///
+    /// # Returns
+    /// A `Result` containing the number of rows deleted or a Diesel error.
+    pub async fn delete(
+        conn: &mut AsyncPgConnection,
+        game_board_id: i64,
+        locale: &str,
+    ) -> Result<usize, diesel::result::Error> {
+        diesel::delete(
+            game_board_translations::table
+                .filter(game_board_translations::game_board_id.eq(game_board_id))
+                .filter(game_board_translations::locale.eq(locale)),
+        )
+        .execute(conn)
+        .await
+    }
+}
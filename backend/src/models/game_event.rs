@@ -0,0 +1,222 @@
+// models/game_event.rs
+//! The append-only event log of a game.
+//!
+//! Every action that changes a game in play (selecting a cell, buzzing,
+//! responding, judging, changing a score, advancing the round, passing control
+//! of the board) records a `GameEvent`. Events are numbered from 0 within each
+//! game and are never updated, so the log can be replayed to rebuild the game
+//! at any point.
+
+use crate::db::schema::{game_events, games};
+use crate::models::game::Game;
+use async_graphql::{ComplexObject, Enum, SimpleObject};
+use chrono::{DateTime, Utc};
+use diesel::deserialize::{self, FromSql, FromSqlRow};
+use diesel::expression::AsExpression;
+use diesel::pg::{Pg, PgValue};
+use diesel::prelude::*;
+use diesel::serialize::{self, Output, ToSql};
+use diesel::sql_types::Text;
+use diesel_async::scoped_futures::ScopedFutureExt;
+use diesel_async::{AsyncConnection, AsyncPgConnection, RunQueryDsl};
+use serde::{Deserialize, Serialize};
+
+/// What kind of thing happened in a game.
+#[derive(
+    Enum, Copy, Clone, Debug, PartialEq, Eq, AsExpression, FromSqlRow, Serialize, Deserialize,
+)]
+#[diesel(sql_type = Text)]
+#[serde(rename_all = "snake_case")]
+pub enum GameEventType {
+    /// The host opened a cell.
+    CellSelected,
+    /// A team buzzed in.
+    Buzz,
+    /// A player typed a response.
+    Response,
+    /// The host ruled on an answer, changing a score.
+    Judged,
+    /// The host set a score directly.
+    ScoreChanged,
+    /// The game moved on to the next round.
+    RoundAdvanced,
+    /// Another player got control of the board.
+    ControlChanged,
+}
+
+impl GameEventType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            GameEventType::CellSelected => "cell_selected",
+            GameEventType::Buzz => "buzz",
+            GameEventType::Response => "response",
+            GameEventType::Judged => "judged",
+            GameEventType::ScoreChanged => "score_changed",
+            GameEventType::RoundAdvanced => "round_advanced",
+            GameEventType::ControlChanged => "control_changed",
+        }
+    }
+}
+
+impl ToSql<Text, Pg> for GameEventType {
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Pg>) -> serialize::Result {
+        <str as ToSql<Text, Pg>>::to_sql(self.as_str(), out)
+    }
+}
+
+impl FromSql<Text, Pg> for GameEventType {
+    fn from_sql(bytes: PgValue<'_>) -> deserialize::Result<Self> {
+        match <String as FromSql<Text, Pg>>::from_sql(bytes)?.as_str() {
+            "cell_selected" => Ok(GameEventType::CellSelected),
+            "buzz" => Ok(GameEventType::Buzz),
+            "response" => Ok(GameEventType::Response),
+            "judged" => Ok(GameEventType::Judged),
+            "score_changed" => Ok(GameEventType::ScoreChanged),
+            "round_advanced" => Ok(GameEventType::RoundAdvanced),
+            "control_changed" => Ok(GameEventType::ControlChanged),
+            other => Err(format!("Unknown game event type: {}", other).into()),
+        }
+    }
+}
+
+/// The details of an event. Which fields are set depends on its type.
+#[derive(Serialize, Deserialize, SimpleObject, Default, Clone, Debug, PartialEq)]
+#[serde(default)]
+pub struct GameEventData {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub game_question_id: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub grid_row: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub grid_col: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub player_id: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub team_id: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub member_id: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub response_id: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub response: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub correct: Option<bool>,
+    /// How much a ruling changed the score by.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub points: Option<i32>,
+    /// The score after the event.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub score: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub round: Option<i32>,
+}
+
+/// Represents one entry in a game's event log.
+#[derive(Identifiable, Associations, Queryable, Selectable, Debug, SimpleObject, Clone)]
+#[graphql(complex)]
+#[diesel(table_name = game_events)]
+#[diesel(belongs_to(Game))]
+pub struct GameEvent {
+    pub id: i64,
+    pub created_at: DateTime<Utc>,
+    pub game_id: i64,
+    /// The position of the event in the game's log, from 0.
+    pub sequence: i32,
+    pub event_type: GameEventType,
+    #[graphql(skip)]
+    pub data: serde_json::Value,
+}
+
+#[ComplexObject]
+impl GameEvent {
+    /// The details of the event
+    async fn data(&self) -> async_graphql::Result<GameEventData> {
+        Ok(self.event_data()?)
+    }
+}
+
+/// Represents a new event to be inserted into the database.
+#[derive(Debug, Insertable)]
+#[diesel(table_name = game_events)]
+pub struct NewGameEvent {
+    pub game_id: i64,
+    pub sequence: i32,
+    pub event_type: GameEventType,
+    pub data: serde_json::Value,
+}
+
+impl GameEvent {
+    /// Decode the stored details.
+    pub fn event_data(&self) -> Result<GameEventData, serde_json::Error> {
+        serde_json::from_value(self.data.clone())
+    }
+
+    /// Fetch a game's events in the order they happened.
+    ///
+    /// # Arguments
+    /// * `conn` - A mutable reference to an async PostgreSQL connection.
+    /// * `game_id` - The unique identifier of the game.
+    ///
+    /// # Returns
+    /// A `Result` containing a vector of events or a Diesel error.
+    pub async fn fetch_by_game_id(
+        conn: &mut AsyncPgConnection,
+        game_id: i64,
+    ) -> Result<Vec<Self>, diesel::result::Error> {
+        game_events::table
+            .filter(game_events::game_id.eq(game_id))
+            .order(game_events::sequence)
+            .load::<Self>(conn)
+            .await
+    }
+
+    /// Append an event to a game's log.
+    ///
+    /// The game row is locked while the next sequence number is taken, so
+    /// concurrent events in the same game are numbered without gaps.
+    ///
+    /// # Arguments
+    /// * `conn` - A mutable reference to an async PostgreSQL connection.
+    /// * `game_id` - The unique identifier of the game.
+    /// * `event_type` - What happened.
+    /// * `data` - The details of the event.
+    ///
+    /// # Returns
+    /// A `Result` containing the recorded event or a Diesel error.
+    pub async fn record(
+        conn: &mut AsyncPgConnection,
+        game_id: i64,
+        event_type: GameEventType,
+        data: GameEventData,
+    ) -> Result<Self, diesel::result::Error> {
+        let data = serde_json::to_value(&data)
+            .map_err(|e| diesel::result::Error::SerializationError(Box::new(e)))?;
+        conn.transaction::<_, diesel::result::Error, _>(|conn| {
+            async move {
+                games::table
+                    .find(game_id)
+                    .select(games::id)
+                    .for_update()
+                    .first::<i64>(conn)
+                    .await?;
+                let last: Option<i32> = game_events::table
+                    .filter(game_events::game_id.eq(game_id))
+                    .select(diesel::dsl::max(game_events::sequence))
+                    .first(conn)
+                    .await?;
+
+                diesel::insert_into(game_events::table)
+                    .values(&NewGameEvent {
+                        game_id,
+                        sequence: last.map_or(0, |s| s + 1),
+                        event_type,
+                        data,
+                    })
+                    .get_result(conn)
+                    .await
+            }
+            .scope_boxed()
+        })
+        .await
+    }
+}
@@ -0,0 +1,187 @@
+// models/game_question.rs
+//! This module contains the `GameQuestion` struct and related logic.
+//!
+//! A `GameQuestion` is a game's own copy of one cell of its board, taken when
+//! the game is created. Games read their questions from here rather than the
+//! live board, so editing a board never changes a game in progress.
+
+use crate::db::schema::game_questions;
+use crate::models::game::Game;
+use crate::models::GBQ;
+use async_graphql::SimpleObject;
+use chrono::{DateTime, Utc};
+use derive_builder::Builder;
+use diesel::prelude::*;
+use diesel_async::{AsyncPgConnection, RunQueryDsl};
+
+/// Represents a frozen board cell belonging to a game.
+#[derive(
+    Identifiable, Associations, Queryable, Selectable, Debug, SimpleObject, Builder, Clone,
+)]
+#[diesel(table_name = game_questions)]
+#[diesel(belongs_to(Game))]
+pub struct GameQuestion {
+    pub id: i64,
+    pub created_at: DateTime<Utc>,
+    pub game_id: i64,
+    /// The library question this cell was copied from, unless it was purged.
+    pub source_question_id: Option<i64>,
+    pub question: String,
+    pub answer: String,
+    pub daily_double: bool,
+    pub points: i32,
+    pub grid_row: i32,
+    pub grid_col: i32,
+    /// Other responses to accept besides `answer`.
+    pub alternate_answers: Vec<String>,
+    /// When the cell was first opened or buzzed on, if it has been played.
+    pub played_at: Option<DateTime<Utc>>,
+}
+
+/// Represents a new frozen cell to be inserted into the database.
+#[derive(Debug, Insertable, Builder)]
+#[diesel(table_name = game_questions)]
+pub struct NewGameQuestion {
+    pub game_id: i64,
+    pub source_question_id: Option<i64>,
+    pub question: String,
+    pub answer: String,
+    pub daily_double: bool,
+    pub points: i32,
+    pub grid_row: i32,
+    pub grid_col: i32,
+    pub alternate_answers: Vec<String>,
+}
+
+impl GameQuestion {
+    /// Find a frozen cell by its unique ID.
+    ///
+    /// # Arguments
+    /// * `conn` - A mutable reference to an async PostgreSQL connection.
+    /// * `game_question_id` - The unique identifier of the game question.
+    ///
+    /// # Returns
+    /// A `Result` containing the game question or a Diesel error.
+    pub async fn find_by_id(
+        conn: &mut AsyncPgConnection,
+        game_question_id: i64,
+    ) -> Result<Self, diesel::result::Error> {
+        game_questions::table
+            .find(game_question_id)
+            .first(conn)
+            .await
+    }
+
+    /// Fetch every frozen cell of a game, ordered by column then row.
+    ///
+    /// # Arguments
+    /// * `conn` - A mutable reference to an async PostgreSQL connection.
+    /// * `game_id` - The unique identifier of the game.
+    ///
+    /// # Returns
+    /// A `Result` containing a vector of game questions or a Diesel error.
+    pub async fn fetch_by_game_id(
+        conn: &mut AsyncPgConnection,
+        game_id: i64,
+    ) -> Result<Vec<Self>, diesel::result::Error> {
+        game_questions::table
+            .filter(game_questions::game_id.eq(game_id))
+            .order((game_questions::grid_col, game_questions::grid_row))
+            .load::<Self>(conn)
+            .await
+    }
+
+    /// Find the frozen cell of a game at a grid position.
+    ///
+    /// # Arguments
+    /// * `conn` - A mutable reference to an async PostgreSQL connection.
+    /// * `game_id` - The unique identifier of the game.
+    /// * `grid_row` - The row of the cell.
+    /// * `grid_col` - The column of the cell.
+    ///
+    /// # Returns
+    /// A `Result` containing the game question or a Diesel error.
+    pub async fn find_by_cell(
+        conn: &mut AsyncPgConnection,
+        game_id: i64,
+        grid_row: i32,
+        grid_col: i32,
+    ) -> Result<Self, diesel::result::Error> {
+        game_questions::table
+            .filter(game_questions::game_id.eq(game_id))
+            .filter(game_questions::grid_row.eq(grid_row))
+            .filter(game_questions::grid_col.eq(grid_col))
+            .first(conn)
+            .await
+    }
+
+    /// Blank out the answers, so the cell can be shown to contestants.
+    pub fn hide_answers(&mut self) {
+        self.answer.clear();
+        self.alternate_answers.clear();
+    }
+
+    /// Mark a frozen cell as played, keeping the time it was first played.
+    ///
+    /// # Arguments
+    /// * `conn` - A mutable reference to an async PostgreSQL connection.
+    /// * `game_question_id` - The unique identifier of the game question.
+    ///
+    /// # Returns
+    /// A `Result` containing 1 if the cell was newly marked, 0 if it had
+    /// already been played, or a Diesel error.
+    pub async fn mark_played(
+        conn: &mut AsyncPgConnection,
+        game_question_id: i64,
+    ) -> Result<usize, diesel::result::Error> {
+        diesel::update(
+            game_questions::table
+                .find(game_question_id)
+                .filter(game_questions::played_at.is_null()),
+        )
+        .set(game_questions::played_at.eq(Some(Utc::now())))
+        .execute(conn)
+        .await
+    }
+
+    /// Copy the current cells and question text of a board into a game.
+    ///
+    /// # Arguments
+    /// * `conn` - A mutable reference to an async PostgreSQL connection.
+    /// * `game_id` - The unique identifier of the game receiving the copy.
+    /// * `board_id` - The unique identifier of the game board to copy from.
+    ///
+    /// # Returns
+    /// A `Result` containing the frozen cells or a Diesel error.
+    pub async fn freeze_board(
+        conn: &mut AsyncPgConnection,
+        game_id: i64,
+        board_id: i64,
+    ) -> Result<Vec<Self>, diesel::result::Error> {
+        let cells: Vec<NewGameQuestion> =
+            GBQ::fetch_all_game_board_questions_by_board_id(conn, board_id)
+                .await?
+                .into_iter()
+                .map(|gbq| NewGameQuestion {
+                    game_id,
+                    source_question_id: Some(gbq.question.id),
+                    question: gbq.question.question,
+                    answer: gbq.question.answer,
+                    daily_double: gbq.mapping.daily_double,
+                    points: gbq.mapping.points,
+                    grid_row: gbq.mapping.grid_row,
+                    grid_col: gbq.mapping.grid_col,
+                    alternate_answers: gbq.question.alternate_answers,
+                })
+                .collect();
+
+        if cells.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        diesel::insert_into(game_questions::table)
+            .values(&cells)
+            .get_results(conn)
+            .await
+    }
+}
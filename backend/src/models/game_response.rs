@@ -0,0 +1,142 @@
+// models/game_response.rs
+//! This module contains the `GameResponse` struct and related logic.
+//!
+//! A `GameResponse` is the answer a remote contestant typed for a game
+//! question while it was open, and the host's ruling on it.
+
+use crate::db::schema::game_responses;
+use crate::models::game_question::GameQuestion;
+use crate::models::player::Player;
+use async_graphql::SimpleObject;
+use chrono::{DateTime, Utc};
+use diesel::prelude::*;
+use diesel_async::{AsyncPgConnection, RunQueryDsl};
+
+/// Represents a contestant's typed response to a game question.
+#[derive(Identifiable, Associations, Queryable, Selectable, Debug, SimpleObject, Clone)]
+#[diesel(table_name = game_responses)]
+#[diesel(belongs_to(GameQuestion))]
+#[diesel(belongs_to(Player))]
+pub struct GameResponse {
+    pub id: i64,
+    /// When the response was submitted.
+    pub created_at: DateTime<Utc>,
+    pub game_question_id: i64,
+    pub player_id: i64,
+    pub response: String,
+    /// The host's ruling, or `None` until the response is judged.
+    pub correct: Option<bool>,
+    /// Points added to (or, when negative, taken from) the player's score.
+    pub points_awarded: i32,
+    pub judged_at: Option<DateTime<Utc>>,
+}
+
+/// Represents a new response to be inserted into the database.
+#[derive(Debug, Insertable)]
+#[diesel(table_name = game_responses)]
+pub struct NewGameResponse {
+    pub game_question_id: i64,
+    pub player_id: i64,
+    pub response: String,
+}
+
+impl GameResponse {
+    /// Find a response by its unique ID.
+    ///
+    /// # Arguments
+    /// * `conn` - A mutable reference to an async PostgreSQL connection.
+    /// * `response_id` - The unique identifier of the response to fetch.
+    ///
+    /// # Returns
+    /// A `Result` containing the response or a Diesel error.
+    pub async fn find_by_id(
+        conn: &mut AsyncPgConnection,
+        response_id: i64,
+    ) -> Result<Self, diesel::result::Error> {
+        game_responses::table.find(response_id).first(conn).await
+    }
+
+    /// Find a response by its unique ID and lock it until the transaction
+    /// ends, so concurrent rulings on it wait for each other.
+    ///
+    /// # Arguments
+    /// * `conn` - A mutable reference to an async PostgreSQL connection.
+    /// * `response_id` - The unique identifier of the response to lock.
+    ///
+    /// # Returns
+    /// A `Result` containing the response or a Diesel error.
+    pub async fn find_for_update(
+        conn: &mut AsyncPgConnection,
+        response_id: i64,
+    ) -> Result<Self, diesel::result::Error> {
+        game_responses::table
+            .find(response_id)
+            .for_update()
+            .first(conn)
+            .await
+    }
+
+    /// Fetch the responses to a game question, in the order they arrived.
+    ///
+    /// # Arguments
+    /// * `conn` - A mutable reference to an async PostgreSQL connection.
+    /// * `game_question_id` - The unique identifier of the game question.
+    ///
+    /// # Returns
+    /// A `Result` containing a vector of responses or a Diesel error.
+    pub async fn fetch_by_game_question_id(
+        conn: &mut AsyncPgConnection,
+        game_question_id: i64,
+    ) -> Result<Vec<Self>, diesel::result::Error> {
+        game_responses::table
+            .filter(game_responses::game_question_id.eq(game_question_id))
+            .order((game_responses::created_at, game_responses::id))
+            .load::<Self>(conn)
+            .await
+    }
+
+    /// Store a contestant's response.
+    ///
+    /// # Arguments
+    /// * `conn` - A mutable reference to an async PostgreSQL connection.
+    /// * `new_response` - A `NewGameResponse` instance containing the response.
+    ///
+    /// # Returns
+    /// A `Result` containing the new response, or a unique violation if the
+    /// player already responded to this game question.
+    pub async fn create(
+        conn: &mut AsyncPgConnection,
+        new_response: NewGameResponse,
+    ) -> Result<Self, diesel::result::Error> {
+        diesel::insert_into(game_responses::table)
+            .values(&new_response)
+            .get_result(conn)
+            .await
+    }
+
+    /// Record the host's ruling on a response.
+    ///
+    /// # Arguments
+    /// * `conn` - A mutable reference to an async PostgreSQL connection.
+    /// * `response_id` - The unique identifier of the response.
+    /// * `correct` - Whether the response was accepted.
+    /// * `points_awarded` - The signed points the ruling is worth.
+    ///
+    /// # Returns
+    /// A `Result` containing the judged response or a Diesel error.
+    pub async fn judge(
+        conn: &mut AsyncPgConnection,
+        response_id: i64,
+        correct: bool,
+        points_awarded: i32,
+    ) -> Result<Self, diesel::result::Error> {
+        diesel::update(game_responses::table.find(response_id))
+            .set((
+                game_responses::correct.eq(Some(correct)),
+                game_responses::points_awarded.eq(points_awarded),
+                game_responses::judged_at.eq(Some(Utc::now())),
+            ))
+            .get_result(conn)
+            .await
+    }
+}
@@ -0,0 +1,327 @@
+// models/game_timer.rs
+//! This module contains the `GameTimer` struct and related logic.
+//!
+//! Each game has at most one timer row, created the first time the host
+//! configures or starts a timer. It holds the current phase, when that phase
+//! ends, and how long each phase lasts in this game.
+
+use crate::db::schema::game_timers;
+use crate::models::game::Game;
+use async_graphql::{Enum, SimpleObject};
+use chrono::{DateTime, Utc};
+use diesel::deserialize::{self, FromSql, FromSqlRow};
+use diesel::expression::AsExpression;
+use diesel::pg::{Pg, PgValue};
+use diesel::prelude::*;
+use diesel::serialize::{self, Output, ToSql};
+use diesel::sql_types::Text;
+use diesel_async::{AsyncPgConnection, RunQueryDsl};
+
+/// Where a game is in the cycle of showing and answering a question.
+#[derive(Enum, Copy, Clone, Debug, PartialEq, Eq, AsExpression, FromSqlRow)]
+#[diesel(sql_type = Text)]
+pub enum GamePhase {
+    /// No timer is running.
+    Idle,
+    /// The clue is being read; buzzing is not open yet.
+    Reading,
+    /// Contestants may buzz in.
+    BuzzWindow,
+    /// The contestant who buzzed must answer.
+    Answering,
+    /// Contestants are writing their Final Jeopardy responses.
+    FinalJeopardy,
+}
+
+impl GamePhase {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            GamePhase::Idle => "idle",
+            GamePhase::Reading => "reading",
+            GamePhase::BuzzWindow => "buzz_window",
+            GamePhase::Answering => "answering",
+            GamePhase::FinalJeopardy => "final_jeopardy",
+        }
+    }
+}
+
+impl ToSql<Text, Pg> for GamePhase {
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Pg>) -> serialize::Result {
+        <str as ToSql<Text, Pg>>::to_sql(self.as_str(), out)
+    }
+}
+
+impl FromSql<Text, Pg> for GamePhase {
+    fn from_sql(bytes: PgValue<'_>) -> deserialize::Result<Self> {
+        match <String as FromSql<Text, Pg>>::from_sql(bytes)?.as_str() {
+            "idle" => Ok(GamePhase::Idle),
+            "reading" => Ok(GamePhase::Reading),
+            "buzz_window" => Ok(GamePhase::BuzzWindow),
+            "answering" => Ok(GamePhase::Answering),
+            "final_jeopardy" => Ok(GamePhase::FinalJeopardy),
+            other => Err(format!("Unknown game phase: {}", other).into()),
+        }
+    }
+}
+
+/// Represents the server-side clock of a game.
+#[derive(Identifiable, Associations, Queryable, Selectable, Debug, SimpleObject, Clone)]
+#[diesel(primary_key(game_id))]
+#[diesel(table_name = game_timers)]
+#[diesel(belongs_to(Game))]
+pub struct GameTimer {
+    pub game_id: i64,
+    pub phase: GamePhase,
+    /// The game question the phase is about, if any.
+    pub game_question_id: Option<i64>,
+    /// When the current phase began.
+    pub started_at: DateTime<Utc>,
+    /// When the current phase runs out, or `None` if it does not.
+    pub ends_at: Option<DateTime<Utc>>,
+    pub reading_seconds: i32,
+    pub buzz_seconds: i32,
+    pub answer_seconds: i32,
+    pub final_seconds: i32,
+    /// While the game is paused, how long the phase had left.
+    pub paused_remaining_ms: Option<i64>,
+}
+
+/// Represents the phase lengths to change for a game. Unset fields are kept.
+#[derive(Debug, Default, AsChangeset)]
+#[diesel(table_name = game_timers)]
+pub struct UpdateTimerSettings {
+    pub reading_seconds: Option<i32>,
+    pub buzz_seconds: Option<i32>,
+    pub answer_seconds: Option<i32>,
+    pub final_seconds: Option<i32>,
+}
+
+impl GameTimer {
+    /// Find the timer of a game.
+    ///
+    /// # Arguments
+    /// * `conn` - A mutable reference to an async PostgreSQL connection.
+    /// * `game_id` - The unique identifier of the game.
+    ///
+    /// # Returns
+    /// A `Result` containing the timer, `None` if the game never used one, or
+    /// a Diesel error.
+    pub async fn find(
+        conn: &mut AsyncPgConnection,
+        game_id: i64,
+    ) -> Result<Option<Self>, diesel::result::Error> {
+        game_timers::table
+            .find(game_id)
+            .first(conn)
+            .await
+            .optional()
+    }
+
+    /// Fetch the timer of a game, creating an idle one with the default
+    /// phase lengths if it does not exist yet.
+    ///
+    /// # Arguments
+    /// * `conn` - A mutable reference to an async PostgreSQL connection.
+    /// * `game_id` - The unique identifier of the game.
+    ///
+    /// # Returns
+    /// A `Result` containing the timer or a Diesel error.
+    pub async fn ensure(
+        conn: &mut AsyncPgConnection,
+        game_id: i64,
+    ) -> Result<Self, diesel::result::Error> {
+        diesel::insert_into(game_timers::table)
+            .values(game_timers::game_id.eq(game_id))
+            .on_conflict_do_nothing()
+            .execute(conn)
+            .await?;
+        game_timers::table.find(game_id).first(conn).await
+    }
+
+    /// Fetch every timer whose phase ran out at or before `now`.
+    ///
+    /// # Arguments
+    /// * `conn` - A mutable reference to an async PostgreSQL connection.
+    /// * `now` - The current server time.
+    ///
+    /// # Returns
+    /// A `Result` containing the expired timers or a Diesel error.
+    pub async fn fetch_expired(
+        conn: &mut AsyncPgConnection,
+        now: DateTime<Utc>,
+    ) -> Result<Vec<Self>, diesel::result::Error> {
+        game_timers::table
+            .filter(game_timers::ends_at.le(now))
+            .order(game_timers::ends_at)
+            .load::<Self>(conn)
+            .await
+    }
+
+    /// Move a game's timer into a phase.
+    ///
+    /// # Arguments
+    /// * `conn` - A mutable reference to an async PostgreSQL connection.
+    /// * `game_id` - The unique identifier of the game.
+    /// * `phase` - The phase to enter.
+    /// * `game_question_id` - The game question the phase is about.
+    /// * `started_at` - When the phase begins.
+    /// * `ends_at` - When the phase runs out, if it does.
+    ///
+    /// # Returns
+    /// A `Result` containing the updated timer or a Diesel error.
+    pub async fn set_phase(
+        conn: &mut AsyncPgConnection,
+        game_id: i64,
+        phase: GamePhase,
+        game_question_id: Option<i64>,
+        started_at: DateTime<Utc>,
+        ends_at: Option<DateTime<Utc>>,
+    ) -> Result<Self, diesel::result::Error> {
+        diesel::update(game_timers::table.find(game_id))
+            .set((
+                game_timers::phase.eq(phase),
+                game_timers::game_question_id.eq(game_question_id),
+                game_timers::started_at.eq(started_at),
+                game_timers::ends_at.eq(ends_at),
+                game_timers::paused_remaining_ms.eq(None::<i64>),
+            ))
+            .get_result(conn)
+            .await
+    }
+
+    /// Stop a running phase from running out, keeping the time it had left.
+    ///
+    /// # Arguments
+    /// * `conn` - A mutable reference to an async PostgreSQL connection.
+    /// * `running` - The timer as it was read before pausing.
+    /// * `now` - When the game is paused.
+    ///
+    /// # Returns
+    /// A `Result` containing the updated timer, `None` if no phase was
+    /// running or the timer had already moved on, or a Diesel error.
+    pub async fn pause(
+        conn: &mut AsyncPgConnection,
+        running: &GameTimer,
+        now: DateTime<Utc>,
+    ) -> Result<Option<Self>, diesel::result::Error> {
+        let Some(ends_at) = running.ends_at else {
+            return Ok(None);
+        };
+        diesel::update(
+            game_timers::table
+                .find(running.game_id)
+                .filter(game_timers::ends_at.eq(ends_at)),
+        )
+        .set((
+            game_timers::ends_at.eq(None::<DateTime<Utc>>),
+            game_timers::paused_remaining_ms.eq(Some((ends_at - now).num_milliseconds().max(0))),
+        ))
+        .get_result(conn)
+        .await
+        .optional()
+    }
+
+    /// Restart a paused phase with the time it had left.
+    ///
+    /// # Arguments
+    /// * `conn` - A mutable reference to an async PostgreSQL connection.
+    /// * `game_id` - The unique identifier of the game.
+    /// * `now` - When the game is unpaused.
+    ///
+    /// # Returns
+    /// A `Result` containing the updated timer, `None` if no phase was
+    /// paused, or a Diesel error.
+    pub async fn unpause(
+        conn: &mut AsyncPgConnection,
+        game_id: i64,
+        now: DateTime<Utc>,
+    ) -> Result<Option<Self>, diesel::result::Error> {
+        let Some(timer) = Self::find(conn, game_id).await? else {
+            return Ok(None);
+        };
+        let Some(remaining_ms) = timer.paused_remaining_ms else {
+            return Ok(None);
+        };
+        diesel::update(
+            game_timers::table
+                .find(game_id)
+                .filter(game_timers::paused_remaining_ms.eq(remaining_ms)),
+        )
+        .set((
+            game_timers::ends_at.eq(Some(now + chrono::Duration::milliseconds(remaining_ms))),
+            game_timers::paused_remaining_ms.eq(None::<i64>),
+        ))
+        .get_result(conn)
+        .await
+        .optional()
+    }
+
+    /// Move a timer out of a phase that ran out, unless another server or
+    /// request already did.
+    ///
+    /// # Arguments
+    /// * `conn` - A mutable reference to an async PostgreSQL connection.
+    /// * `expired` - The timer as it was read when it ran out.
+    /// * `phase` - The phase to enter.
+    /// * `game_question_id` - The game question the new phase is about.
+    /// * `ends_at` - When the new phase runs out, if it does. It starts when
+    ///   the old one ended.
+    ///
+    /// # Returns
+    /// A `Result` containing the updated timer, `None` if the timer had
+    /// already moved on, or a Diesel error.
+    pub async fn advance(
+        conn: &mut AsyncPgConnection,
+        expired: &GameTimer,
+        phase: GamePhase,
+        game_question_id: Option<i64>,
+        ends_at: Option<DateTime<Utc>>,
+    ) -> Result<Option<Self>, diesel::result::Error> {
+        let Some(expired_at) = expired.ends_at else {
+            return Ok(None);
+        };
+        diesel::update(
+            game_timers::table
+                .find(expired.game_id)
+                .filter(game_timers::phase.eq(expired.phase))
+                .filter(game_timers::ends_at.eq(expired_at)),
+        )
+        .set((
+            game_timers::phase.eq(phase),
+            game_timers::game_question_id.eq(game_question_id),
+            game_timers::started_at.eq(expired_at),
+            game_timers::ends_at.eq(ends_at),
+        ))
+        .get_result(conn)
+        .await
+        .optional()
+    }
+
+    /// Change how long each phase lasts in a game.
+    ///
+    /// # Arguments
+    /// * `conn` - A mutable reference to an async PostgreSQL connection.
+    /// * `game_id` - The unique identifier of the game.
+    /// * `settings` - The phase lengths to change.
+    ///
+    /// # Returns
+    /// A `Result` containing the updated timer or a Diesel error.
+    pub async fn update_settings(
+        conn: &mut AsyncPgConnection,
+        game_id: i64,
+        settings: UpdateTimerSettings,
+    ) -> Result<Self, diesel::result::Error> {
+        let timer = Self::ensure(conn, game_id).await?;
+        if settings.reading_seconds.is_none()
+            && settings.buzz_seconds.is_none()
+            && settings.answer_seconds.is_none()
+            && settings.final_seconds.is_none()
+        {
+            return Ok(timer);
+        }
+        diesel::update(game_timers::table.find(game_id))
+            .set(&settings)
+            .get_result(conn)
+            .await
+    }
+}
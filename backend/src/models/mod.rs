@@ -1,4 +1,7 @@
 // model modules
+pub mod board_collaborator;
+pub mod board_rating;
+pub mod board_report;
 pub mod game;
 pub mod game_board;
 pub mod game_board_question;
@@ -8,6 +11,16 @@ pub use game_board_question_mapping::{
     GameBoardQuestionMapping as GBQMapping, NewGameBoardQuestionMapping as NewGBQMapping,
     UpdateGameBoardQuestionMapping as UpdateGBQMapping,
 };
+pub mod game_board_revision;
+pub mod game_board_translation;
+pub mod game_event;
+pub mod game_question;
+pub mod game_response;
+pub mod game_timer;
 pub mod player;
 pub mod question;
+pub mod question_media;
+pub mod question_translation;
+pub mod team;
+pub mod team_answer;
 pub mod user;
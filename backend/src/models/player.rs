@@ -26,6 +26,10 @@ pub struct Player {
     pub game_id: i64,
     pub player_name: String,
     pub score: i32,
+    /// Sent with a remote contestant's responses to prove who they are.
+    /// Only the host can read it.
+    #[graphql(skip)]
+    pub player_token: String,
 }
 
 /// Represents a new player to be inserted into the database.
@@ -116,6 +120,26 @@ impl Player {
             .await
     }
 
+    /// Add `delta` (which may be negative) to a player's score.
+    ///
+    /// # Arguments
+    /// * `conn` - A mutable reference to an async PostgreSQL connection.
+    /// * `player_id` - The unique identifier of the player.
+    /// * `delta` - The points to add.
+    ///
+    /// # Returns
+    /// A `Result` containing the updated player or a Diesel error.
+    pub async fn adjust_score(
+        conn: &mut AsyncPgConnection,
+        player_id: i64,
+        delta: i32,
+    ) -> Result<Self, diesel::result::Error> {
+        diesel::update(players::table.find(player_id))
+            .set(players::score.eq(players::score + delta))
+            .get_result(conn)
+            .await
+    }
+
     /// Delete a player by their unique ID.
     ///
     /// # Arguments
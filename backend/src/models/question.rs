@@ -1,8 +1,12 @@
 // models/question.rs
 
+use crate::db::pool::DBPool;
 use crate::db::schema::questions;
+use crate::models::question_media::QuestionMedia;
+use crate::models::question_translation::QuestionTranslation;
 use crate::models::user::User;
-use async_graphql::SimpleObject;
+use crate::services::rich_text::render_markdown;
+use async_graphql::{ComplexObject, Context, SimpleObject};
 use chrono::{DateTime, Utc};
 use derive_builder::Builder;
 use diesel::prelude::*;
@@ -16,6 +20,7 @@ use diesel_async::{AsyncPgConnection, RunQueryDsl};
 #[derive(
     Identifiable, Associations, Queryable, SimpleObject, Selectable, Debug, Builder, Clone,
 )]
+#[graphql(complex)]
 #[diesel(table_name = questions)]
 #[diesel(belongs_to(User))]
 pub struct Question {
@@ -27,10 +32,72 @@ pub struct Question {
     pub updated_at: DateTime<Utc>,
     /// The unique identifier of the user who created the question.
     pub user_id: i64,
-    /// The text of the question.
+    /// The text of the question, as Markdown.
     pub question: String,
-    /// The answer to the question.
+    /// The answer to the question, as Markdown.
     pub answer: String,
+    /// The timestamp when the question was archived, if it has been.
+    /// Archived questions leave the author's library but stay on any
+    /// board that already uses them.
+    pub archived_at: Option<DateTime<Utc>>,
+    /// Other responses to accept besides `answer`.
+    pub alternate_answers: Vec<String>,
+    /// The locale the question is written in. When a question is fetched
+    /// for another locale and has a translation into it, this is the
+    /// translation's locale.
+    pub locale: String,
+    /// Lowercased topics the question is filed under, used by the board
+    /// generator alongside the categories it has been placed in.
+    pub tags: Vec<String>,
+    /// How hard the question is, from 1 (easiest) to 5.
+    pub difficulty: Option<i32>,
+    /// The question this one was copied from, when the board generator
+    /// copied it out of another user's library.
+    pub copied_from_id: Option<i64>,
+}
+
+#[ComplexObject]
+impl Question {
+    /// The text of the question rendered as HTML
+    async fn question_html(&self) -> String {
+        render_markdown(&self.question)
+    }
+
+    /// The answer rendered as HTML
+    async fn answer_html(&self) -> String {
+        render_markdown(&self.answer)
+    }
+
+    /// Pictures, audio and video attached to the question
+    async fn media(&self, ctx: &Context<'_>) -> async_graphql::Result<Vec<QuestionMedia>> {
+        let pool = ctx.data::<DBPool>().map_err(|e| {
+            async_graphql::Error::new(format!("Cannot get DBPool from context: {:?}", e))
+        })?;
+        let mut conn = pool
+            .get()
+            .await
+            .map_err(|e| async_graphql::Error::new(format!("Failed to get connection: {}", e)))?;
+
+        let media = QuestionMedia::fetch_by_question_id(&mut conn, self.id).await?;
+        Ok(media)
+    }
+
+    /// Translations of the question into other locales
+    async fn translations(
+        &self,
+        ctx: &Context<'_>,
+    ) -> async_graphql::Result<Vec<QuestionTranslation>> {
+        let pool = ctx.data::<DBPool>().map_err(|e| {
+            async_graphql::Error::new(format!("Cannot get DBPool from context: {:?}", e))
+        })?;
+        let mut conn = pool
+            .get()
+            .await
+            .map_err(|e| async_graphql::Error::new(format!("Failed to get connection: {}", e)))?;
+
+        let translations = QuestionTranslation::fetch_by_question_id(&mut conn, self.id).await?;
+        Ok(translations)
+    }
 }
 
 /// Represents a new question to be inserted into the database.
@@ -40,6 +107,12 @@ pub struct NewQuestion {
     pub user_id: i64,
     pub question: String,
     pub answer: String,
+    #[builder(default)]
+    pub alternate_answers: Vec<String>,
+    #[builder(default)]
+    pub tags: Vec<String>,
+    #[builder(default)]
+    pub difficulty: Option<i32>,
 }
 
 /// Represents the fields to update in an existing question.
@@ -48,6 +121,10 @@ pub struct NewQuestion {
 pub struct UpdateQuestion {
     pub question: Option<String>,
     pub answer: Option<String>,
+    pub alternate_answers: Option<Vec<String>>,
+    pub locale: Option<String>,
+    pub tags: Option<Vec<String>>,
+    pub difficulty: Option<i32>,
 }
 
 impl Question {
@@ -84,7 +161,7 @@ impl Question {
             .await
     }
 
-    /// Fetch all questions from the database.
+    /// Fetch all live (non-archived) questions from the database.
     ///
     /// # Arguments
     /// * `conn` - A mutable reference to an async PostgreSQL connection.
@@ -92,10 +169,13 @@ impl Question {
     /// # Returns
     /// A `Result` containing a vector of questions or a Diesel error.
     pub async fn all(conn: &mut AsyncPgConnection) -> Result<Vec<Self>, diesel::result::Error> {
-        questions::table.load::<Self>(conn).await
+        questions::table
+            .filter(questions::archived_at.is_null())
+            .load::<Self>(conn)
+            .await
     }
 
-    /// Fetch all questions created by a specific user.
+    /// Fetch all live (non-archived) questions created by a specific user.
     ///
     /// # Arguments
     /// * `conn` - A mutable reference to an async PostgreSQL connection.
@@ -109,6 +189,26 @@ impl Question {
     ) -> Result<Vec<Self>, diesel::result::Error> {
         questions::table
             .filter(questions::user_id.eq(user_id))
+            .filter(questions::archived_at.is_null())
+            .load::<Self>(conn)
+            .await
+    }
+
+    /// Fetch all archived questions created by a specific user.
+    ///
+    /// # Arguments
+    /// * `conn` - A mutable reference to an async PostgreSQL connection.
+    /// * `user_id` - The unique identifier of the user.
+    ///
+    /// # Returns
+    /// A `Result` containing a vector of archived questions or a Diesel error.
+    pub async fn fetch_archived_by_user(
+        conn: &mut AsyncPgConnection,
+        user_id: i64,
+    ) -> Result<Vec<Self>, diesel::result::Error> {
+        questions::table
+            .filter(questions::user_id.eq(user_id))
+            .filter(questions::archived_at.is_not_null())
             .load::<Self>(conn)
             .await
     }
@@ -131,6 +231,47 @@ impl Question {
             .await
     }
 
+    /// Copy another user's question into a user's library, reusing an
+    /// earlier copy the user still has instead of making another.
+    ///
+    /// # Arguments
+    /// * `conn` - A mutable reference to an async PostgreSQL connection.
+    /// * `original` - The question to copy.
+    /// * `user_id` - The unique identifier of the user who will own the copy.
+    ///
+    /// # Returns
+    /// A `Result` containing the user's copy of the question or a Diesel error.
+    pub async fn copy_for_user(
+        conn: &mut AsyncPgConnection,
+        original: &Question,
+        user_id: i64,
+    ) -> Result<Self, diesel::result::Error> {
+        let existing = questions::table
+            .filter(questions::user_id.eq(user_id))
+            .filter(questions::copied_from_id.eq(original.id))
+            .filter(questions::archived_at.is_null())
+            .order(questions::id)
+            .first::<Self>(conn)
+            .await
+            .optional()?;
+        if let Some(copy) = existing {
+            return Ok(copy);
+        }
+        diesel::insert_into(questions::table)
+            .values((
+                questions::user_id.eq(user_id),
+                questions::question.eq(&original.question),
+                questions::answer.eq(&original.answer),
+                questions::alternate_answers.eq(&original.alternate_answers),
+                questions::locale.eq(&original.locale),
+                questions::tags.eq(&original.tags),
+                questions::difficulty.eq(original.difficulty),
+                questions::copied_from_id.eq(original.id),
+            ))
+            .get_result(conn)
+            .await
+    }
+
     /// Update an existing question.
     ///
     /// # Arguments
@@ -151,7 +292,48 @@ impl Question {
             .await
     }
 
-    /// Delete a question by its unique ID.
+    /// Archive (soft-delete) a question.
+    ///
+    /// Mappings are left untouched, so boards that already use the question
+    /// keep showing it. It can no longer be added to new boards.
+    ///
+    /// # Arguments
+    /// * `conn` - A mutable reference to an async PostgreSQL connection.
+    /// * `question_id` - The unique identifier of the question to archive.
+    ///
+    /// # Returns
+    /// A `Result` containing the archived question or a Diesel error.
+    pub async fn archive(
+        conn: &mut AsyncPgConnection,
+        question_id: i64,
+    ) -> Result<Self, diesel::result::Error> {
+        diesel::update(questions::table.find(question_id))
+            .set(questions::archived_at.eq(Some(Utc::now())))
+            .get_result(conn)
+            .await
+    }
+
+    /// Restore a previously archived question.
+    ///
+    /// # Arguments
+    /// * `conn` - A mutable reference to an async PostgreSQL connection.
+    /// * `question_id` - The unique identifier of the question to restore.
+    ///
+    /// # Returns
+    /// A `Result` containing the restored question or a Diesel error.
+    pub async fn restore(
+        conn: &mut AsyncPgConnection,
+        question_id: i64,
+    ) -> Result<Self, diesel::result::Error> {
+        diesel::update(questions::table.find(question_id))
+            .set(questions::archived_at.eq(None::<DateTime<Utc>>))
+            .get_result(conn)
+            .await
+    }
+
+    /// Permanently delete a question by its unique ID.
+    ///
+    /// This also removes the question from every board that uses it.
     ///
     /// # Arguments
     /// * `conn` - A mutable reference to an async PostgreSQL connection.
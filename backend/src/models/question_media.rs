@@ -0,0 +1,171 @@
+// models/question_media.rs
+//! This module contains the `QuestionMedia` struct and related logic.
+//!
+//! A question can have pictures, audio and video attached. Only a row
+//! describing each file is kept here; the file itself lives in media storage
+//! under `storage_key`, and clients get a signed URL to it.
+
+use crate::db::schema::question_media;
+use crate::models::question::Question;
+use crate::storage::MediaStore;
+use async_graphql::{ComplexObject, Context, Enum, SimpleObject};
+use chrono::{DateTime, Utc};
+use diesel::deserialize::{self, FromSql, FromSqlRow};
+use diesel::expression::AsExpression;
+use diesel::pg::{Pg, PgValue};
+use diesel::prelude::*;
+use diesel::serialize::{self, Output, ToSql};
+use diesel::sql_types::Text;
+use diesel_async::{AsyncPgConnection, RunQueryDsl};
+use std::time::Duration;
+
+/// How long a signed media URL stays valid.
+pub const SIGNED_URL_TTL: Duration = Duration::from_secs(60 * 60);
+
+/// What kind of media a file is.
+#[derive(Enum, Copy, Clone, Debug, PartialEq, Eq, AsExpression, FromSqlRow)]
+#[diesel(sql_type = Text)]
+pub enum MediaKind {
+    Image,
+    Audio,
+    Video,
+}
+
+impl MediaKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MediaKind::Image => "image",
+            MediaKind::Audio => "audio",
+            MediaKind::Video => "video",
+        }
+    }
+}
+
+impl ToSql<Text, Pg> for MediaKind {
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Pg>) -> serialize::Result {
+        <str as ToSql<Text, Pg>>::to_sql(self.as_str(), out)
+    }
+}
+
+impl FromSql<Text, Pg> for MediaKind {
+    fn from_sql(bytes: PgValue<'_>) -> deserialize::Result<Self> {
+        match <String as FromSql<Text, Pg>>::from_sql(bytes)?.as_str() {
+            "image" => Ok(MediaKind::Image),
+            "audio" => Ok(MediaKind::Audio),
+            "video" => Ok(MediaKind::Video),
+            other => Err(format!("Unknown media kind: {}", other).into()),
+        }
+    }
+}
+
+/// Represents a media file attached to a question.
+#[derive(Identifiable, Associations, Queryable, Selectable, Debug, SimpleObject, Clone)]
+#[graphql(complex)]
+#[diesel(table_name = question_media)]
+#[diesel(belongs_to(Question))]
+pub struct QuestionMedia {
+    pub id: i64,
+    pub created_at: DateTime<Utc>,
+    pub question_id: i64,
+    pub media_kind: MediaKind,
+    pub content_type: String,
+    /// The name of the file as it was uploaded.
+    pub file_name: String,
+    pub size_bytes: i64,
+    #[graphql(skip)]
+    pub storage_key: String,
+}
+
+#[ComplexObject]
+impl QuestionMedia {
+    /// A link to the file that expires after an hour
+    async fn url(&self, ctx: &Context<'_>) -> async_graphql::Result<String> {
+        let storage = ctx.data::<MediaStore>().map_err(|e| {
+            async_graphql::Error::new(format!("Cannot get MediaStore from context: {:?}", e))
+        })?;
+        Ok(storage.signed_url(&self.storage_key, SIGNED_URL_TTL)?)
+    }
+}
+
+/// Represents a new media file to be inserted into the database.
+#[derive(Debug, Insertable)]
+#[diesel(table_name = question_media)]
+pub struct NewQuestionMedia {
+    pub question_id: i64,
+    pub media_kind: MediaKind,
+    pub content_type: String,
+    pub file_name: String,
+    pub size_bytes: i64,
+    pub storage_key: String,
+}
+
+impl QuestionMedia {
+    /// Find a media file by its unique ID.
+    ///
+    /// # Arguments
+    /// * `conn` - A mutable reference to an async PostgreSQL connection.
+    /// * `media_id` - The unique identifier of the media file.
+    ///
+    /// # Returns
+    /// A `Result` containing the media file or a Diesel error.
+    pub async fn find_by_id(
+        conn: &mut AsyncPgConnection,
+        media_id: i64,
+    ) -> Result<Self, diesel::result::Error> {
+        question_media::table.find(media_id).first(conn).await
+    }
+
+    /// Fetch the media attached to a question, oldest first.
+    ///
+    /// # Arguments
+    /// * `conn` - A mutable reference to an async PostgreSQL connection.
+    /// * `question_id` - The unique identifier of the question.
+    ///
+    /// # Returns
+    /// A `Result` containing a vector of media files or a Diesel error.
+    pub async fn fetch_by_question_id(
+        conn: &mut AsyncPgConnection,
+        question_id: i64,
+    ) -> Result<Vec<Self>, diesel::result::Error> {
+        question_media::table
+            .filter(question_media::question_id.eq(question_id))
+            .order(question_media::id)
+            .load::<Self>(conn)
+            .await
+    }
+
+    /// Insert a new media file into the database.
+    ///
+    /// # Arguments
+    /// * `conn` - A mutable reference to an async PostgreSQL connection.
+    /// * `new_media` - The media file to insert.
+    ///
+    /// # Returns
+    /// A `Result` containing the inserted media file or a Diesel error.
+    pub async fn create(
+        conn: &mut AsyncPgConnection,
+        new_media: NewQuestionMedia,
+    ) -> Result<Self, diesel::result::Error> {
+        diesel::insert_into(question_media::table)
+            .values(&new_media)
+            .get_result(conn)
+            .await
+    }
+
+    /// Delete a media file from the database.
+    ///
+    /// # Arguments
+    /// * `conn` - A mutable reference to an async PostgreSQL connection.
+    /// * `media_id` - The unique identifier of the media file.
+    ///
+    /// # Returns
+    /// A `Result` containing the deleted media file or a Diesel error.
+    pub async fn delete(
+        conn: &mut AsyncPgConnection,
+        media_id: i64,
+    ) -> Result<Self, diesel::result::Error> {
+        diesel::delete(question_media::table.find(media_id))
+            .get_result(conn)
+            .await
+    }
+}
@@ -0,0 +1,133 @@
+// models/question_translation.rs
+//! This module contains the `QuestionTranslation` struct and related logic.
+//!
+//! A question is written in one locale (`Question::locale`) and can be
+//! translated into others, one translation per locale.
+
+use crate::db::schema::question_translations;
+use crate::models::question::Question;
+use async_graphql::SimpleObject;
+use chrono::{DateTime, Utc};
+use diesel::prelude::*;
+use diesel::upsert::excluded;
+use diesel_async::{AsyncPgConnection, RunQueryDsl};
+
+/// Represents a question's clue and answer in another locale.
+#[derive(Identifiable, Associations, Queryable, Selectable, Debug, SimpleObject, Clone)]
+#[diesel(table_name = question_translations)]
+#[diesel(belongs_to(Question))]
+pub struct QuestionTranslation {
+    pub id: i64,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub question_id: i64,
+    pub locale: String,
+    /// The translated clue, as Markdown.
+    pub question: String,
+    /// The translated answer, as Markdown. Without one the original answer
+    /// is used, which suits names and places.
+    pub answer: Option<String>,
+}
+
+/// Represents a translation to be inserted or replaced.
+#[derive(Debug, Insertable, Clone)]
+#[diesel(table_name = question_translations)]
+pub struct NewQuestionTranslation {
+    pub question_id: i64,
+    pub locale: String,
+    pub question: String,
+    pub answer: Option<String>,
+}
+
+impl QuestionTranslation {
+    /// Fetch every translation of a question.
+    ///
+    /// # Arguments
+    /// * `conn` - A mutable reference to an async PostgreSQL connection.
+    /// * `question_id` - The unique identifier of the question.
+    ///
+    /// # Returns
+    /// A `Result` containing a vector of translations or a Diesel error.
+    pub async fn fetch_by_question_id(
+        conn: &mut AsyncPgConnection,
+        question_id: i64,
+    ) -> Result<Vec<Self>, diesel::result::Error> {
+        question_translations::table
+            .filter(question_translations::question_id.eq(question_id))
+            .order(question_translations::locale)
+            .load::<Self>(conn)
+            .await
+    }
+
+    /// Fetch the translations of several questions into any of `locales`.
+    ///
+    /// # Arguments
+    /// * `conn` - A mutable reference to an async PostgreSQL connection.
+    /// * `question_ids` - The unique identifiers of the questions.
+    /// * `locales` - The locales wanted.
+    ///
+    /// # Returns
+    /// A `Result` containing a vector of translations or a Diesel error.
+    pub async fn fetch_for_questions(
+        conn: &mut AsyncPgConnection,
+        question_ids: &[i64],
+        locales: &[String],
+    ) -> Result<Vec<Self>, diesel::result::Error> {
+        question_translations::table
+            .filter(question_translations::question_id.eq_any(question_ids))
+            .filter(question_translations::locale.eq_any(locales))
+            .load::<Self>(conn)
+            .await
+    }
+
+    /// Insert a translation, replacing any existing one for the same question
+    /// and locale.
+    ///
+    /// # Arguments
+    /// * `conn` - A mutable reference to an async PostgreSQL connection.
+    /// * `translation` - The translation to store.
+    ///
+    /// # Returns
+    /// A `Result` containing the stored translation or a Diesel error.
+    pub async fn upsert(
+        conn: &mut AsyncPgConnection,
+        translation: NewQuestionTranslation,
+    ) -> Result<Self, diesel::result::Error> {
+        diesel::insert_into(question_translations::table)
+            .values(&translation)
+            .on_conflict((
+                question_translations::question_id,
+                question_translations::locale,
+            ))
+            .do_update()
+            .set((
+                question_translations::question.eq(excluded(question_translations::question)),
+                question_translations::answer.eq(excluded(question_translations::answer)),
+            ))
+            .get_result(conn)
+            .await
+    }
+
+    /// Delete a question's translation into a locale.
+    ///
+    /// # Arguments
+    /// * `conn` - A mutable reference to an async PostgreSQL connection.
+    /// * `question_id` - The unique identifier of the question.
+    /// * `locale` - The locale of the translation.
+    ///
+    /// # Returns
+    /// A `Result` containing the number of rows deleted or a Diesel error.
+    pub async fn delete(
+        conn: &mut AsyncPgConnection,
+        question_id: i64,
+        locale: &str,
+    ) -> Result<usize, diesel::result::Error> {
+        diesel::delete(
+            question_translations::table
+                .filter(question_translations::question_id.eq(question_id))
+                .filter(question_translations::locale.eq(locale)),
+        )
+        .execute(conn)
+        .await
+    }
+}
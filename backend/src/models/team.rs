@@ -0,0 +1,282 @@
+// models/team.rs
+//! This module contains the `Team` and `TeamMember` structs and related logic.
+//!
+//! In team play a team holds the score and buzzes in, while each correct
+//! answer is credited to the member who gave it. Members are registered
+//! users or named guests.
+
+use crate::db::schema::{team_members, teams};
+use crate::models::game::Game;
+use crate::models::user::User;
+use async_graphql::SimpleObject;
+use chrono::{DateTime, Utc};
+use derive_builder::Builder;
+use diesel::prelude::*;
+use diesel_async::{AsyncPgConnection, RunQueryDsl};
+
+/// Represents a team competing in a game.
+#[derive(
+    Identifiable, Associations, Queryable, Selectable, Debug, SimpleObject, Builder, Clone,
+)]
+#[diesel(table_name = teams)]
+#[diesel(belongs_to(Game))]
+pub struct Team {
+    pub id: i64,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub game_id: i64,
+    pub team_name: String,
+    pub score: i32,
+}
+
+/// Represents a new team to be inserted into the database.
+#[derive(Debug, Insertable, Builder)]
+#[diesel(table_name = teams)]
+pub struct NewTeam {
+    pub game_id: i64,
+    pub team_name: String,
+}
+
+/// Represents the fields to update in an existing team record.
+#[derive(Debug, AsChangeset)]
+#[diesel(table_name = teams)]
+pub struct UpdateTeam {
+    pub team_name: Option<String>,
+    pub score: Option<i32>,
+}
+
+/// Represents a member of a team.
+#[derive(
+    Identifiable, Associations, Queryable, Selectable, Debug, SimpleObject, Builder, Clone,
+)]
+#[diesel(table_name = team_members)]
+#[diesel(belongs_to(Team))]
+#[diesel(belongs_to(User))]
+pub struct TeamMember {
+    pub id: i64,
+    pub created_at: DateTime<Utc>,
+    pub team_id: i64,
+    /// The registered user, or `None` for a guest.
+    pub user_id: Option<i64>,
+    /// The name shown on the scoreboard.
+    pub member_name: String,
+    /// Sent with a remote member's buzzes to prove who they are. Only the
+    /// host can read it.
+    #[graphql(skip)]
+    pub member_token: String,
+}
+
+/// Represents a new team member to be inserted into the database.
+#[derive(Debug, Insertable, Builder)]
+#[diesel(table_name = team_members)]
+pub struct NewTeamMember {
+    pub team_id: i64,
+    pub user_id: Option<i64>,
+    pub member_name: String,
+}
+
+impl Team {
+    /// Find a team by its unique ID.
+    ///
+    /// # Arguments
+    /// * `conn` - A mutable reference to an async PostgreSQL connection.
+    /// * `team_id` - The unique identifier of the team to fetch.
+    ///
+    /// # Returns
+    /// A `Result` containing the team or a Diesel error.
+    pub async fn find_by_id(
+        conn: &mut AsyncPgConnection,
+        team_id: i64,
+    ) -> Result<Self, diesel::result::Error> {
+        teams::table.find(team_id).first(conn).await
+    }
+
+    /// Fetch all teams of a game, in the order they were created.
+    ///
+    /// # Arguments
+    /// * `conn` - A mutable reference to an async PostgreSQL connection.
+    /// * `game_id` - The unique identifier of the game.
+    ///
+    /// # Returns
+    /// A `Result` containing a vector of teams or a Diesel error.
+    pub async fn fetch_by_game_id(
+        conn: &mut AsyncPgConnection,
+        game_id: i64,
+    ) -> Result<Vec<Self>, diesel::result::Error> {
+        teams::table
+            .filter(teams::game_id.eq(game_id))
+            .order(teams::id)
+            .load::<Self>(conn)
+            .await
+    }
+
+    /// Create a new team in the database.
+    ///
+    /// # Arguments
+    /// * `conn` - A mutable reference to an async PostgreSQL connection.
+    /// * `new_team` - A `NewTeam` instance containing the team's data.
+    ///
+    /// # Returns
+    /// A `Result` containing the newly created team or a Diesel error.
+    pub async fn create(
+        conn: &mut AsyncPgConnection,
+        new_team: NewTeam,
+    ) -> Result<Self, diesel::result::Error> {
+        diesel::insert_into(teams::table)
+            .values(&new_team)
+            .get_result(conn)
+            .await
+    }
+
+    /// Update an existing team record.
+    ///
+    /// # Arguments
+    /// * `conn` - A mutable reference to an async PostgreSQL connection.
+    /// * `team_id` - The unique identifier of the team to update.
+    /// * `updated_fields` - An `UpdateTeam` instance containing the updated fields.
+    ///
+    /// # Returns
+    /// A `Result` containing the updated team or a Diesel error.
+    pub async fn update_team(
+        conn: &mut AsyncPgConnection,
+        team_id: i64,
+        updated_fields: UpdateTeam,
+    ) -> Result<Self, diesel::result::Error> {
+        diesel::update(teams::table.find(team_id))
+            .set((&updated_fields, teams::updated_at.eq(Utc::now())))
+            .get_result(conn)
+            .await
+    }
+
+    /// Add `delta` (which may be negative) to a team's score.
+    ///
+    /// # Arguments
+    /// * `conn` - A mutable reference to an async PostgreSQL connection.
+    /// * `team_id` - The unique identifier of the team.
+    /// * `delta` - The points to add.
+    ///
+    /// # Returns
+    /// A `Result` containing the updated team or a Diesel error.
+    pub async fn adjust_score(
+        conn: &mut AsyncPgConnection,
+        team_id: i64,
+        delta: i32,
+    ) -> Result<Self, diesel::result::Error> {
+        diesel::update(teams::table.find(team_id))
+            .set((
+                teams::score.eq(teams::score + delta),
+                teams::updated_at.eq(Utc::now()),
+            ))
+            .get_result(conn)
+            .await
+    }
+
+    /// Delete a team, and by cascade its members, buzzes and answers.
+    ///
+    /// # Arguments
+    /// * `conn` - A mutable reference to an async PostgreSQL connection.
+    /// * `team_id` - The unique identifier of the team to delete.
+    ///
+    /// # Returns
+    /// A `Result` containing the deleted team or a Diesel error.
+    pub async fn delete(
+        conn: &mut AsyncPgConnection,
+        team_id: i64,
+    ) -> Result<Self, diesel::result::Error> {
+        diesel::delete(teams::table.find(team_id))
+            .get_result(conn)
+            .await
+    }
+}
+
+impl TeamMember {
+    /// Find a team member by their unique ID.
+    ///
+    /// # Arguments
+    /// * `conn` - A mutable reference to an async PostgreSQL connection.
+    /// * `member_id` - The unique identifier of the member to fetch.
+    ///
+    /// # Returns
+    /// A `Result` containing the member or a Diesel error.
+    pub async fn find_by_id(
+        conn: &mut AsyncPgConnection,
+        member_id: i64,
+    ) -> Result<Self, diesel::result::Error> {
+        team_members::table.find(member_id).first(conn).await
+    }
+
+    /// Fetch all members of a team, in the order they joined.
+    ///
+    /// # Arguments
+    /// * `conn` - A mutable reference to an async PostgreSQL connection.
+    /// * `team_id` - The unique identifier of the team.
+    ///
+    /// # Returns
+    /// A `Result` containing a vector of members or a Diesel error.
+    pub async fn fetch_by_team_id(
+        conn: &mut AsyncPgConnection,
+        team_id: i64,
+    ) -> Result<Vec<Self>, diesel::result::Error> {
+        team_members::table
+            .filter(team_members::team_id.eq(team_id))
+            .order(team_members::id)
+            .load::<Self>(conn)
+            .await
+    }
+
+    /// Fetch the members of every team in a game.
+    ///
+    /// # Arguments
+    /// * `conn` - A mutable reference to an async PostgreSQL connection.
+    /// * `game_id` - The unique identifier of the game.
+    ///
+    /// # Returns
+    /// A `Result` containing a vector of members or a Diesel error.
+    pub async fn fetch_by_game_id(
+        conn: &mut AsyncPgConnection,
+        game_id: i64,
+    ) -> Result<Vec<Self>, diesel::result::Error> {
+        team_members::table
+            .inner_join(teams::table)
+            .filter(teams::game_id.eq(game_id))
+            .order(team_members::id)
+            .select(TeamMember::as_select())
+            .load::<Self>(conn)
+            .await
+    }
+
+    /// Create a new team member in the database.
+    ///
+    /// # Arguments
+    /// * `conn` - A mutable reference to an async PostgreSQL connection.
+    /// * `new_member` - A `NewTeamMember` instance containing the member's data.
+    ///
+    /// # Returns
+    /// A `Result` containing the newly created member or a Diesel error.
+    pub async fn create(
+        conn: &mut AsyncPgConnection,
+        new_member: NewTeamMember,
+    ) -> Result<Self, diesel::result::Error> {
+        diesel::insert_into(team_members::table)
+            .values(&new_member)
+            .get_result(conn)
+            .await
+    }
+
+    /// Delete a team member. Their past answers stay credited to the team.
+    ///
+    /// # Arguments
+    /// * `conn` - A mutable reference to an async PostgreSQL connection.
+    /// * `member_id` - The unique identifier of the member to delete.
+    ///
+    /// # Returns
+    /// A `Result` containing the deleted member or a Diesel error.
+    pub async fn delete(
+        conn: &mut AsyncPgConnection,
+        member_id: i64,
+    ) -> Result<Self, diesel::result::Error> {
+        diesel::delete(team_members::table.find(member_id))
+            .get_result(conn)
+            .await
+    }
+}
@@ -0,0 +1,183 @@
+// models/team_answer.rs
+//! This module contains the `TeamBuzz` and `TeamAnswer` structs and related
+//! logic: the order teams buzzed in on a question, and the host's ruling on
+//! each team's answer.
+
+use crate::db::schema::{team_answers, team_buzzes};
+use crate::models::game_question::GameQuestion;
+use crate::models::team::Team;
+use async_graphql::SimpleObject;
+use chrono::{DateTime, Utc};
+use diesel::prelude::*;
+use diesel_async::{AsyncPgConnection, RunQueryDsl};
+
+/// Represents a team buzzing in on a question.
+#[derive(Identifiable, Associations, Queryable, Selectable, Debug, SimpleObject, Clone)]
+#[diesel(table_name = team_buzzes)]
+#[diesel(belongs_to(GameQuestion))]
+#[diesel(belongs_to(Team))]
+pub struct TeamBuzz {
+    pub id: i64,
+    pub buzzed_at: DateTime<Utc>,
+    pub game_question_id: i64,
+    pub team_id: i64,
+    /// The member who pressed the buzzer, if known.
+    pub member_id: Option<i64>,
+}
+
+/// Represents a new buzz to be inserted into the database.
+#[derive(Debug, Insertable)]
+#[diesel(table_name = team_buzzes)]
+pub struct NewTeamBuzz {
+    pub game_question_id: i64,
+    pub team_id: i64,
+    pub member_id: Option<i64>,
+}
+
+/// Represents the host's ruling on a team's answer.
+#[derive(Identifiable, Associations, Queryable, Selectable, Debug, SimpleObject, Clone)]
+#[diesel(table_name = team_answers)]
+#[diesel(belongs_to(GameQuestion))]
+#[diesel(belongs_to(Team))]
+pub struct TeamAnswer {
+    pub id: i64,
+    pub created_at: DateTime<Utc>,
+    pub game_question_id: i64,
+    pub team_id: i64,
+    /// The member credited with the answer, if known.
+    pub member_id: Option<i64>,
+    pub correct: bool,
+    /// Points added to (or, when negative, taken from) the team's score.
+    pub points_awarded: i32,
+}
+
+/// Represents a new ruling to be inserted into the database.
+#[derive(Debug, Insertable)]
+#[diesel(table_name = team_answers)]
+pub struct NewTeamAnswer {
+    pub game_question_id: i64,
+    pub team_id: i64,
+    pub member_id: Option<i64>,
+    pub correct: bool,
+    pub points_awarded: i32,
+}
+
+impl TeamBuzz {
+    /// Record a team buzzing in.
+    ///
+    /// # Arguments
+    /// * `conn` - A mutable reference to an async PostgreSQL connection.
+    /// * `new_buzz` - A `NewTeamBuzz` instance containing the buzz's data.
+    ///
+    /// # Returns
+    /// A `Result` containing the new buzz, or a unique violation if the team
+    /// already buzzed on this question.
+    pub async fn create(
+        conn: &mut AsyncPgConnection,
+        new_buzz: NewTeamBuzz,
+    ) -> Result<Self, diesel::result::Error> {
+        diesel::insert_into(team_buzzes::table)
+            .values(&new_buzz)
+            .get_result(conn)
+            .await
+    }
+
+    /// Find a team's buzz on a question.
+    ///
+    /// # Arguments
+    /// * `conn` - A mutable reference to an async PostgreSQL connection.
+    /// * `game_question_id` - The unique identifier of the game question.
+    /// * `team_id` - The unique identifier of the team.
+    ///
+    /// # Returns
+    /// A `Result` containing the buzz or a Diesel error.
+    pub async fn find(
+        conn: &mut AsyncPgConnection,
+        game_question_id: i64,
+        team_id: i64,
+    ) -> Result<Self, diesel::result::Error> {
+        team_buzzes::table
+            .filter(team_buzzes::game_question_id.eq(game_question_id))
+            .filter(team_buzzes::team_id.eq(team_id))
+            .first(conn)
+            .await
+    }
+
+    /// Fetch the buzzes on a question, first buzz first.
+    ///
+    /// # Arguments
+    /// * `conn` - A mutable reference to an async PostgreSQL connection.
+    /// * `game_question_id` - The unique identifier of the game question.
+    ///
+    /// # Returns
+    /// A `Result` containing a vector of buzzes or a Diesel error.
+    pub async fn fetch_by_game_question_id(
+        conn: &mut AsyncPgConnection,
+        game_question_id: i64,
+    ) -> Result<Vec<Self>, diesel::result::Error> {
+        team_buzzes::table
+            .filter(team_buzzes::game_question_id.eq(game_question_id))
+            .order((team_buzzes::buzzed_at, team_buzzes::id))
+            .load::<Self>(conn)
+            .await
+    }
+}
+
+impl TeamAnswer {
+    /// Record the host's ruling on a team's answer.
+    ///
+    /// # Arguments
+    /// * `conn` - A mutable reference to an async PostgreSQL connection.
+    /// * `new_answer` - A `NewTeamAnswer` instance containing the ruling.
+    ///
+    /// # Returns
+    /// A `Result` containing the new ruling, or a unique violation if the
+    /// team was already judged on this question.
+    pub async fn create(
+        conn: &mut AsyncPgConnection,
+        new_answer: NewTeamAnswer,
+    ) -> Result<Self, diesel::result::Error> {
+        diesel::insert_into(team_answers::table)
+            .values(&new_answer)
+            .get_result(conn)
+            .await
+    }
+
+    /// Fetch the rulings on a question.
+    ///
+    /// # Arguments
+    /// * `conn` - A mutable reference to an async PostgreSQL connection.
+    /// * `game_question_id` - The unique identifier of the game question.
+    ///
+    /// # Returns
+    /// A `Result` containing a vector of rulings or a Diesel error.
+    pub async fn fetch_by_game_question_id(
+        conn: &mut AsyncPgConnection,
+        game_question_id: i64,
+    ) -> Result<Vec<Self>, diesel::result::Error> {
+        team_answers::table
+            .filter(team_answers::game_question_id.eq(game_question_id))
+            .order(team_answers::id)
+            .load::<Self>(conn)
+            .await
+    }
+
+    /// Fetch every ruling given to the teams of a game.
+    ///
+    /// # Arguments
+    /// * `conn` - A mutable reference to an async PostgreSQL connection.
+    /// * `team_ids` - The teams of the game.
+    ///
+    /// # Returns
+    /// A `Result` containing a vector of rulings or a Diesel error.
+    pub async fn fetch_by_team_ids(
+        conn: &mut AsyncPgConnection,
+        team_ids: &[i64],
+    ) -> Result<Vec<Self>, diesel::result::Error> {
+        team_answers::table
+            .filter(team_answers::team_id.eq_any(team_ids))
+            .order(team_answers::id)
+            .load::<Self>(conn)
+            .await
+    }
+}
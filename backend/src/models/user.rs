@@ -23,6 +23,7 @@ use diesel_async::{AsyncPgConnection, RunQueryDsl};
 ///     updated_at: Utc::now(),
 ///     username: String::from("johndoe"),
 ///     firebase_uid: String::from("123456"),
+///     is_moderator: false,
 /// };
 ///
 /// println!("{:?}", user);
@@ -40,6 +41,8 @@ pub struct User {
     pub username: String,
     /// The Firebase UID of the user.
     pub firebase_uid: String,
+    /// Whether the user may review reported game boards.
+    pub is_moderator: bool,
 }
 
 /// Represents a new user to be inserted into the database.
@@ -144,4 +147,43 @@ impl User {
 
         Ok(user)
     }
+
+    /// Permanently delete a user.
+    ///
+    /// Everything the user owns (boards, questions, games) is removed by cascade.
+    ///
+    /// # Arguments
+    /// * `conn` - A mutable reference to an async PostgreSQL connection.
+    /// * `user_id` - The unique identifier of the user to delete.
+    ///
+    /// # Returns
+    /// A `Result` containing the number of rows affected or a Diesel error.
+    pub async fn delete(
+        conn: &mut AsyncPgConnection,
+        user_id: i64,
+    ) -> Result<usize, diesel::result::Error> {
+        diesel::delete(users::table.find(user_id))
+            .execute(conn)
+            .await
+    }
+
+    /// Grant or revoke moderator rights.
+    ///
+    /// # Arguments
+    /// * `conn` - A mutable reference to an async PostgreSQL connection.
+    /// * `user_id` - The unique identifier of the user.
+    /// * `is_moderator` - Whether the user may review reported game boards.
+    ///
+    /// # Returns
+    /// A `Result` containing the updated user or a Diesel error.
+    pub async fn set_moderator(
+        conn: &mut AsyncPgConnection,
+        user_id: i64,
+        is_moderator: bool,
+    ) -> Result<Self, diesel::result::Error> {
+        diesel::update(users::table.find(user_id))
+            .set(users::is_moderator.eq(is_moderator))
+            .get_result(conn)
+            .await
+    }
 }
@@ -0,0 +1,354 @@
+// src/services/admin.rs
+//! Operations behind the `jeopardy-admin` CLI: database statistics, finding
+//! abandoned games, and moving boards between databases as JSON.
+
+use crate::db::schema::game_boards;
+use crate::graphql::validation::{
+    Validate, Validator, GRID_COLS, MAX_ANSWER_LENGTH, MAX_CATEGORY_LENGTH, MAX_POINTS,
+    MAX_QUESTION_LENGTH, MAX_TITLE_LENGTH,
+};
+use crate::models::game::Game;
+use crate::models::game_board::GameBoard;
+use crate::models::game_board_question_mapping::{
+    GameBoardQuestionMapping, NewGameBoardQuestionMapping,
+};
+use crate::models::game_board_revision::GameBoardRevision;
+use crate::models::question::{NewQuestion, Question};
+use chrono::{DateTime, Utc};
+use diesel::prelude::*;
+use diesel::sql_types::{BigInt, Timestamptz};
+use diesel::QueryableByName;
+use diesel_async::scoped_futures::ScopedFutureExt;
+use diesel_async::{AsyncConnection, AsyncPgConnection, RunQueryDsl};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Row counts and the size of the database.
+#[derive(QueryableByName, Clone, Debug, PartialEq, Eq)]
+pub struct DatabaseStats {
+    #[diesel(sql_type = BigInt)]
+    pub users: i64,
+    #[diesel(sql_type = BigInt)]
+    pub game_boards: i64,
+    #[diesel(sql_type = BigInt)]
+    pub archived_game_boards: i64,
+    #[diesel(sql_type = BigInt)]
+    pub public_game_boards: i64,
+    #[diesel(sql_type = BigInt)]
+    pub questions: i64,
+    #[diesel(sql_type = BigInt)]
+    pub media_files: i64,
+    #[diesel(sql_type = BigInt)]
+    pub media_bytes: i64,
+    #[diesel(sql_type = BigInt)]
+    pub games: i64,
+    #[diesel(sql_type = BigInt)]
+    pub players: i64,
+    #[diesel(sql_type = BigInt)]
+    pub game_events: i64,
+    #[diesel(sql_type = BigInt)]
+    pub database_bytes: i64,
+}
+
+impl fmt::Display for DatabaseStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "users          {}", self.users)?;
+        writeln!(
+            f,
+            "game boards    {} ({} archived, {} public)",
+            self.game_boards, self.archived_game_boards, self.public_game_boards
+        )?;
+        writeln!(f, "questions      {}", self.questions)?;
+        writeln!(
+            f,
+            "media files    {} ({} MiB)",
+            self.media_files,
+            self.media_bytes / (1024 * 1024)
+        )?;
+        writeln!(f, "games          {}", self.games)?;
+        writeln!(f, "players        {}", self.players)?;
+        writeln!(f, "game events    {}", self.game_events)?;
+        write!(
+            f,
+            "database size  {} MiB",
+            self.database_bytes / (1024 * 1024)
+        )
+    }
+}
+
+/// Count what the database holds.
+///
+/// # Arguments
+/// * `conn` - A mutable reference to an async PostgreSQL connection.
+///
+/// # Returns
+/// A `Result` containing the statistics or a Diesel error.
+pub async fn database_stats(
+    conn: &mut AsyncPgConnection,
+) -> Result<DatabaseStats, diesel::result::Error> {
+    diesel::sql_query(
+        "SELECT
+            (SELECT COUNT(*) FROM users) AS users,
+            (SELECT COUNT(*) FROM game_boards) AS game_boards,
+            (SELECT COUNT(*) FROM game_boards WHERE archived_at IS NOT NULL) AS archived_game_boards,
+            (SELECT COUNT(*) FROM game_boards WHERE visibility = 'public') AS public_game_boards,
+            (SELECT COUNT(*) FROM questions) AS questions,
+            (SELECT COUNT(*) FROM question_media) AS media_files,
+            (SELECT COALESCE(SUM(size_bytes), 0)::BIGINT FROM question_media) AS media_bytes,
+            (SELECT COUNT(*) FROM games) AS games,
+            (SELECT COUNT(*) FROM players) AS players,
+            (SELECT COUNT(*) FROM game_events) AS game_events,
+            pg_database_size(current_database()) AS database_bytes",
+    )
+    .get_result(conn)
+    .await
+}
+
+/// A game nobody has touched in a while.
+#[derive(QueryableByName, Clone, Debug, PartialEq, Eq)]
+pub struct AbandonedGame {
+    #[diesel(sql_type = BigInt)]
+    pub id: i64,
+    #[diesel(sql_type = BigInt)]
+    pub user_id: i64,
+    #[diesel(sql_type = Timestamptz)]
+    pub last_activity: DateTime<Utc>,
+    /// How many cells were played before play stopped.
+    #[diesel(sql_type = BigInt)]
+    pub cells_played: i64,
+    #[diesel(sql_type = BigInt)]
+    pub cells: i64,
+}
+
+/// Find games left unfinished: nothing has happened in them since
+/// `idle_since` and some of their cells were never played, whether opened by
+/// the host or buzzed on by a team.
+///
+/// # Arguments
+/// * `conn` - A mutable reference to an async PostgreSQL connection.
+/// * `idle_since` - Games with activity after this are kept.
+///
+/// # Returns
+/// A `Result` containing the abandoned games, oldest first, or a Diesel error.
+pub async fn abandoned_games(
+    conn: &mut AsyncPgConnection,
+    idle_since: DateTime<Utc>,
+) -> Result<Vec<AbandonedGame>, diesel::result::Error> {
+    diesel::sql_query(
+        "SELECT * FROM (
+            SELECT g.id, g.user_id,
+                GREATEST(g.updated_at,
+                    COALESCE((SELECT MAX(e.created_at) FROM game_events e WHERE e.game_id = g.id),
+                             g.updated_at)) AS last_activity,
+                (SELECT COUNT(*) FROM game_questions q
+                  WHERE q.game_id = g.id AND q.played_at IS NOT NULL) AS cells_played,
+                (SELECT COUNT(*) FROM game_questions q WHERE q.game_id = g.id) AS cells
+            FROM games g
+         ) AS activity
+         WHERE last_activity < $1 AND cells_played < cells
+         ORDER BY last_activity, id",
+    )
+    .bind::<Timestamptz, _>(idle_since)
+    .load(conn)
+    .await
+}
+
+/// Delete abandoned games, with their players, responses and events.
+///
+/// # Arguments
+/// * `conn` - A mutable reference to an async PostgreSQL connection.
+/// * `games` - The games to delete, as found by `abandoned_games`.
+///
+/// # Returns
+/// A `Result` containing the number of games deleted or a Diesel error.
+pub async fn purge_games(
+    conn: &mut AsyncPgConnection,
+    games: &[AbandonedGame],
+) -> Result<usize, diesel::result::Error> {
+    let mut purged = 0;
+    for game in games {
+        purged += Game::purge(conn, game.id).await?;
+    }
+    Ok(purged)
+}
+
+/// A game board and its questions, as written by `export_board`.
+///
+/// Only the board's text is exported: media attached to questions, board and
+/// question translations, and the board's visibility are left behind.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct BoardExport {
+    pub title: String,
+    /// One per column; a column without a name is a blank string.
+    pub categories: Vec<String>,
+    pub locale: String,
+    pub cells: Vec<ExportedCell>,
+}
+
+/// One occupied cell of an exported board, with its question.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct ExportedCell {
+    pub grid_row: i32,
+    pub grid_col: i32,
+    pub points: i32,
+    pub daily_double: bool,
+    pub question: String,
+    pub answer: String,
+    #[serde(default)]
+    pub alternate_answers: Vec<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub difficulty: Option<i32>,
+}
+
+impl Validate for BoardExport {
+    fn validate(&self, v: &mut Validator) {
+        v.non_blank("title", &self.title)
+            .max_length("title", &self.title, MAX_TITLE_LENGTH);
+        v.locale("locale", &self.locale);
+        if self.categories.len() != GRID_COLS as usize {
+            v.add("categories", format!("must have {} categories", GRID_COLS));
+        }
+        v.each("categories", &self.categories, |v, field, category| {
+            v.max_length(field, category, MAX_CATEGORY_LENGTH);
+        });
+        v.each("cells", &self.cells, |v, field, cell| {
+            v.grid_row(&format!("{}.gridRow", field), cell.grid_row)
+                .grid_col(&format!("{}.gridCol", field), cell.grid_col)
+                .range(&format!("{}.points", field), cell.points, 0, MAX_POINTS);
+            let question = format!("{}.question", field);
+            v.non_blank(&question, &cell.question)
+                .max_length(&question, &cell.question, MAX_QUESTION_LENGTH)
+                .markdown(&question, &cell.question);
+            let answer = format!("{}.answer", field);
+            v.non_blank(&answer, &cell.answer)
+                .max_length(&answer, &cell.answer, MAX_ANSWER_LENGTH)
+                .markdown(&answer, &cell.answer);
+            if let Some(difficulty) = cell.difficulty {
+                v.difficulty(&format!("{}.difficulty", field), difficulty);
+            }
+        });
+        let mut cells: Vec<(i32, i32)> = self
+            .cells
+            .iter()
+            .map(|c| (c.grid_row, c.grid_col))
+            .collect();
+        cells.sort();
+        if cells.windows(2).any(|pair| pair[0] == pair[1]) {
+            v.add("cells", "must not put two questions in the same cell");
+        }
+    }
+}
+
+/// Write a game board and its questions out as a `BoardExport`.
+///
+/// # Arguments
+/// * `conn` - A mutable reference to an async PostgreSQL connection.
+/// * `game_board_id` - The unique identifier of the game board.
+///
+/// # Returns
+/// A `Result` containing the export or a Diesel error.
+pub async fn export_board(
+    conn: &mut AsyncPgConnection,
+    game_board_id: i64,
+) -> Result<BoardExport, diesel::result::Error> {
+    let board = GameBoard::find_by_id(conn, game_board_id).await?;
+    let mut mappings =
+        GameBoardQuestionMapping::fetch_mappings_by_board_id(conn, game_board_id).await?;
+    mappings.sort_by_key(|m| (m.grid_col, m.grid_row));
+
+    let mut cells = Vec::with_capacity(mappings.len());
+    for mapping in mappings {
+        let question = Question::find_by_id(conn, mapping.question_id).await?;
+        cells.push(ExportedCell {
+            grid_row: mapping.grid_row,
+            grid_col: mapping.grid_col,
+            points: mapping.points,
+            daily_double: mapping.daily_double,
+            question: question.question,
+            answer: question.answer,
+            alternate_answers: question.alternate_answers,
+            tags: question.tags,
+            difficulty: question.difficulty,
+        });
+    }
+
+    Ok(BoardExport {
+        title: board.title,
+        categories: (0..GRID_COLS as usize)
+            .map(|col| {
+                board
+                    .categories
+                    .get(col)
+                    .cloned()
+                    .flatten()
+                    .unwrap_or_default()
+            })
+            .collect(),
+        locale: board.locale,
+        cells,
+    })
+}
+
+/// Create a game board, and a question for each of its cells, from an
+/// export, in a single transaction. Validate the export first.
+///
+/// # Arguments
+/// * `conn` - A mutable reference to an async PostgreSQL connection.
+/// * `user_id` - The unique identifier of the user who will own the board.
+/// * `export` - The board to create.
+///
+/// # Returns
+/// A `Result` containing the new game board or a Diesel error.
+pub async fn import_board(
+    conn: &mut AsyncPgConnection,
+    user_id: i64,
+    export: BoardExport,
+) -> Result<GameBoard, diesel::result::Error> {
+    conn.transaction::<_, diesel::result::Error, _>(|conn| {
+        async move {
+            let board: GameBoard = diesel::insert_into(game_boards::table)
+                .values((
+                    game_boards::user_id.eq(user_id),
+                    game_boards::title.eq(export.title),
+                    game_boards::categories.eq(export.categories),
+                    game_boards::locale.eq(export.locale),
+                ))
+                .get_result(conn)
+                .await?;
+
+            for cell in export.cells {
+                let question = Question::create(
+                    conn,
+                    NewQuestion {
+                        user_id,
+                        question: cell.question,
+                        answer: cell.answer,
+                        alternate_answers: cell.alternate_answers,
+                        tags: cell.tags,
+                        difficulty: cell.difficulty,
+                    },
+                )
+                .await?;
+                GameBoardQuestionMapping::create_mapping(
+                    conn,
+                    NewGameBoardQuestionMapping {
+                        board_id: board.id,
+                        question_id: question.id,
+                        daily_double: cell.daily_double,
+                        points: cell.points,
+                        grid_row: cell.grid_row,
+                        grid_col: cell.grid_col,
+                    },
+                )
+                .await?;
+            }
+
+            GameBoardRevision::record(conn, board.id).await?;
+            Ok(board)
+        }
+        .scope_boxed()
+    })
+    .await
+}
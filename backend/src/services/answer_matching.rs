@@ -0,0 +1,186 @@
+// src/services/answer_matching.rs
+//! Help hosts judge free-text responses.
+//!
+//! A response is compared with a question's answer and its alternate answers
+//! after normalization: case, punctuation, a leading "what is"/"who is" and
+//! articles are ignored. The closest accepted answer gives a confidence
+//! between 0 and 1 based on edit distance, which maps to a suggested verdict.
+//! The host always has the final say.
+
+use async_graphql::{Enum, SimpleObject};
+
+/// Responses at least this similar to an accepted answer are suggested
+/// correct.
+pub const ACCEPT_THRESHOLD: f64 = 0.8;
+/// Responses less similar than this to every accepted answer are suggested
+/// incorrect. Anything in between is left to the host.
+pub const REJECT_THRESHOLD: f64 = 0.5;
+
+const QUESTION_WORDS: [&str; 5] = ["what", "who", "where", "when", "which"];
+const QUESTION_VERBS: [&str; 4] = ["is", "are", "was", "were"];
+const ARTICLES: [&str; 3] = ["a", "an", "the"];
+
+/// The verdict suggested to the host.
+#[derive(Enum, Copy, Clone, Debug, PartialEq, Eq)]
+pub enum MatchVerdict {
+    Correct,
+    /// Close enough that the host should decide.
+    Unsure,
+    Incorrect,
+}
+
+/// How well a response matches a question's accepted answers.
+#[derive(SimpleObject, Clone, Debug, PartialEq)]
+pub struct AnswerMatch {
+    /// Similarity to the closest accepted answer, from 0 to 1.
+    pub confidence: f64,
+    pub verdict: MatchVerdict,
+    /// The accepted answer the response came closest to.
+    pub matched_answer: Option<String>,
+    /// The response as it was compared.
+    pub normalized_response: String,
+}
+
+/// Reduce a response or answer to the words that matter: lowercase,
+/// without punctuation, a leading "what is"-style phrase or articles.
+///
+/// # Arguments
+/// * `text` - The text to normalize.
+///
+/// # Returns
+/// The normalized words joined by single spaces.
+pub fn normalize_answer(text: &str) -> String {
+    let cleaned: String = text
+        .to_lowercase()
+        .replace('&', " and ")
+        .chars()
+        .filter(|c| *c != '\'' && *c != '\u{2019}')
+        .map(|c| if c.is_alphanumeric() { c } else { ' ' })
+        .collect();
+
+    let mut words: Vec<&str> = cleaned.split_whitespace().collect();
+    if words.len() > 2 && QUESTION_WORDS.contains(&words[0]) && QUESTION_VERBS.contains(&words[1]) {
+        words.drain(..2);
+    } else if words.len() > 1 && (words[0] == "whats" || words[0] == "whos") {
+        words.remove(0);
+    }
+
+    words
+        .into_iter()
+        .filter(|w| !ARTICLES.contains(w))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// The Levenshtein distance between two strings, counted in characters.
+///
+/// # Arguments
+/// * `a` - The first string.
+/// * `b` - The second string.
+///
+/// # Returns
+/// The number of single-character insertions, deletions and substitutions
+/// needed to turn `a` into `b`.
+pub fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    let mut current = vec![0; b.len() + 1];
+
+    for (i, ca) in a.chars().enumerate() {
+        current[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != *cb);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+    previous[b.len()]
+}
+
+/// Similarity of two normalized strings, from 0 (nothing in common) to 1
+/// (identical). Numbers must match exactly, so "1984" is not close to "1985".
+fn similarity(response: &str, answer: &str) -> f64 {
+    let digits = |s: &str| s.chars().filter(char::is_ascii_digit).collect::<String>();
+    if digits(response) != digits(answer) {
+        return 0.0;
+    }
+
+    let longest = response.chars().count().max(answer.chars().count());
+    if longest == 0 {
+        return 0.0;
+    }
+    1.0 - edit_distance(response, answer) as f64 / longest as f64
+}
+
+/// The forms of an answer a response may match: the answer itself and, when
+/// it has parenthesized optional parts like "(John) Adams", the answer
+/// without them.
+fn accepted_forms(answer: &str) -> Vec<String> {
+    let mut forms = vec![normalize_answer(answer)];
+    if answer.contains('(') {
+        let mut depth = 0usize;
+        let required: String = answer
+            .chars()
+            .filter(|c| match c {
+                '(' => {
+                    depth += 1;
+                    false
+                }
+                ')' => {
+                    depth = depth.saturating_sub(1);
+                    false
+                }
+                _ => depth == 0,
+            })
+            .collect();
+        let required = normalize_answer(&required);
+        if !required.is_empty() && !forms.contains(&required) {
+            forms.push(required);
+        }
+    }
+    forms
+}
+
+/// Compare a response with a question's answer and alternate answers.
+///
+/// # Arguments
+/// * `response` - What the player said or typed.
+/// * `answer` - The question's answer.
+/// * `alternate_answers` - Other responses to accept.
+///
+/// # Returns
+/// The confidence, suggested verdict and closest accepted answer.
+pub fn match_answer(response: &str, answer: &str, alternate_answers: &[String]) -> AnswerMatch {
+    let normalized_response = normalize_answer(response);
+
+    let mut best: Option<(f64, &str)> = None;
+    if !normalized_response.is_empty() {
+        for accepted in std::iter::once(answer).chain(alternate_answers.iter().map(String::as_str))
+        {
+            for form in accepted_forms(accepted) {
+                let score = similarity(&normalized_response, &form);
+                if best.is_none_or(|(top, _)| score > top) {
+                    best = Some((score, accepted));
+                }
+            }
+        }
+    }
+
+    let confidence = best.map_or(0.0, |(score, _)| score);
+    let verdict = if confidence >= ACCEPT_THRESHOLD {
+        MatchVerdict::Correct
+    } else if confidence >= REJECT_THRESHOLD {
+        MatchVerdict::Unsure
+    } else {
+        MatchVerdict::Incorrect
+    };
+
+    AnswerMatch {
+        confidence,
+        verdict,
+        matched_answer: best
+            .filter(|(score, _)| *score > 0.0)
+            .map(|(_, accepted)| accepted.to_string()),
+        normalized_response,
+    }
+}
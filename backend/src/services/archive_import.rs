@@ -0,0 +1,739 @@
+// src/services/archive_import.rs
+//! Importing archived episodes from J-Archive.
+//!
+//! Two kinds of local file are understood:
+//!
+//! * the community TSV dumps: one clue per line, under a header naming the
+//!   columns (`round`, `clue_value`, `daily_double_value`, `category`,
+//!   `answer`, `question`, `air_date`). As on the show, `answer` holds the
+//!   clue and `question` the correct response.
+//! * J-Archive game pages (`showgame.php`) saved as HTML, in both the older
+//!   layout that hides responses in `onmouseover` and the current one.
+//!
+//! Each round of an episode becomes a game board. The show has six
+//! categories to our five columns, so clues in a sixth category go into the
+//! library without a cell. Clues already in the importing user's library are
+//! reused rather than duplicated, and rounds whose board the user already
+//! has are skipped, so importing the same files twice changes nothing.
+
+use crate::graphql::validation::{GRID_COLS, GRID_ROWS};
+use crate::models::game_board::{GameBoard, NewGameBoard, UpdateGameBoard};
+use crate::models::game_board_question_mapping::{
+    GameBoardQuestionMapping, NewGameBoardQuestionMapping,
+};
+use crate::models::game_board_revision::GameBoardRevision;
+use crate::models::question::{NewQuestion, Question};
+use crate::services::board_generator::normalize_tag;
+use crate::services::rich_text::escape_markdown;
+use diesel_async::scoped_futures::ScopedFutureExt;
+use diesel_async::{AsyncConnection, AsyncPgConnection};
+use regex::Regex;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+
+static TITLE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?s)<title>(.*?)</title>").unwrap());
+static SHOW_NUMBER: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"Show #(\d+)").unwrap());
+static AIR_DATE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\d{4}-\d{2}-\d{2}").unwrap());
+static CATEGORY_NAME: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"(?s)<td class="category_name">(.*?)</td>"#).unwrap());
+static CLUE_TEXT: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"(?s)<td[^>]*\bid="clue_(?:J|DJ)_(\d+)_(\d+)"[^>]*>(.*?)</td>"#).unwrap()
+});
+static FINAL_CLUE_TEXT: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"(?s)<td[^>]*\bid="clue_FJ"[^>]*>(.*?)</td>"#).unwrap());
+static CLUE_VALUE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"(?s)class="clue_value(_daily_double)?"[^>]*>(.*?)</td>"#).unwrap()
+});
+static CORRECT_RESPONSE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"(?s)<em class="correct_response">(.*?)</em>"#).unwrap());
+static LINE_BREAK: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?i)<br\s*/?>").unwrap());
+static TAG: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?s)<[^>]*>").unwrap());
+
+/// A round of an episode.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ArchiveRound {
+    Jeopardy,
+    DoubleJeopardy,
+    FinalJeopardy,
+}
+
+impl ArchiveRound {
+    pub fn title(&self) -> &'static str {
+        match self {
+            ArchiveRound::Jeopardy => "Jeopardy!",
+            ArchiveRound::DoubleJeopardy => "Double Jeopardy!",
+            ArchiveRound::FinalJeopardy => "Final Jeopardy!",
+        }
+    }
+
+    /// The top row's value, for rounds whose values were not recorded.
+    fn default_unit(&self) -> i32 {
+        match self {
+            ArchiveRound::Jeopardy => 200,
+            ArchiveRound::DoubleJeopardy => 400,
+            ArchiveRound::FinalJeopardy => 0,
+        }
+    }
+
+    /// Read a round as written in a TSV dump: `1` to `3`, or its name.
+    fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().trim_end_matches('!') {
+            "1" | "j" | "jeopardy" => Some(ArchiveRound::Jeopardy),
+            "2" | "dj" | "double jeopardy" => Some(ArchiveRound::DoubleJeopardy),
+            "3" | "fj" | "final jeopardy" => Some(ArchiveRound::FinalJeopardy),
+            _ => None,
+        }
+    }
+}
+
+/// A clue as it was played.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ArchivedClue {
+    pub round: ArchiveRound,
+    pub category: String,
+    /// The clue as read to the contestants.
+    pub clue: String,
+    pub response: String,
+    /// The cell's value on the board, when the file records it. A Daily
+    /// Double's wager is not its value.
+    pub value: Option<i32>,
+    pub daily_double: bool,
+    /// The zero-based column and row on the show's board, when known.
+    pub position: Option<(usize, usize)>,
+}
+
+/// An episode read from an archive file.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ArchivedEpisode {
+    pub show_number: Option<i32>,
+    pub air_date: Option<String>,
+    /// Each round's categories in board order.
+    pub categories: BTreeMap<ArchiveRound, Vec<String>>,
+    pub clues: Vec<ArchivedClue>,
+}
+
+impl ArchivedEpisode {
+    /// The title of the board made from one of the episode's rounds.
+    pub fn board_title(&self, round: ArchiveRound) -> String {
+        let episode = match (self.show_number, &self.air_date) {
+            (Some(number), Some(date)) => format!("Show #{} ({})", number, date),
+            (Some(number), None) => format!("Show #{}", number),
+            (None, Some(date)) => format!("Episode of {}", date),
+            (None, None) => "Archived episode".to_string(),
+        };
+        format!("{}: {}", episode, round.title())
+    }
+
+    fn add_category(&mut self, round: ArchiveRound, category: &str) {
+        let categories = self.categories.entry(round).or_default();
+        if !categories.iter().any(|c| c == category) {
+            categories.push(category.to_string());
+        }
+    }
+}
+
+/// What an import did.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ArchiveImportSummary {
+    pub episodes: usize,
+    pub boards_created: usize,
+    /// Rounds whose board the user already had.
+    pub boards_skipped: usize,
+    pub questions_created: usize,
+    /// Clues that were already in the library.
+    pub questions_reused: usize,
+    /// Clues added to the library that have no cell on their board.
+    pub clues_unplaced: usize,
+}
+
+impl fmt::Display for ArchiveImportSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} episodes: {} boards created, {} already imported; {} questions created, {} reused, {} without a cell",
+            self.episodes,
+            self.boards_created,
+            self.boards_skipped,
+            self.questions_created,
+            self.questions_reused,
+            self.clues_unplaced
+        )
+    }
+}
+
+/// Why an import failed.
+#[derive(Debug)]
+pub enum ImportError {
+    Io {
+        path: PathBuf,
+        error: std::io::Error,
+    },
+    /// The file is neither `.tsv` nor `.html`.
+    UnknownFormat(PathBuf),
+    Parse(String),
+    Database(diesel::result::Error),
+}
+
+impl fmt::Display for ImportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ImportError::Io { path, error } => write!(f, "{}: {}", path.display(), error),
+            ImportError::UnknownFormat(path) => write!(
+                f,
+                "{}: expected a .tsv dump or a saved .html game page",
+                path.display()
+            ),
+            ImportError::Parse(message) => write!(f, "{}", message),
+            ImportError::Database(e) => write!(f, "Database error: {:?}", e),
+        }
+    }
+}
+
+impl std::error::Error for ImportError {}
+
+impl From<diesel::result::Error> for ImportError {
+    fn from(e: diesel::result::Error) -> Self {
+        ImportError::Database(e)
+    }
+}
+
+/// Collapse runs of whitespace and trim.
+fn plain(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Decode the HTML entities J-Archive uses.
+fn decode_entities(text: &str) -> String {
+    let mut decoded = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(at) = rest.find('&') {
+        decoded.push_str(&rest[..at]);
+        rest = &rest[at..];
+        let entity = rest[1..]
+            .find(';')
+            .filter(|end| *end <= 10)
+            .map(|end| &rest[1..=end]);
+        let c = entity.and_then(|entity| match entity {
+            "amp" => Some('&'),
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            "nbsp" => Some(' '),
+            _ => entity
+                .strip_prefix("#x")
+                .or_else(|| entity.strip_prefix("#X"))
+                .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+                .or_else(|| entity.strip_prefix('#').and_then(|dec| dec.parse().ok()))
+                .and_then(char::from_u32),
+        });
+        match (c, entity) {
+            (Some(c), Some(entity)) => {
+                decoded.push(c);
+                rest = &rest[entity.len() + 2..];
+            }
+            _ => {
+                decoded.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    decoded.push_str(rest);
+    decoded
+}
+
+/// The text of an HTML fragment.
+fn html_text(html: &str) -> String {
+    let text = LINE_BREAK.replace_all(html, " ");
+    plain(&decode_entities(&TAG.replace_all(&text, "")))
+}
+
+/// A dollar amount such as `$1,000`, or nothing for blank or zero.
+fn parse_value(value: &str) -> Option<i32> {
+    let digits: String = value.chars().filter(char::is_ascii_digit).collect();
+    digits.parse().ok().filter(|v| *v > 0)
+}
+
+/// A TSV field, without the quotes some dumps put around it.
+fn tsv_field(field: &str) -> String {
+    let field = field.trim();
+    match field
+        .strip_prefix('"')
+        .and_then(|inner| inner.strip_suffix('"'))
+    {
+        Some(inner) => inner.replace("\"\"", "\""),
+        None => field.to_string(),
+    }
+}
+
+/// Parse a community TSV dump, which may hold many episodes.
+///
+/// # Arguments
+/// * `text` - The contents of the dump.
+///
+/// # Returns
+/// The episodes in the order they first appear, or what is wrong with the file.
+pub fn parse_tsv(text: &str) -> Result<Vec<ArchivedEpisode>, ImportError> {
+    let mut lines = text
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty());
+    let (_, header) = lines
+        .next()
+        .ok_or_else(|| ImportError::Parse("the file is empty".to_string()))?;
+    let header: Vec<String> = header
+        .split('\t')
+        .map(|name| tsv_field(name).to_lowercase())
+        .collect();
+    let column = |names: &[&str]| header.iter().position(|h| names.contains(&h.as_str()));
+    let required = |names: &[&str]| {
+        column(names).ok_or_else(|| ImportError::Parse(format!("missing a {} column", names[0])))
+    };
+    let round_col = required(&["round"])?;
+    let value_col = required(&["clue_value", "value"])?;
+    let category_col = required(&["category"])?;
+    let clue_col = required(&["answer", "clue"])?;
+    let response_col = required(&["question", "response", "correct_response"])?;
+    let daily_double_col = column(&["daily_double_value", "daily_double"]);
+    let air_date_col = column(&["air_date"]);
+    let show_number_col = column(&["show_number"]);
+
+    let mut episodes: Vec<ArchivedEpisode> = Vec::new();
+    let mut episode_index: HashMap<(Option<i32>, Option<String>), usize> = HashMap::new();
+    for (i, line) in lines {
+        let fields: Vec<String> = line.split('\t').map(tsv_field).collect();
+        let field = |col: usize| fields.get(col).map(String::as_str).unwrap_or("");
+
+        let round = ArchiveRound::parse(field(round_col)).ok_or_else(|| {
+            ImportError::Parse(format!(
+                "line {}: unknown round \"{}\"",
+                i + 1,
+                field(round_col)
+            ))
+        })?;
+        let daily_double = daily_double_col.is_some_and(|col| {
+            let flag = field(col).to_lowercase();
+            parse_value(&flag).is_some() || flag == "yes" || flag == "true"
+        });
+        let category = plain(field(category_col));
+        let clue = plain(field(clue_col));
+        let response = plain(field(response_col));
+        // Clues never revealed on air have no text
+        if category.is_empty() || clue.is_empty() || response.is_empty() {
+            continue;
+        }
+
+        let show_number = show_number_col.and_then(|col| field(col).parse().ok());
+        let air_date = air_date_col
+            .map(|col| field(col).to_string())
+            .filter(|date| !date.is_empty());
+        let index = *episode_index
+            .entry((show_number, air_date.clone()))
+            .or_insert_with(|| {
+                episodes.push(ArchivedEpisode {
+                    show_number,
+                    air_date,
+                    ..Default::default()
+                });
+                episodes.len() - 1
+            });
+        let episode = &mut episodes[index];
+        episode.add_category(round, &category);
+        episode.clues.push(ArchivedClue {
+            round,
+            category,
+            clue,
+            response,
+            value: parse_value(field(value_col)),
+            daily_double,
+            position: None,
+        });
+    }
+    Ok(episodes)
+}
+
+/// The correct response in a fragment of a game page. Older pages keep it,
+/// escaped, in an `onmouseover` script.
+fn correct_response(html: &str) -> Option<String> {
+    let decoded = decode_entities(html)
+        .replace("\\'", "'")
+        .replace("\\\"", "\"");
+    CORRECT_RESPONSE
+        .captures(&decoded)
+        .map(|caps| html_text(&caps[1]))
+        .filter(|response| !response.is_empty())
+}
+
+/// Parse a J-Archive game page.
+///
+/// # Arguments
+/// * `html` - The saved page.
+///
+/// # Returns
+/// The episode, or what is wrong with the page.
+pub fn parse_html(html: &str) -> Result<ArchivedEpisode, ImportError> {
+    let mut episode = ArchivedEpisode::default();
+    if let Some(title) = TITLE.captures(html).map(|caps| html_text(&caps[1])) {
+        episode.show_number = SHOW_NUMBER
+            .captures(&title)
+            .and_then(|caps| caps[1].parse().ok());
+        episode.air_date = AIR_DATE.find(&title).map(|date| date.as_str().to_string());
+    }
+
+    let mut sections: Vec<(usize, ArchiveRound)> = [
+        ("id=\"jeopardy_round\"", ArchiveRound::Jeopardy),
+        ("id=\"double_jeopardy_round\"", ArchiveRound::DoubleJeopardy),
+        ("id=\"final_jeopardy_round\"", ArchiveRound::FinalJeopardy),
+    ]
+    .into_iter()
+    .filter_map(|(marker, round)| html.find(marker).map(|at| (at, round)))
+    .collect();
+    sections.sort();
+
+    for (i, (start, round)) in sections.iter().enumerate() {
+        let end = sections.get(i + 1).map_or(html.len(), |(at, _)| *at);
+        let section = &html[*start..end];
+        let categories: Vec<String> = CATEGORY_NAME
+            .captures_iter(section)
+            .map(|caps| html_text(&caps[1]))
+            .collect();
+
+        if *round == ArchiveRound::FinalJeopardy {
+            let category = categories.first().cloned().unwrap_or_default();
+            let clue = FINAL_CLUE_TEXT
+                .captures(section)
+                .map(|caps| html_text(&caps[1]));
+            if let (Some(clue), Some(response)) = (clue, correct_response(section)) {
+                episode.add_category(*round, &category);
+                episode.clues.push(ArchivedClue {
+                    round: *round,
+                    category,
+                    clue,
+                    response,
+                    value: None,
+                    daily_double: false,
+                    position: Some((0, 0)),
+                });
+            }
+            continue;
+        }
+
+        for category in &categories {
+            episode.add_category(*round, category);
+        }
+        for cell in section.split("<td class=\"clue\">").skip(1) {
+            // Clues never revealed on air have no text
+            let Some(caps) = CLUE_TEXT.captures(cell) else {
+                continue;
+            };
+            let col: usize = caps[1].parse().unwrap_or(0);
+            let row: usize = caps[2].parse().unwrap_or(0);
+            let clue = html_text(&caps[3]);
+            let (Some(response), Some(category)) = (
+                correct_response(cell),
+                col.checked_sub(1).and_then(|c| categories.get(c)),
+            ) else {
+                continue;
+            };
+            if clue.is_empty() || row == 0 {
+                continue;
+            }
+            let value = CLUE_VALUE.captures(cell);
+            let daily_double = value.as_ref().is_some_and(|caps| caps.get(1).is_some());
+            episode.clues.push(ArchivedClue {
+                round: *round,
+                category: category.clone(),
+                clue,
+                response,
+                value: value
+                    .filter(|_| !daily_double)
+                    .and_then(|caps| parse_value(&caps[2])),
+                daily_double,
+                position: Some((col - 1, row - 1)),
+            });
+        }
+    }
+
+    if episode.clues.is_empty() {
+        return Err(ImportError::Parse(
+            "no clues found; expected a J-Archive game page".to_string(),
+        ));
+    }
+    Ok(episode)
+}
+
+/// Read and parse an archive file, choosing the parser by extension.
+///
+/// # Arguments
+/// * `path` - A `.tsv` dump or a saved `.html` game page.
+///
+/// # Returns
+/// The episodes in the file, or why it could not be read.
+pub async fn read_archive_file(path: &Path) -> Result<Vec<ArchivedEpisode>, ImportError> {
+    let extension = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(str::to_lowercase);
+    let is_tsv = match extension.as_deref() {
+        Some("tsv") => true,
+        Some("html" | "htm") => false,
+        _ => return Err(ImportError::UnknownFormat(path.to_path_buf())),
+    };
+    let bytes = tokio::fs::read(path)
+        .await
+        .map_err(|error| ImportError::Io {
+            path: path.to_path_buf(),
+            error,
+        })?;
+    let text = String::from_utf8_lossy(&bytes);
+    let parsed = if is_tsv {
+        parse_tsv(&text)
+    } else {
+        parse_html(&text).map(|episode| vec![episode])
+    };
+    parsed.map_err(|e| ImportError::Parse(format!("{}: {}", path.display(), e)))
+}
+
+/// Where a round's clues go: each clue's zero-based row and column, if it
+/// has a cell.
+pub fn layout_round(
+    round: ArchiveRound,
+    categories: &[String],
+    clues: &[&ArchivedClue],
+) -> Vec<Option<(usize, usize)>> {
+    let mut cells = vec![None; clues.len()];
+    if round == ArchiveRound::FinalJeopardy {
+        if let Some(cell) = cells.first_mut() {
+            *cell = Some((0, 0));
+        }
+        return cells;
+    }
+
+    let unit = round_unit(round, clues);
+    let columns = categories.len().min(GRID_COLS as usize);
+    let rows = GRID_ROWS as usize;
+    let cols: Vec<Option<usize>> = clues
+        .iter()
+        .map(|clue| {
+            clue.position
+                .map(|(col, _)| col)
+                .or_else(|| categories.iter().position(|c| *c == clue.category))
+                .filter(|col| *col < columns)
+        })
+        .collect();
+
+    // Clues whose row is known, from the page or from their value, first
+    let mut taken: HashSet<(usize, usize)> = HashSet::new();
+    for (i, clue) in clues.iter().enumerate() {
+        let Some(col) = cols[i] else {
+            continue;
+        };
+        let row = clue.position.map(|(_, row)| row).or_else(|| {
+            clue.value
+                .filter(|value| value % unit == 0)
+                .map(|value| (value / unit - 1) as usize)
+        });
+        if let Some(row) = row.filter(|row| *row < rows) {
+            if taken.insert((row, col)) {
+                cells[i] = Some((row, col));
+            }
+        }
+    }
+    // Then the rest, such as Daily Doubles, into their column's free rows
+    for (i, col) in cols.iter().enumerate() {
+        let Some(col) = *col else {
+            continue;
+        };
+        if cells[i].is_none() {
+            if let Some(row) = (0..rows).find(|row| !taken.contains(&(*row, col))) {
+                taken.insert((row, col));
+                cells[i] = Some((row, col));
+            }
+        }
+    }
+    cells
+}
+
+/// The value of a round's top row: its smallest regular clue value.
+fn round_unit(round: ArchiveRound, clues: &[&ArchivedClue]) -> i32 {
+    clues
+        .iter()
+        .filter(|clue| !clue.daily_double)
+        .filter_map(|clue| clue.value)
+        .min()
+        .unwrap_or_else(|| round.default_unit())
+}
+
+/// Questions compare by their text, ignoring case and spacing.
+fn library_key(question: &str, answer: &str) -> (String, String) {
+    (plain(question).to_lowercase(), plain(answer).to_lowercase())
+}
+
+/// Import parsed episodes into a user's library, one board per round.
+///
+/// Clues already in the library are reused, except that a board holds a
+/// question only once: a clue repeated within a round gets a question of its
+/// own for every cell after the first.
+///
+/// # Arguments
+/// * `conn` - A mutable reference to an async PostgreSQL connection.
+/// * `user_id` - The unique identifier of the user who will own the boards.
+/// * `episodes` - The episodes to import.
+///
+/// # Returns
+/// A `Result` containing what was imported or a Diesel error.
+pub async fn import_episodes(
+    conn: &mut AsyncPgConnection,
+    user_id: i64,
+    episodes: &[ArchivedEpisode],
+) -> Result<ArchiveImportSummary, diesel::result::Error> {
+    let mut summary = ArchiveImportSummary {
+        episodes: episodes.len(),
+        ..Default::default()
+    };
+    let mut library: HashMap<(String, String), i64> = Question::fetch_by_user(conn, user_id)
+        .await?
+        .into_iter()
+        .map(|q| (library_key(&q.question, &q.answer), q.id))
+        .collect();
+    let mut existing: HashSet<String> = GameBoard::fetch_by_user(conn, user_id)
+        .await?
+        .into_iter()
+        .map(|board| board.title)
+        .collect();
+
+    for episode in episodes {
+        for (round, categories) in &episode.categories {
+            let title = episode.board_title(*round);
+            let clues: Vec<&ArchivedClue> = episode
+                .clues
+                .iter()
+                .filter(|clue| clue.round == *round)
+                .collect();
+            if clues.is_empty() {
+                continue;
+            }
+            if !existing.insert(title.clone()) {
+                summary.boards_skipped += 1;
+                continue;
+            }
+            let cells = layout_round(*round, categories, &clues);
+            let unit = round_unit(*round, &clues);
+            let library = &mut library;
+            let summary = &mut summary;
+
+            conn.transaction::<_, diesel::result::Error, _>(|conn| {
+                async move {
+                    let board = GameBoard::create(conn, NewGameBoard { user_id, title }).await?;
+                    let board_categories: Vec<String> = (0..GRID_COLS as usize)
+                        .map(|col| {
+                            categories.get(col).cloned().unwrap_or_else(|| {
+                                board
+                                    .categories
+                                    .get(col)
+                                    .cloned()
+                                    .flatten()
+                                    .unwrap_or_default()
+                            })
+                        })
+                        .collect();
+                    GameBoard::update_game_board(
+                        conn,
+                        board.id,
+                        UpdateGameBoard {
+                            title: None,
+                            categories: Some(board_categories),
+                            locale: None,
+                        },
+                    )
+                    .await?;
+
+                    let mut placed: HashSet<i64> = HashSet::new();
+                    for (clue, cell) in clues.iter().zip(cells) {
+                        let question = escape_markdown(&clue.clue);
+                        let answer = escape_markdown(&clue.response);
+                        let key = library_key(&question, &answer);
+                        let reusable = library
+                            .get(&key)
+                            .copied()
+                            .filter(|id| cell.is_none() || !placed.contains(id));
+                        let question_id = match reusable {
+                            Some(id) => {
+                                summary.questions_reused += 1;
+                                id
+                            }
+                            None => {
+                                let created = Question::create(
+                                    conn,
+                                    NewQuestion {
+                                        user_id,
+                                        question,
+                                        answer,
+                                        alternate_answers: Vec::new(),
+                                        tags: vec![normalize_tag(&clue.category)],
+                                        difficulty: cell
+                                            .filter(|_| *round != ArchiveRound::FinalJeopardy)
+                                            .map(|(row, _)| row as i32 + 1),
+                                    },
+                                )
+                                .await?;
+                                summary.questions_created += 1;
+                                library.entry(key).or_insert(created.id);
+                                created.id
+                            }
+                        };
+
+                        let Some((row, col)) = cell else {
+                            summary.clues_unplaced += 1;
+                            continue;
+                        };
+                        GameBoardQuestionMapping::create_mapping(
+                            conn,
+                            NewGameBoardQuestionMapping {
+                                board_id: board.id,
+                                question_id,
+                                daily_double: clue.daily_double,
+                                points: unit * (row as i32 + 1),
+                                grid_row: row as i32,
+                                grid_col: col as i32,
+                            },
+                        )
+                        .await?;
+                        placed.insert(question_id);
+                    }
+
+                    GameBoardRevision::record(conn, board.id).await?;
+                    summary.boards_created += 1;
+                    Ok(())
+                }
+                .scope_boxed()
+            })
+            .await?;
+        }
+    }
+    Ok(summary)
+}
+
+/// Read archive files and import every episode in them. Nothing is stored
+/// unless every file parses.
+///
+/// # Arguments
+/// * `conn` - A mutable reference to an async PostgreSQL connection.
+/// * `user_id` - The unique identifier of the user who will own the boards.
+/// * `paths` - `.tsv` dumps and saved `.html` game pages.
+///
+/// # Returns
+/// What was imported, or why the import failed.
+pub async fn import_archive_files(
+    conn: &mut AsyncPgConnection,
+    user_id: i64,
+    paths: &[PathBuf],
+) -> Result<ArchiveImportSummary, ImportError> {
+    let mut episodes = Vec::new();
+    for path in paths {
+        episodes.extend(read_archive_file(path).await?);
+    }
+    Ok(import_episodes(conn, user_id, &episodes).await?)
+}
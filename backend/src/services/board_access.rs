@@ -0,0 +1,144 @@
+// src/services/board_access.rs
+//! Decides what a user may do with a game board.
+//!
+//! The owner may do anything. Invited collaborators may edit or view according
+//! to their role, and anyone may view a public board. Unlisted boards are
+//! only opened through their share link, which is checked where it is used.
+
+use crate::auth::firebase_auth::require_user;
+use crate::models::board_collaborator::{BoardCollaborator, CollaboratorRole};
+use crate::models::game_board::{BoardVisibility, GameBoard};
+use crate::models::user::User;
+use async_graphql::{Context, Enum, SimpleObject};
+use diesel::OptionalExtension;
+use diesel_async::AsyncPgConnection;
+
+/// Levels of access to a board, from least to most.
+#[derive(Enum, Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum BoardAccess {
+    /// See and clone the board.
+    View,
+    /// Change the board's title, categories and cells.
+    Edit,
+    /// Everything, including sharing, archiving and purging.
+    Own,
+}
+
+impl BoardAccess {
+    fn verb(&self) -> &'static str {
+        match self {
+            BoardAccess::View => "view",
+            BoardAccess::Edit => "edit",
+            BoardAccess::Own => "manage",
+        }
+    }
+}
+
+impl From<CollaboratorRole> for BoardAccess {
+    fn from(role: CollaboratorRole) -> Self {
+        match role {
+            CollaboratorRole::Editor => BoardAccess::Edit,
+            CollaboratorRole::Viewer => BoardAccess::View,
+        }
+    }
+}
+
+/// A board's sharing settings, as shown to its owner.
+#[derive(SimpleObject, Clone, Debug)]
+pub struct BoardSharing {
+    pub board_id: i64,
+    pub visibility: BoardVisibility,
+    /// Present once the board has been unlisted or public.
+    pub share_token: Option<String>,
+    pub collaborators: Vec<BoardCollaborator>,
+}
+
+/// The highest access `user_id` has to `board`, if any.
+pub async fn board_access(
+    conn: &mut AsyncPgConnection,
+    board: &GameBoard,
+    user_id: i64,
+) -> Result<Option<BoardAccess>, diesel::result::Error> {
+    if board.user_id == user_id {
+        return Ok(Some(BoardAccess::Own));
+    }
+
+    let invited = BoardCollaborator::find(conn, board.id, user_id)
+        .await
+        .optional()?
+        .map(|c| BoardAccess::from(c.role));
+    let shared = (board.visibility == BoardVisibility::Public).then_some(BoardAccess::View);
+
+    Ok(invited.max(shared))
+}
+
+/// Returns the requestor and the board, or an error unless the requestor
+/// has at least `needed` access to it.
+pub async fn require_board_access(
+    ctx: &Context<'_>,
+    conn: &mut AsyncPgConnection,
+    board_id: i64,
+    needed: BoardAccess,
+) -> Result<(User, GameBoard), async_graphql::Error> {
+    let requestor = require_user(ctx, conn).await?;
+    let board = match GameBoard::find_by_id(conn, board_id).await {
+        Ok(board) => board,
+        Err(diesel::NotFound) => return Err(async_graphql::Error::new("Gameboard not found")),
+        Err(e) => {
+            return Err(async_graphql::Error::new(format!(
+                "Database error: {:?}",
+                e
+            )))
+        }
+    };
+
+    let access = board_access(conn, &board, requestor.id).await?;
+    if access < Some(needed) {
+        tracing::info!(
+            "DENIED Requestor: {} | Game board: {} | Needed: {:?} | Has: {:?}",
+            requestor.id,
+            board.id,
+            needed,
+            access
+        );
+        return Err(async_graphql::Error::new(format!(
+            "Requestor cannot {} this game board",
+            needed.verb()
+        )));
+    }
+
+    Ok((requestor, board))
+}
+
+/// Returns the board if `share_token` is its current share link, which
+/// opens unlisted boards to anyone holding it.
+pub async fn require_share_token(
+    conn: &mut AsyncPgConnection,
+    board_id: i64,
+    share_token: &str,
+) -> Result<GameBoard, async_graphql::Error> {
+    match GameBoard::find_by_share_token(conn, share_token).await {
+        Ok(board) if board.id == board_id => Ok(board),
+        Ok(_) | Err(diesel::NotFound) => Err(async_graphql::Error::new(
+            "This share link is invalid or no longer shared",
+        )),
+        Err(e) => Err(async_graphql::Error::new(format!(
+            "Database error: {:?}",
+            e
+        ))),
+    }
+}
+
+/// Collect the sharing settings of a board.
+pub async fn board_sharing(
+    conn: &mut AsyncPgConnection,
+    board: GameBoard,
+) -> Result<BoardSharing, diesel::result::Error> {
+    let collaborators = BoardCollaborator::fetch_by_board_id(conn, board.id).await?;
+    Ok(BoardSharing {
+        board_id: board.id,
+        visibility: board.visibility,
+        share_token: board.share_token,
+        collaborators,
+    })
+}
@@ -0,0 +1,157 @@
+// src/services/board_control.rs
+//! Control of the board.
+//!
+//! The player or team in control picks the next cell. Whoever last gave a
+//! correct response gets control automatically, in the same transaction as
+//! the ruling; the host can give it to any player at any time, e.g. to pick
+//! who starts. Every change is logged as a game event and published on the
+//! `ControlHub` once it is committed.
+
+use crate::models::game::Game;
+use crate::models::game_event::{GameEvent, GameEventData, GameEventType};
+use crate::models::player::Player;
+use crate::models::team::Team;
+use async_graphql::SimpleObject;
+use diesel_async::scoped_futures::ScopedFutureExt;
+use diesel_async::{AsyncConnection, AsyncPgConnection};
+use tokio::sync::broadcast;
+
+/// Control changes kept for slow subscribers before they start missing some.
+const HUB_CAPACITY: usize = 64;
+
+/// Who has control of a game's board.
+#[derive(SimpleObject, Clone, Debug)]
+pub struct BoardControl {
+    pub game_id: i64,
+    /// The player in control, or `None` if nobody is.
+    pub player: Option<Player>,
+    /// The team in control, when teams are playing.
+    pub team: Option<Team>,
+}
+
+/// Publishes control changes to subscribed clients.
+#[derive(Clone)]
+pub struct ControlHub {
+    sender: broadcast::Sender<BoardControl>,
+}
+
+impl Default for ControlHub {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ControlHub {
+    pub fn new() -> Self {
+        let (sender, _) = broadcast::channel(HUB_CAPACITY);
+        ControlHub { sender }
+    }
+
+    /// Send a game's new control to every subscriber. Having none is fine.
+    pub fn publish(&self, control: &BoardControl) {
+        let _ = self.sender.send(control.clone());
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<BoardControl> {
+        self.sender.subscribe()
+    }
+}
+
+/// Who has control of a game's board now.
+///
+/// # Arguments
+/// * `conn` - A mutable reference to an async PostgreSQL connection.
+/// * `game_id` - The unique identifier of the game.
+///
+/// # Returns
+/// A `Result` containing the board control or a Diesel error.
+pub async fn current_control(
+    conn: &mut AsyncPgConnection,
+    game_id: i64,
+) -> Result<BoardControl, diesel::result::Error> {
+    let game = Game::find_by_id(conn, game_id).await?;
+    let player = match game.control_player_id {
+        Some(player_id) => Some(Player::find_by_id(conn, player_id).await?),
+        None => None,
+    };
+    let team = match game.control_team_id {
+        Some(team_id) => Some(Team::find_by_id(conn, team_id).await?),
+        None => None,
+    };
+    Ok(BoardControl {
+        game_id,
+        player,
+        team,
+    })
+}
+
+/// Hand control of a game's board to a player or a team, or to nobody, and
+/// log the change. It does not open a transaction of its own, so a ruling
+/// and the control it earns are committed together; publish the result once
+/// the caller's transaction has committed.
+///
+/// # Arguments
+/// * `conn` - A mutable reference to an async PostgreSQL connection.
+/// * `game_id` - The unique identifier of the game.
+/// * `player_id` - The player to give control to. It must be in the game.
+/// * `team_id` - The team to give control to. It must be in the game.
+///
+/// # Returns
+/// A `Result` containing the new board control, `None` if whoever it was
+/// handed to already had it, or a Diesel error.
+pub async fn hand_control(
+    conn: &mut AsyncPgConnection,
+    game_id: i64,
+    player_id: Option<i64>,
+    team_id: Option<i64>,
+) -> Result<Option<BoardControl>, diesel::result::Error> {
+    let game = Game::find_by_id(conn, game_id).await?;
+    if game.control_player_id == player_id && game.control_team_id == team_id {
+        return Ok(None);
+    }
+
+    Game::set_control(conn, game_id, player_id, team_id).await?;
+    GameEvent::record(
+        conn,
+        game_id,
+        GameEventType::ControlChanged,
+        GameEventData {
+            player_id,
+            team_id,
+            ..Default::default()
+        },
+    )
+    .await?;
+    Ok(Some(current_control(conn, game_id).await?))
+}
+
+/// Give control of a game's board to a player, or to nobody. Giving it to
+/// the player who already has it changes nothing.
+///
+/// # Arguments
+/// * `conn` - A mutable reference to an async PostgreSQL connection.
+/// * `hub` - Where to publish the change.
+/// * `game_id` - The unique identifier of the game.
+/// * `player_id` - The player to give control to. It must be in the game.
+///
+/// # Returns
+/// A `Result` containing the board control or a Diesel error.
+pub async fn set_control(
+    conn: &mut AsyncPgConnection,
+    hub: &ControlHub,
+    game_id: i64,
+    player_id: Option<i64>,
+) -> Result<BoardControl, diesel::result::Error> {
+    let changed = conn
+        .transaction::<_, diesel::result::Error, _>(|conn| {
+            hand_control(conn, game_id, player_id, None).scope_boxed()
+        })
+        .await?;
+    match changed {
+        Some(control) => {
+            hub.publish(&control);
+            Ok(control)
+        }
+        None => current_control(conn, game_id).await,
+    }
+}
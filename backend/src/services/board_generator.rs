@@ -0,0 +1,525 @@
+// src/services/board_generator.rs
+//! Generating a game board from a question library.
+//!
+//! Each column is a category, matched against question tags and against the
+//! categories a question has already been placed under on the requestor's
+//! or public boards. A column's questions are ordered from easiest to
+//! hardest and given ascending points. Questions the requestor has hosted in
+//! recent games are only used when a column cannot be filled without them.
+//!
+//! Every random choice comes from a generator seeded with the request's
+//! seed, so the same seed over the same library always gives the same board.
+
+use crate::db::schema::{
+    game_board_question_mappings, game_boards, game_questions, games, questions,
+};
+use crate::graphql::validation::{GRID_COLS, MAX_DIFFICULTY};
+use crate::models::game_board::{BoardVisibility, GameBoard, NewGameBoard, UpdateGameBoard};
+use crate::models::game_board_question_mapping::{
+    GameBoardQuestionMapping, NewGameBoardQuestionMapping,
+};
+use crate::models::game_board_revision::GameBoardRevision;
+use crate::models::question::Question;
+use async_graphql::{Enum, SimpleObject};
+use diesel::prelude::*;
+use diesel_async::scoped_futures::ScopedFutureExt;
+use diesel_async::{AsyncConnection, AsyncPgConnection, RunQueryDsl};
+use rand::rngs::StdRng;
+use rand::seq::{IndexedRandom, SliceRandom};
+use rand::SeedableRng;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::fmt;
+
+pub const DEFAULT_BASE_POINTS: i32 = 100;
+pub const DEFAULT_DAILY_DOUBLES: i32 = 1;
+pub const MAX_DAILY_DOUBLES: i32 = 3;
+/// How many of the requestor's latest games to avoid repeating questions from.
+pub const DEFAULT_RECENT_GAMES: i32 = 5;
+pub const MAX_RECENT_GAMES: i32 = 50;
+
+/// Whose questions the generator may use.
+#[derive(Enum, Copy, Clone, Debug, PartialEq, Eq, Default)]
+pub enum QuestionSource {
+    /// The requestor's own library.
+    #[default]
+    Mine,
+    /// Questions on public boards in the gallery.
+    Public,
+    /// Both of the above.
+    All,
+}
+
+/// What to generate.
+#[derive(Clone, Debug)]
+pub struct GeneratorOptions {
+    pub title: String,
+    /// The categories to fill, one per column. When empty, `cols` categories
+    /// are chosen from the library.
+    pub categories: Vec<String>,
+    pub rows: i32,
+    pub cols: i32,
+    pub min_difficulty: Option<i32>,
+    pub max_difficulty: Option<i32>,
+    pub source: QuestionSource,
+    /// Row `r` is worth `base_points * (r + 1)`.
+    pub base_points: i32,
+    pub daily_doubles: i32,
+    pub recent_games: i32,
+    pub seed: u64,
+}
+
+/// A generated board and the seed that reproduces it.
+#[derive(SimpleObject, Clone, Debug)]
+pub struct GeneratedGameBoard {
+    pub game_board: GameBoard,
+    pub seed: i64,
+}
+
+/// A question the generator may place.
+#[derive(Clone, Debug)]
+pub struct Candidate {
+    pub question: Question,
+    /// Normalized tags and categories the question matches.
+    pub labels: BTreeSet<String>,
+    /// Whether the question was used in one of the requestor's recent games.
+    pub recent: bool,
+}
+
+/// One cell of a planned board.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PlannedCell {
+    pub question_id: i64,
+    pub grid_row: i32,
+    pub grid_col: i32,
+    pub points: i32,
+    pub daily_double: bool,
+}
+
+/// The categories and cells of a board, before anything is stored.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BoardPlan {
+    pub categories: Vec<String>,
+    pub cells: Vec<PlannedCell>,
+}
+
+/// Why a board could not be generated.
+#[derive(Debug)]
+pub enum GeneratorError {
+    NotEnoughQuestions {
+        category: String,
+        found: usize,
+        needed: usize,
+    },
+    NotEnoughCategories {
+        found: usize,
+        needed: usize,
+    },
+    Database(diesel::result::Error),
+}
+
+impl fmt::Display for GeneratorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GeneratorError::NotEnoughQuestions {
+                category,
+                found,
+                needed,
+            } => write!(
+                f,
+                "Not enough questions for \"{}\": found {}, need {}",
+                category, found, needed
+            ),
+            GeneratorError::NotEnoughCategories { found, needed } => write!(
+                f,
+                "Only {} categories have enough questions, need {}",
+                found, needed
+            ),
+            GeneratorError::Database(e) => write!(f, "Database error: {:?}", e),
+        }
+    }
+}
+
+impl std::error::Error for GeneratorError {}
+
+impl From<diesel::result::Error> for GeneratorError {
+    fn from(e: diesel::result::Error) -> Self {
+        GeneratorError::Database(e)
+    }
+}
+
+/// Tags and categories compare trimmed and case-insensitively.
+///
+/// # Arguments
+/// * `tag` - A tag or category name.
+///
+/// # Returns
+/// The tag as stored.
+pub fn normalize_tag(tag: &str) -> String {
+    tag.split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+/// Normalize a question's tags, dropping duplicates but keeping their order.
+///
+/// # Arguments
+/// * `tags` - The tags as the client sent them.
+///
+/// # Returns
+/// The tags to store.
+pub fn normalize_tags(tags: &[String]) -> Vec<String> {
+    let mut seen = HashSet::new();
+    tags.iter()
+        .map(|tag| normalize_tag(tag))
+        .filter(|tag| seen.insert(tag.clone()))
+        .collect()
+}
+
+/// Load the questions a user may generate a board from.
+///
+/// Archived questions are left out, as are questions outside the difficulty
+/// range; unrated questions only qualify when no range is given.
+///
+/// # Arguments
+/// * `conn` - A mutable reference to an async PostgreSQL connection.
+/// * `user_id` - The unique identifier of the requestor.
+/// * `options` - The generator options.
+///
+/// # Returns
+/// A `Result` containing the candidates ordered by question id, or a Diesel error.
+pub async fn load_candidates(
+    conn: &mut AsyncPgConnection,
+    user_id: i64,
+    options: &GeneratorOptions,
+) -> Result<Vec<Candidate>, diesel::result::Error> {
+    let public_questions = game_board_question_mappings::table
+        .inner_join(game_boards::table)
+        .filter(game_boards::visibility.eq(BoardVisibility::Public))
+        .filter(game_boards::archived_at.is_null())
+        .filter(game_boards::flagged_at.is_null())
+        .select(game_board_question_mappings::question_id);
+
+    let mut query = questions::table
+        .filter(questions::archived_at.is_null())
+        .into_boxed();
+    query = match options.source {
+        QuestionSource::Mine => query.filter(questions::user_id.eq(user_id)),
+        QuestionSource::Public => query.filter(questions::id.eq_any(public_questions)),
+        QuestionSource::All => query.filter(
+            questions::user_id
+                .eq(user_id)
+                .or(questions::id.eq_any(public_questions)),
+        ),
+    };
+    if let Some(min) = options.min_difficulty {
+        query = query.filter(questions::difficulty.ge(min));
+    }
+    if let Some(max) = options.max_difficulty {
+        query = query.filter(questions::difficulty.le(max));
+    }
+    let found: Vec<Question> = query.order(questions::id).load(conn).await?;
+    let ids: Vec<i64> = found.iter().map(|q| q.id).collect();
+
+    // The categories each question sits under on boards the requestor can see
+    let placements: Vec<(i64, i32, Vec<Option<String>>)> = game_board_question_mappings::table
+        .inner_join(game_boards::table)
+        .filter(game_board_question_mappings::question_id.eq_any(&ids))
+        .filter(
+            game_boards::user_id
+                .eq(user_id)
+                .or(game_boards::visibility.eq(BoardVisibility::Public)),
+        )
+        .select((
+            game_board_question_mappings::question_id,
+            game_board_question_mappings::grid_col,
+            game_boards::categories,
+        ))
+        .load(conn)
+        .await?;
+    let mut placed_under: HashMap<i64, Vec<String>> = HashMap::new();
+    for (question_id, grid_col, categories) in placements {
+        if let Some(Some(category)) = categories.get(grid_col as usize) {
+            placed_under
+                .entry(question_id)
+                .or_default()
+                .push(normalize_tag(category));
+        }
+    }
+
+    let recent = recently_used(conn, user_id, options.recent_games).await?;
+
+    // A question the requestor has already copied is offered as the copy only
+    let copied: HashSet<i64> = found
+        .iter()
+        .filter(|q| q.user_id == user_id)
+        .filter_map(|q| q.copied_from_id)
+        .collect();
+
+    Ok(found
+        .into_iter()
+        .filter(|question| !copied.contains(&question.id))
+        .map(|question| {
+            let mut labels: BTreeSet<String> = question.tags.iter().cloned().collect();
+            labels.extend(placed_under.remove(&question.id).unwrap_or_default());
+            Candidate {
+                recent: recent.contains(&question.id)
+                    || question
+                        .copied_from_id
+                        .is_some_and(|original| recent.contains(&original)),
+                labels,
+                question,
+            }
+        })
+        .collect())
+}
+
+/// The library questions played in a user's latest games. A copy and the
+/// question it was copied from stand in for each other, so both are included.
+async fn recently_used(
+    conn: &mut AsyncPgConnection,
+    user_id: i64,
+    recent_games: i32,
+) -> Result<HashSet<i64>, diesel::result::Error> {
+    if recent_games <= 0 {
+        return Ok(HashSet::new());
+    }
+    let game_ids: Vec<i64> = games::table
+        .filter(games::user_id.eq(user_id))
+        .order((games::created_at.desc(), games::id.desc()))
+        .limit(recent_games as i64)
+        .select(games::id)
+        .load(conn)
+        .await?;
+    let used: Vec<Option<i64>> = game_questions::table
+        .filter(game_questions::game_id.eq_any(game_ids))
+        .select(game_questions::source_question_id)
+        .load(conn)
+        .await?;
+    let used: Vec<i64> = used.into_iter().flatten().collect();
+    let related: Vec<(i64, Option<i64>)> = questions::table
+        .filter(
+            questions::id
+                .eq_any(&used)
+                .or(questions::copied_from_id.eq_any(&used)),
+        )
+        .select((questions::id, questions::copied_from_id))
+        .load(conn)
+        .await?;
+
+    let mut recent: HashSet<i64> = used.into_iter().collect();
+    for (id, copied_from_id) in related {
+        recent.insert(id);
+        recent.extend(copied_from_id);
+    }
+    Ok(recent)
+}
+
+/// Fill one column with `rows` questions labelled `label`, easiest first.
+fn fill_column<'a>(
+    candidates: &'a [Candidate],
+    label: &str,
+    rows: usize,
+    used: &HashSet<i64>,
+    rng: &mut StdRng,
+) -> Result<Vec<&'a Candidate>, usize> {
+    let mut pool: Vec<&Candidate> = candidates
+        .iter()
+        .filter(|c| c.labels.contains(label) && !used.contains(&c.question.id))
+        .collect();
+    if pool.len() < rows {
+        return Err(pool.len());
+    }
+    pool.shuffle(rng);
+    // Fresh questions first; the sort is stable so the shuffle still decides ties
+    pool.sort_by_key(|c| c.recent);
+    pool.truncate(rows);
+    // Unrated questions count as middling
+    pool.sort_by_key(|c| c.question.difficulty.unwrap_or((MAX_DIFFICULTY + 1) / 2));
+    Ok(pool)
+}
+
+/// Lay out a board from the candidates. No database access, so the same
+/// candidates and options always give the same plan.
+///
+/// # Arguments
+/// * `candidates` - The questions that may be placed, ordered by id.
+/// * `options` - The generator options.
+///
+/// # Returns
+/// The planned board, or why it cannot be filled.
+pub fn plan_board(
+    candidates: &[Candidate],
+    options: &GeneratorOptions,
+) -> Result<BoardPlan, GeneratorError> {
+    let mut rng = StdRng::seed_from_u64(options.seed);
+    let rows = options.rows as usize;
+    let mut used: HashSet<i64> = HashSet::new();
+    let mut columns: Vec<(String, Vec<&Candidate>)> = Vec::new();
+
+    if options.categories.is_empty() {
+        // Any label with enough questions may become a category
+        let mut counts: BTreeMap<&str, usize> = BTreeMap::new();
+        for candidate in candidates {
+            for label in &candidate.labels {
+                *counts.entry(label.as_str()).or_default() += 1;
+            }
+        }
+        let mut labels: Vec<&str> = counts
+            .into_iter()
+            .filter(|(_, count)| *count >= rows)
+            .map(|(label, _)| label)
+            .collect();
+        labels.shuffle(&mut rng);
+        for label in labels {
+            if columns.len() == options.cols as usize {
+                break;
+            }
+            if let Ok(picked) = fill_column(candidates, label, rows, &used, &mut rng) {
+                used.extend(picked.iter().map(|c| c.question.id));
+                columns.push((label.to_string(), picked));
+            }
+        }
+        if columns.len() < options.cols as usize {
+            return Err(GeneratorError::NotEnoughCategories {
+                found: columns.len(),
+                needed: options.cols as usize,
+            });
+        }
+    } else {
+        for category in &options.categories {
+            let label = normalize_tag(category);
+            let picked =
+                fill_column(candidates, &label, rows, &used, &mut rng).map_err(|found| {
+                    GeneratorError::NotEnoughQuestions {
+                        category: category.trim().to_string(),
+                        found,
+                        needed: rows,
+                    }
+                })?;
+            used.extend(picked.iter().map(|c| c.question.id));
+            columns.push((category.trim().to_string(), picked));
+        }
+    }
+
+    // Daily Doubles stay off the cheapest row, as on the show
+    let first_row = if rows > 1 { 1 } else { 0 };
+    let eligible: Vec<(i32, i32)> = (0..columns.len() as i32)
+        .flat_map(|col| (first_row..rows as i32).map(move |row| (row, col)))
+        .collect();
+    let daily_doubles: HashSet<(i32, i32)> = eligible
+        .choose_multiple(&mut rng, options.daily_doubles.max(0) as usize)
+        .copied()
+        .collect();
+
+    let mut cells = Vec::new();
+    for (col, (_, picked)) in columns.iter().enumerate() {
+        for (row, candidate) in picked.iter().enumerate() {
+            let (grid_row, grid_col) = (row as i32, col as i32);
+            cells.push(PlannedCell {
+                question_id: candidate.question.id,
+                grid_row,
+                grid_col,
+                points: options.base_points * (grid_row + 1),
+                daily_double: daily_doubles.contains(&(grid_row, grid_col)),
+            });
+        }
+    }
+
+    Ok(BoardPlan {
+        categories: columns.into_iter().map(|(name, _)| name).collect(),
+        cells,
+    })
+}
+
+/// Generate a board for a user and store it, in a single transaction.
+///
+/// Questions from other users' libraries are copied into the user's library
+/// first, so the new board only uses questions its owner can edit. A copy the
+/// user already has is reused rather than copied again.
+///
+/// # Arguments
+/// * `conn` - A mutable reference to an async PostgreSQL connection.
+/// * `user_id` - The unique identifier of the user who will own the board.
+/// * `options` - The generator options.
+///
+/// # Returns
+/// A `Result` containing the new game board, or why it cannot be generated.
+pub async fn generate_board(
+    conn: &mut AsyncPgConnection,
+    user_id: i64,
+    options: GeneratorOptions,
+) -> Result<GameBoard, GeneratorError> {
+    let candidates = load_candidates(conn, user_id, &options).await?;
+    let plan = plan_board(&candidates, &options)?;
+    let by_id: HashMap<i64, Question> = candidates
+        .into_iter()
+        .map(|c| (c.question.id, c.question))
+        .collect();
+
+    let board = conn
+        .transaction::<_, diesel::result::Error, _>(|conn| {
+            async move {
+                let board = GameBoard::create(
+                    conn,
+                    NewGameBoard {
+                        user_id,
+                        title: options.title,
+                    },
+                )
+                .await?;
+
+                // Columns the plan leaves empty keep their default names
+                let categories: Vec<String> = (0..GRID_COLS as usize)
+                    .map(|col| {
+                        plan.categories.get(col).cloned().unwrap_or_else(|| {
+                            board
+                                .categories
+                                .get(col)
+                                .cloned()
+                                .flatten()
+                                .unwrap_or_default()
+                        })
+                    })
+                    .collect();
+                let board = GameBoard::update_game_board(
+                    conn,
+                    board.id,
+                    UpdateGameBoard {
+                        title: None,
+                        categories: Some(categories),
+                        locale: None,
+                    },
+                )
+                .await?;
+
+                for cell in plan.cells {
+                    let question = &by_id[&cell.question_id];
+                    let question_id = if question.user_id == user_id {
+                        question.id
+                    } else {
+                        Question::copy_for_user(conn, question, user_id).await?.id
+                    };
+
+                    GameBoardQuestionMapping::create_mapping(
+                        conn,
+                        NewGameBoardQuestionMapping {
+                            board_id: board.id,
+                            question_id,
+                            daily_double: cell.daily_double,
+                            points: cell.points,
+                            grid_row: cell.grid_row,
+                            grid_col: cell.grid_col,
+                        },
+                    )
+                    .await?;
+                }
+
+                GameBoardRevision::record(conn, board.id).await?;
+                Ok(board)
+            }
+            .scope_boxed()
+        })
+        .await?;
+    Ok(board)
+}
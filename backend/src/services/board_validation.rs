@@ -0,0 +1,182 @@
+// src/services/board_validation.rs
+//! Checks whether a game board is complete enough to play.
+//!
+//! The checks are pure functions over a board and its `GBQ`s so they can be
+//! reused by `validateGameBoard` and by `createGame` before a game starts.
+
+use crate::graphql::validation::{GRID_COLS, GRID_ROWS};
+use crate::models::game_board::GameBoard;
+use crate::models::GBQ;
+use async_graphql::{Enum, SimpleObject};
+use diesel_async::AsyncPgConnection;
+use std::collections::HashMap;
+
+/// The kind of problem found on a board.
+#[derive(Enum, Copy, Clone, Debug, PartialEq, Eq)]
+pub enum BoardIssueKind {
+    /// A grid position has no question.
+    EmptyCell,
+    /// Two cells in the same column are worth the same points.
+    DuplicatePoints,
+    /// A category column has no name.
+    MissingCategory,
+    /// More than one Daily Double in a round.
+    MultipleDailyDoubles,
+    /// A question has no clue text.
+    BlankQuestion,
+    /// A question has no answer.
+    BlankAnswer,
+}
+
+/// A single problem found on a board.
+#[derive(SimpleObject, Clone, Debug)]
+pub struct BoardIssue {
+    pub kind: BoardIssueKind,
+    pub message: String,
+    pub grid_row: Option<i32>,
+    pub grid_col: Option<i32>,
+    pub question_id: Option<i64>,
+}
+
+/// The result of validating a board.
+#[derive(SimpleObject, Clone, Debug)]
+pub struct BoardValidationReport {
+    pub board_id: i64,
+    /// True when the board has no issues and can be played.
+    pub ready: bool,
+    pub issues: Vec<BoardIssue>,
+}
+
+impl BoardValidationReport {
+    /// Returns true if any issue of `kind` was found.
+    pub fn has_issue(&self, kind: BoardIssueKind) -> bool {
+        self.issues.iter().any(|issue| issue.kind == kind)
+    }
+}
+
+/// Validate a board against its questions.
+pub fn validate_board(board: &GameBoard, cells: &[GBQ]) -> BoardValidationReport {
+    let mut issues = Vec::new();
+
+    // Categories
+    for col in 0..GRID_COLS {
+        let name = board.categories.get(col as usize).cloned().flatten();
+        if name.is_none_or(|n| n.trim().is_empty()) {
+            issues.push(BoardIssue {
+                kind: BoardIssueKind::MissingCategory,
+                message: format!("Category {} has no name", col + 1),
+                grid_row: None,
+                grid_col: Some(col),
+                question_id: None,
+            });
+        }
+    }
+
+    // Empty cells
+    for col in 0..GRID_COLS {
+        for row in 0..GRID_ROWS {
+            let filled = cells
+                .iter()
+                .any(|c| c.mapping.grid_row == row && c.mapping.grid_col == col);
+            if !filled {
+                issues.push(BoardIssue {
+                    kind: BoardIssueKind::EmptyCell,
+                    message: format!("Cell [{},{}] has no question", row, col),
+                    grid_row: Some(row),
+                    grid_col: Some(col),
+                    question_id: None,
+                });
+            }
+        }
+    }
+
+    // Duplicate points within a column
+    let mut seen: HashMap<(i32, i32), i32> = HashMap::new();
+    let mut sorted: Vec<&GBQ> = cells.iter().collect();
+    sorted.sort_by_key(|c| (c.mapping.grid_col, c.mapping.grid_row));
+    for cell in sorted {
+        let key = (cell.mapping.grid_col, cell.mapping.points);
+        if let Some(first_row) = seen.get(&key) {
+            issues.push(BoardIssue {
+                kind: BoardIssueKind::DuplicatePoints,
+                message: format!(
+                    "Cell [{},{}] is worth {} points, same as row {}",
+                    cell.mapping.grid_row, cell.mapping.grid_col, cell.mapping.points, first_row
+                ),
+                grid_row: Some(cell.mapping.grid_row),
+                grid_col: Some(cell.mapping.grid_col),
+                question_id: Some(cell.question.id),
+            });
+        } else {
+            seen.insert(key, cell.mapping.grid_row);
+        }
+    }
+
+    // Daily Doubles. A board is a single round.
+    let daily_doubles: Vec<&GBQ> = cells.iter().filter(|c| c.mapping.daily_double).collect();
+    if daily_doubles.len() > 1 {
+        for cell in daily_doubles.iter().skip(1) {
+            issues.push(BoardIssue {
+                kind: BoardIssueKind::MultipleDailyDoubles,
+                message: format!(
+                    "Cell [{},{}] is an extra Daily Double; a round allows one",
+                    cell.mapping.grid_row, cell.mapping.grid_col
+                ),
+                grid_row: Some(cell.mapping.grid_row),
+                grid_col: Some(cell.mapping.grid_col),
+                question_id: Some(cell.question.id),
+            });
+        }
+    }
+
+    // Question content
+    for cell in cells {
+        if cell.question.question.trim().is_empty() {
+            issues.push(BoardIssue {
+                kind: BoardIssueKind::BlankQuestion,
+                message: format!(
+                    "Cell [{},{}] has a blank clue",
+                    cell.mapping.grid_row, cell.mapping.grid_col
+                ),
+                grid_row: Some(cell.mapping.grid_row),
+                grid_col: Some(cell.mapping.grid_col),
+                question_id: Some(cell.question.id),
+            });
+        }
+        if cell.question.answer.trim().is_empty() {
+            issues.push(BoardIssue {
+                kind: BoardIssueKind::BlankAnswer,
+                message: format!(
+                    "Cell [{},{}] has a blank answer",
+                    cell.mapping.grid_row, cell.mapping.grid_col
+                ),
+                grid_row: Some(cell.mapping.grid_row),
+                grid_col: Some(cell.mapping.grid_col),
+                question_id: Some(cell.question.id),
+            });
+        }
+    }
+
+    BoardValidationReport {
+        board_id: board.id,
+        ready: issues.is_empty(),
+        issues,
+    }
+}
+
+/// Load a board with its questions and validate it.
+///
+/// # Arguments
+/// * `conn` - A mutable reference to an async PostgreSQL connection.
+/// * `game_board_id` - The unique identifier of the game board to validate.
+///
+/// # Returns
+/// A `Result` containing the validation report or a Diesel error.
+pub async fn validate_game_board(
+    conn: &mut AsyncPgConnection,
+    game_board_id: i64,
+) -> Result<BoardValidationReport, diesel::result::Error> {
+    let board = GameBoard::find_by_id(conn, game_board_id).await?;
+    let cells = GBQ::fetch_all_game_board_questions_by_board_id(conn, game_board_id).await?;
+    Ok(validate_board(&board, &cells))
+}
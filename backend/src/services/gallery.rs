@@ -0,0 +1,254 @@
+// src/services/gallery.rs
+//! The public board gallery and its moderation.
+//!
+//! Public, live boards that have not been flagged are listed with their play
+//! count, ratings and favourites. Boards reported by `REPORT_THRESHOLD` users
+//! are flagged automatically and stay hidden until a moderator reviews them.
+
+use crate::models::board_report::{BoardReport, NewBoardReport};
+use crate::models::game_board::GameBoard;
+use async_graphql::{Enum, SimpleObject};
+use chrono::{DateTime, Utc};
+use diesel::sql_types::{Array, BigInt, Float8, Nullable, Text, Timestamptz};
+use diesel::QueryableByName;
+use diesel_async::scoped_futures::ScopedFutureExt;
+use diesel_async::{AsyncConnection, AsyncPgConnection, RunQueryDsl};
+use std::collections::BTreeMap;
+
+/// Open reports from this many users hide a board until it is reviewed.
+pub const REPORT_THRESHOLD: i64 = 3;
+/// Gallery page size when none is requested.
+pub const DEFAULT_PAGE_SIZE: i32 = 20;
+/// Largest gallery page a client may request.
+pub const MAX_PAGE_SIZE: i32 = 50;
+
+/// How gallery results are ordered.
+#[derive(Enum, Copy, Clone, Debug, PartialEq, Eq, Default)]
+pub enum GallerySort {
+    /// Most played first.
+    #[default]
+    Popular,
+    /// Newest first.
+    Recent,
+    /// Highest average rating first.
+    TopRated,
+}
+
+impl GallerySort {
+    fn order_by(&self) -> &'static str {
+        match self {
+            GallerySort::Popular => "play_count DESC, favourite_count DESC, board_id DESC",
+            GallerySort::Recent => "created_at DESC, board_id DESC",
+            GallerySort::TopRated => {
+                "average_rating DESC NULLS LAST, rating_count DESC, board_id DESC"
+            }
+        }
+    }
+}
+
+/// A public board as listed in the gallery.
+#[derive(QueryableByName, SimpleObject, Clone, Debug)]
+pub struct GalleryEntry {
+    #[diesel(sql_type = BigInt)]
+    pub board_id: i64,
+    #[diesel(sql_type = Text)]
+    pub title: String,
+    #[diesel(sql_type = Array<Nullable<Text>>)]
+    pub categories: Vec<Option<String>>,
+    #[diesel(sql_type = Timestamptz)]
+    pub created_at: DateTime<Utc>,
+    #[diesel(sql_type = BigInt)]
+    pub user_id: i64,
+    /// The username of the board's owner.
+    #[diesel(sql_type = Text)]
+    pub author: String,
+    /// How many games have been started on the board.
+    #[diesel(sql_type = BigInt)]
+    pub play_count: i64,
+    #[diesel(sql_type = Nullable<Float8>)]
+    pub average_rating: Option<f64>,
+    #[diesel(sql_type = BigInt)]
+    pub rating_count: i64,
+    #[diesel(sql_type = BigInt)]
+    pub favourite_count: i64,
+    #[diesel(sql_type = BigInt)]
+    #[graphql(skip)]
+    pub total_count: i64,
+}
+
+/// One page of gallery results.
+#[derive(SimpleObject, Clone, Debug)]
+pub struct GalleryPage {
+    pub entries: Vec<GalleryEntry>,
+    /// Number of boards matching the search across all pages.
+    pub total_count: i64,
+    pub has_more: bool,
+}
+
+/// A board with open reports, as shown to moderators.
+#[derive(SimpleObject, Clone, Debug)]
+pub struct ReportedBoard {
+    pub board: GameBoard,
+    pub reports: Vec<BoardReport>,
+}
+
+/// What a moderator decided about a reported board.
+#[derive(Enum, Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ModerationAction {
+    /// Keep the board out of the gallery.
+    Hide,
+    /// Dismiss the reports and list the board again.
+    Restore,
+}
+
+/// Escape `%`, `_` and `\` so a search term matches literally in `ILIKE`.
+fn like_pattern(search: &str) -> String {
+    let escaped = search
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_");
+    format!("%{}%", escaped)
+}
+
+/// List public boards, optionally filtered by a search over title, author
+/// and categories.
+///
+/// # Arguments
+/// * `conn` - A mutable reference to an async PostgreSQL connection.
+/// * `search` - Text to look for, case-insensitively.
+/// * `sort` - The order of the results.
+/// * `limit` - Maximum number of entries to return.
+/// * `offset` - Number of entries to skip.
+///
+/// # Returns
+/// A `Result` containing the page of results or a Diesel error.
+pub async fn search_gallery(
+    conn: &mut AsyncPgConnection,
+    search: Option<&str>,
+    sort: GallerySort,
+    limit: i32,
+    offset: i32,
+) -> Result<GalleryPage, diesel::result::Error> {
+    let pattern = search
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(like_pattern);
+
+    let query = format!(
+        "SELECT b.id AS board_id, b.title, b.categories, b.created_at, b.user_id,
+                u.username AS author,
+                (SELECT COUNT(*) FROM games g WHERE g.game_board_id = b.id) AS play_count,
+                (SELECT AVG(r.rating)::FLOAT8 FROM board_ratings r WHERE r.board_id = b.id)
+                    AS average_rating,
+                (SELECT COUNT(*) FROM board_ratings r WHERE r.board_id = b.id) AS rating_count,
+                (SELECT COUNT(*) FROM board_favourites f WHERE f.board_id = b.id)
+                    AS favourite_count,
+                COUNT(*) OVER () AS total_count
+         FROM game_boards b
+         JOIN users u ON u.id = b.user_id
+         WHERE b.visibility = 'public'
+           AND b.archived_at IS NULL
+           AND b.flagged_at IS NULL
+           AND ($1::TEXT IS NULL
+                OR b.title ILIKE $1
+                OR u.username ILIKE $1
+                OR array_to_string(b.categories, ' ') ILIKE $1)
+         ORDER BY {}
+         LIMIT $2 OFFSET $3",
+        sort.order_by()
+    );
+
+    let entries: Vec<GalleryEntry> = diesel::sql_query(query)
+        .bind::<Nullable<Text>, _>(pattern)
+        .bind::<BigInt, _>(i64::from(limit))
+        .bind::<BigInt, _>(i64::from(offset))
+        .load(conn)
+        .await?;
+
+    let total_count = entries.iter().map(|e| e.total_count).next().unwrap_or(0);
+    Ok(GalleryPage {
+        has_more: i64::from(offset) + (entries.len() as i64) < total_count,
+        total_count,
+        entries,
+    })
+}
+
+/// Report a board, flagging it once `REPORT_THRESHOLD` users have open
+/// reports on it.
+///
+/// # Arguments
+/// * `conn` - A mutable reference to an async PostgreSQL connection.
+/// * `new_report` - The board, reporting user and reason.
+///
+/// # Returns
+/// A `Result` containing the new report, or a unique violation if the user
+/// already has an open report on the board.
+pub async fn report_board(
+    conn: &mut AsyncPgConnection,
+    new_report: NewBoardReport,
+) -> Result<BoardReport, diesel::result::Error> {
+    conn.transaction::<_, diesel::result::Error, _>(|conn| {
+        async move {
+            let report = BoardReport::create(conn, new_report).await?;
+            if BoardReport::count_open(conn, report.board_id).await? >= REPORT_THRESHOLD {
+                GameBoard::set_flagged(conn, report.board_id, true).await?;
+            }
+            Ok(report)
+        }
+        .scope_boxed()
+    })
+    .await
+}
+
+/// Resolve a board's open reports and hide or restore it.
+///
+/// # Arguments
+/// * `conn` - A mutable reference to an async PostgreSQL connection.
+/// * `board_id` - The unique identifier of the game board.
+/// * `action` - Whether to keep the board hidden or list it again.
+///
+/// # Returns
+/// A `Result` containing the updated game board or a Diesel error.
+pub async fn moderate_board(
+    conn: &mut AsyncPgConnection,
+    board_id: i64,
+    action: ModerationAction,
+) -> Result<GameBoard, diesel::result::Error> {
+    conn.transaction::<_, diesel::result::Error, _>(|conn| {
+        async move {
+            BoardReport::resolve_all(conn, board_id).await?;
+            GameBoard::set_flagged(conn, board_id, action == ModerationAction::Hide).await
+        }
+        .scope_boxed()
+    })
+    .await
+}
+
+/// Fetch every board with open reports, oldest report first.
+///
+/// # Arguments
+/// * `conn` - A mutable reference to an async PostgreSQL connection.
+///
+/// # Returns
+/// A `Result` containing the reported boards or a Diesel error.
+pub async fn fetch_reported_boards(
+    conn: &mut AsyncPgConnection,
+) -> Result<Vec<ReportedBoard>, diesel::result::Error> {
+    let mut by_board: BTreeMap<i64, Vec<BoardReport>> = BTreeMap::new();
+    let mut order = Vec::new();
+    for report in BoardReport::fetch_open(conn).await? {
+        if !by_board.contains_key(&report.board_id) {
+            order.push(report.board_id);
+        }
+        by_board.entry(report.board_id).or_default().push(report);
+    }
+
+    let mut reported = Vec::with_capacity(order.len());
+    for board_id in order {
+        reported.push(ReportedBoard {
+            board: GameBoard::find_by_id(conn, board_id).await?,
+            reports: by_board.remove(&board_id).unwrap_or_default(),
+        });
+    }
+    Ok(reported)
+}
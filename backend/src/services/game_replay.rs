@@ -0,0 +1,242 @@
+// src/services/game_replay.rs
+//! Rebuild a game from its event log.
+//!
+//! Replaying applies a game's events in order, starting from an empty game
+//! where every contestant has 0 points, and stops after any event index so a
+//! game can be stepped through as it happened. The whole log can also be
+//! exported as JSON.
+
+use crate::models::game::Game;
+use crate::models::game_event::{GameEvent, GameEventData, GameEventType};
+use crate::models::player::Player;
+use crate::models::team::Team;
+use async_graphql::SimpleObject;
+use chrono::{DateTime, Utc};
+use diesel_async::AsyncPgConnection;
+use serde::Serialize;
+
+/// A player's or team's score during a replay.
+#[derive(SimpleObject, Clone, Debug, PartialEq)]
+pub struct ContestantScore {
+    pub id: i64,
+    /// The current name, or `None` if the contestant has since been removed.
+    pub name: Option<String>,
+    pub score: i32,
+}
+
+/// The state of a game after some of its events.
+#[derive(SimpleObject, Clone, Debug)]
+pub struct ReplayState {
+    pub game_id: i64,
+    /// The last event applied, or `None` before the first one.
+    pub event_index: Option<i32>,
+    /// How many events the game has in total.
+    pub event_count: i32,
+    pub round: i32,
+    /// The game question currently selected, until it is answered.
+    pub selected_question_id: Option<i64>,
+    /// Game questions answered correctly, in order.
+    pub answered_question_ids: Vec<i64>,
+    /// Teams that buzzed in on the selected question, in order.
+    pub buzz_queue: Vec<i64>,
+    /// The player who picks the next cell, having answered last correctly.
+    pub control_player_id: Option<i64>,
+    /// The team who picks the next cell, having answered last correctly.
+    pub control_team_id: Option<i64>,
+    pub players: Vec<ContestantScore>,
+    pub teams: Vec<ContestantScore>,
+    pub last_event: Option<GameEvent>,
+}
+
+/// One event as written to an exported timeline.
+#[derive(Serialize, Debug)]
+pub struct TimelineEvent {
+    pub sequence: i32,
+    pub created_at: DateTime<Utc>,
+    pub event_type: GameEventType,
+    pub data: GameEventData,
+}
+
+/// A whole game as written to an exported timeline.
+#[derive(Serialize, Debug)]
+pub struct GameTimeline {
+    pub game_id: i64,
+    pub title: String,
+    pub categories: Vec<Option<String>>,
+    pub created_at: DateTime<Utc>,
+    pub exported_at: DateTime<Utc>,
+    pub events: Vec<TimelineEvent>,
+}
+
+fn decode(event: &GameEvent) -> Result<GameEventData, diesel::result::Error> {
+    event
+        .event_data()
+        .map_err(|e| diesel::result::Error::DeserializationError(Box::new(e)))
+}
+
+/// Set a contestant's score, adding it to the list if it is not there yet.
+fn set_score(contestants: &mut Vec<ContestantScore>, id: i64, score: Option<i32>, points: i32) {
+    let index = match contestants.iter().position(|c| c.id == id) {
+        Some(index) => index,
+        None => {
+            contestants.push(ContestantScore {
+                id,
+                name: None,
+                score: 0,
+            });
+            contestants.len() - 1
+        }
+    };
+    let contestant = &mut contestants[index];
+    contestant.score = score.unwrap_or(contestant.score + points);
+}
+
+/// Apply one event to a replay.
+fn apply(state: &mut ReplayState, event_type: GameEventType, data: &GameEventData) {
+    match event_type {
+        GameEventType::CellSelected => {
+            state.selected_question_id = data.game_question_id;
+            state.buzz_queue.clear();
+        }
+        GameEventType::Buzz => {
+            if data.game_question_id != state.selected_question_id {
+                state.selected_question_id = data.game_question_id;
+                state.buzz_queue.clear();
+            }
+            if let Some(team_id) = data.team_id {
+                state.buzz_queue.push(team_id);
+            }
+        }
+        GameEventType::Response => {}
+        GameEventType::Judged | GameEventType::ScoreChanged => {
+            let points = data.points.unwrap_or(0);
+            if let Some(team_id) = data.team_id {
+                set_score(&mut state.teams, team_id, data.score, points);
+            } else if let Some(player_id) = data.player_id {
+                set_score(&mut state.players, player_id, data.score, points);
+            }
+
+            if event_type == GameEventType::Judged && data.correct == Some(true) {
+                state.control_team_id = data.team_id;
+                state.control_player_id = match data.team_id {
+                    Some(_) => None,
+                    None => data.player_id,
+                };
+                if let Some(game_question_id) = data.game_question_id {
+                    if !state.answered_question_ids.contains(&game_question_id) {
+                        state.answered_question_ids.push(game_question_id);
+                    }
+                    if state.selected_question_id == Some(game_question_id) {
+                        state.selected_question_id = None;
+                        state.buzz_queue.clear();
+                    }
+                }
+            }
+        }
+        GameEventType::ControlChanged => {
+            state.control_player_id = data.player_id;
+            state.control_team_id = data.team_id;
+        }
+        GameEventType::RoundAdvanced => {
+            state.round = data.round.unwrap_or(state.round + 1);
+            state.selected_question_id = None;
+            state.buzz_queue.clear();
+        }
+    }
+}
+
+/// Rebuild a game as it was right after one of its events.
+///
+/// # Arguments
+/// * `conn` - A mutable reference to an async PostgreSQL connection.
+/// * `game_id` - The unique identifier of the game.
+/// * `event_index` - The last event to apply, or `None` for all of them.
+///
+/// # Returns
+/// A `Result` containing the replayed state, `None` if the game has no
+/// event at `event_index`, or a Diesel error.
+pub async fn replay_game(
+    conn: &mut AsyncPgConnection,
+    game_id: i64,
+    event_index: Option<i32>,
+) -> Result<Option<ReplayState>, diesel::result::Error> {
+    let events = GameEvent::fetch_by_game_id(conn, game_id).await?;
+    let applied = match event_index {
+        Some(index) if index < 0 || index as usize >= events.len() => return Ok(None),
+        Some(index) => index as usize + 1,
+        None => events.len(),
+    };
+
+    let contestant = |id, name| ContestantScore {
+        id,
+        name: Some(name),
+        score: 0,
+    };
+    let mut state = ReplayState {
+        game_id,
+        event_index: None,
+        event_count: events.len() as i32,
+        round: 1,
+        selected_question_id: None,
+        answered_question_ids: Vec::new(),
+        buzz_queue: Vec::new(),
+        control_player_id: None,
+        control_team_id: None,
+        players: Player::fetch_by_game_id(conn, game_id)
+            .await?
+            .into_iter()
+            .map(|p| contestant(p.id, p.player_name))
+            .collect(),
+        teams: Team::fetch_by_game_id(conn, game_id)
+            .await?
+            .into_iter()
+            .map(|t| contestant(t.id, t.team_name))
+            .collect(),
+        last_event: None,
+    };
+
+    for event in events.into_iter().take(applied) {
+        apply(&mut state, event.event_type, &decode(&event)?);
+        state.event_index = Some(event.sequence);
+        state.last_event = Some(event);
+    }
+    Ok(Some(state))
+}
+
+/// Export a game's whole event log as JSON.
+///
+/// # Arguments
+/// * `conn` - A mutable reference to an async PostgreSQL connection.
+/// * `game_id` - The unique identifier of the game.
+///
+/// # Returns
+/// A `Result` containing the pretty-printed JSON timeline or a Diesel error.
+pub async fn export_timeline(
+    conn: &mut AsyncPgConnection,
+    game_id: i64,
+) -> Result<String, diesel::result::Error> {
+    let game = Game::find_by_id(conn, game_id).await?;
+    let events = GameEvent::fetch_by_game_id(conn, game_id)
+        .await?
+        .iter()
+        .map(|event| {
+            Ok(TimelineEvent {
+                sequence: event.sequence,
+                created_at: event.created_at,
+                event_type: event.event_type,
+                data: decode(event)?,
+            })
+        })
+        .collect::<Result<Vec<_>, diesel::result::Error>>()?;
+
+    let timeline = GameTimeline {
+        game_id: game.id,
+        title: game.title,
+        categories: game.categories,
+        created_at: game.created_at,
+        exported_at: Utc::now(),
+        events,
+    };
+    serde_json::to_string_pretty(&timeline)
+        .map_err(|e| diesel::result::Error::SerializationError(Box::new(e)))
+}
@@ -0,0 +1,378 @@
+// src/services/game_timers.rs
+//! Server-authoritative game timers.
+//!
+//! The server decides when each phase of a question ends: the clue is read,
+//! the buzz window opens, the contestant who buzzed gets a limited time to
+//! answer, and Final Jeopardy has its own clock. A background loop moves
+//! games to their next phase as soon as a phase runs out, and every change is
+//! published on the `TimerHub` with the server's time so that all screens can
+//! count down from the same clock.
+
+use crate::db::pool::DBPool;
+use crate::models::game::Game;
+use crate::models::game_timer::{GamePhase, GameTimer};
+use async_graphql::SimpleObject;
+use chrono::{DateTime, Duration, Utc};
+use diesel_async::AsyncPgConnection;
+use tokio::sync::broadcast;
+use tokio::task::JoinHandle;
+
+/// How often the background loop looks for phases that ran out.
+pub const TIMER_TICK: std::time::Duration = std::time::Duration::from_millis(250);
+pub const MIN_PHASE_SECONDS: i32 = 1;
+pub const MAX_PHASE_SECONDS: i32 = 600;
+
+/// Timer updates kept for slow subscribers before they start missing some.
+const HUB_CAPACITY: usize = 256;
+
+/// The state of a game's timer as sent to clients.
+#[derive(SimpleObject, Clone, Debug, PartialEq)]
+pub struct TimerState {
+    pub game_id: i64,
+    pub phase: GamePhase,
+    pub game_question_id: Option<i64>,
+    pub started_at: DateTime<Utc>,
+    pub ends_at: Option<DateTime<Utc>>,
+    /// The server's clock when this state was sent. Clients count down from
+    /// `endsAt - serverTime` to stay in sync regardless of their own clocks.
+    pub server_time: DateTime<Utc>,
+    /// Milliseconds left in the phase at `serverTime`.
+    pub remaining_ms: Option<i64>,
+    /// Whether the clock is stopped because the game is paused.
+    pub paused: bool,
+}
+
+impl TimerState {
+    /// The state of `timer` as seen at `now`.
+    pub fn of(timer: &GameTimer, now: DateTime<Utc>) -> Self {
+        TimerState {
+            game_id: timer.game_id,
+            phase: timer.phase,
+            game_question_id: timer.game_question_id,
+            started_at: timer.started_at,
+            ends_at: timer.ends_at,
+            server_time: now,
+            remaining_ms: timer
+                .ends_at
+                .map(|ends_at| (ends_at - now).num_milliseconds().max(0))
+                .or(timer.paused_remaining_ms),
+            paused: timer.paused_remaining_ms.is_some(),
+        }
+    }
+
+    /// The state of a game that never started a timer.
+    pub fn idle(game_id: i64, now: DateTime<Utc>) -> Self {
+        TimerState {
+            game_id,
+            phase: GamePhase::Idle,
+            game_question_id: None,
+            started_at: now,
+            ends_at: None,
+            server_time: now,
+            remaining_ms: None,
+            paused: false,
+        }
+    }
+}
+
+/// Publishes timer changes to subscribed clients.
+#[derive(Clone)]
+pub struct TimerHub {
+    sender: broadcast::Sender<TimerState>,
+}
+
+impl Default for TimerHub {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TimerHub {
+    pub fn new() -> Self {
+        let (sender, _) = broadcast::channel(HUB_CAPACITY);
+        TimerHub { sender }
+    }
+
+    /// Send a timer's new state to every subscriber. Having none is fine.
+    pub fn publish(&self, timer: &GameTimer) {
+        let _ = self.sender.send(TimerState::of(timer, Utc::now()));
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<TimerState> {
+        self.sender.subscribe()
+    }
+}
+
+/// How long `phase` lasts in a game, or `None` if it does not run out.
+fn phase_seconds(timer: &GameTimer, phase: GamePhase) -> Option<i32> {
+    match phase {
+        GamePhase::Idle => None,
+        GamePhase::Reading => Some(timer.reading_seconds),
+        GamePhase::BuzzWindow => Some(timer.buzz_seconds),
+        GamePhase::Answering => Some(timer.answer_seconds),
+        GamePhase::FinalJeopardy => Some(timer.final_seconds),
+    }
+}
+
+/// The phase a game moves to when `phase` runs out. A contestant who runs
+/// out of time to answer reopens buzzing for the others.
+fn next_phase(phase: GamePhase) -> GamePhase {
+    match phase {
+        GamePhase::Reading => GamePhase::BuzzWindow,
+        GamePhase::Answering => GamePhase::BuzzWindow,
+        GamePhase::BuzzWindow | GamePhase::FinalJeopardy | GamePhase::Idle => GamePhase::Idle,
+    }
+}
+
+/// Start a phase now, replacing whatever phase the game was in. In a paused
+/// game the phase waits, with its full length, until the game is unpaused.
+/// It may run inside the caller's transaction, so publish the timer once
+/// that transaction has committed.
+///
+/// # Arguments
+/// * `conn` - A mutable reference to an async PostgreSQL connection.
+/// * `game_id` - The unique identifier of the game.
+/// * `phase` - The phase to enter.
+/// * `game_question_id` - The game question the phase is about.
+///
+/// # Returns
+/// A `Result` containing the updated timer or a Diesel error.
+pub async fn enter_phase(
+    conn: &mut AsyncPgConnection,
+    game_id: i64,
+    phase: GamePhase,
+    game_question_id: Option<i64>,
+) -> Result<GameTimer, diesel::result::Error> {
+    let timer = GameTimer::ensure(conn, game_id).await?;
+    let now = Utc::now();
+    let ends_at =
+        phase_seconds(&timer, phase).map(|seconds| now + Duration::seconds(i64::from(seconds)));
+    let game_question_id = if phase == GamePhase::Idle {
+        None
+    } else {
+        game_question_id
+    };
+
+    let mut timer =
+        GameTimer::set_phase(conn, game_id, phase, game_question_id, now, ends_at).await?;
+    if Game::find_by_id(conn, game_id).await?.paused_at.is_some() {
+        if let Some(paused) = GameTimer::pause(conn, &timer, now).await? {
+            timer = paused;
+        }
+    }
+    Ok(timer)
+}
+
+/// Move an expired timer on to its next phase, timed from when the old one
+/// ended so a late tick does not stretch it.
+///
+/// # Arguments
+/// * `conn` - A mutable reference to an async PostgreSQL connection.
+/// * `hub` - Where to publish the change.
+/// * `timer` - The timer whose phase ran out.
+///
+/// # Returns
+/// A `Result` containing the advanced timer, or `None` if another caller
+/// advanced it first, or a Diesel error.
+async fn advance_timer(
+    conn: &mut AsyncPgConnection,
+    hub: &TimerHub,
+    timer: &GameTimer,
+) -> Result<Option<GameTimer>, diesel::result::Error> {
+    let phase = next_phase(timer.phase);
+    let ends_at = match (timer.ends_at, phase_seconds(timer, phase)) {
+        (Some(expired_at), Some(seconds)) => {
+            Some(expired_at + Duration::seconds(i64::from(seconds)))
+        }
+        _ => None,
+    };
+    let game_question_id = if phase == GamePhase::Idle {
+        None
+    } else {
+        timer.game_question_id
+    };
+    let advanced = GameTimer::advance(conn, timer, phase, game_question_id, ends_at).await?;
+    if let Some(advanced) = &advanced {
+        hub.publish(advanced);
+    }
+    Ok(advanced)
+}
+
+/// Move every game whose phase ran out to its next phase.
+///
+/// # Arguments
+/// * `conn` - A mutable reference to an async PostgreSQL connection.
+/// * `hub` - Where to publish the changes.
+/// * `now` - The current server time.
+///
+/// # Returns
+/// A `Result` containing the number of transitions made or a Diesel error.
+pub async fn advance_expired(
+    conn: &mut AsyncPgConnection,
+    hub: &TimerHub,
+    now: DateTime<Utc>,
+) -> Result<usize, diesel::result::Error> {
+    let mut transitions = 0;
+    // A phase that started in the past may itself have run out already, so
+    // keep going until nothing is left; every chain ends at `Idle`.
+    loop {
+        let expired = GameTimer::fetch_expired(conn, now).await?;
+        if expired.is_empty() {
+            return Ok(transitions);
+        }
+        for timer in expired {
+            if advance_timer(conn, hub, &timer).await?.is_some() {
+                transitions += 1;
+            }
+        }
+    }
+}
+
+/// The current state of a game's timer, first applying any transition of
+/// that game that is due so a read never shows a phase that already ran out.
+/// Other games are left to the timer loop.
+///
+/// # Arguments
+/// * `conn` - A mutable reference to an async PostgreSQL connection.
+/// * `hub` - Where to publish any transition made.
+/// * `game_id` - The unique identifier of the game.
+///
+/// # Returns
+/// A `Result` containing the timer state or a Diesel error.
+pub async fn current_state(
+    conn: &mut AsyncPgConnection,
+    hub: &TimerHub,
+    game_id: i64,
+) -> Result<TimerState, diesel::result::Error> {
+    let now = Utc::now();
+    let mut timer = GameTimer::find(conn, game_id).await?;
+    while let Some(expired) = timer
+        .as_ref()
+        .filter(|t| t.ends_at.is_some_and(|ends_at| ends_at <= now))
+    {
+        timer = match advance_timer(conn, hub, expired).await? {
+            Some(advanced) => Some(advanced),
+            // Someone else moved it on; read where they left it
+            None => GameTimer::find(conn, game_id).await?,
+        };
+    }
+    Ok(match timer {
+        Some(timer) => TimerState::of(&timer, now),
+        None => TimerState::idle(game_id, now),
+    })
+}
+
+/// Whether a team may buzz in on `game_question_id` right now. Buzzing is
+/// refused only while the game's timer is reading that question or another
+/// team is answering it; games that do not use timers are unaffected.
+///
+/// # Arguments
+/// * `conn` - A mutable reference to an async PostgreSQL connection.
+/// * `hub` - Where to publish any transition made.
+/// * `game_id` - The unique identifier of the game.
+/// * `game_question_id` - The unique identifier of the game question.
+///
+/// # Returns
+/// A `Result` containing whether buzzing is open or a Diesel error.
+pub async fn buzzing_open(
+    conn: &mut AsyncPgConnection,
+    hub: &TimerHub,
+    game_id: i64,
+    game_question_id: i64,
+) -> Result<bool, diesel::result::Error> {
+    let state = current_state(conn, hub, game_id).await?;
+    Ok(state.game_question_id != Some(game_question_id)
+        || !matches!(state.phase, GamePhase::Reading | GamePhase::Answering))
+}
+
+/// Give the team that just buzzed its time to answer, if the game's timer
+/// had the buzz window open on that question. Publish the updated timer
+/// once the caller's transaction has committed.
+///
+/// # Arguments
+/// * `conn` - A mutable reference to an async PostgreSQL connection.
+/// * `game_id` - The unique identifier of the game.
+/// * `game_question_id` - The unique identifier of the game question.
+///
+/// # Returns
+/// A `Result` containing the updated timer, if it changed, or a Diesel error.
+pub async fn on_buzz(
+    conn: &mut AsyncPgConnection,
+    game_id: i64,
+    game_question_id: i64,
+) -> Result<Option<GameTimer>, diesel::result::Error> {
+    match GameTimer::find(conn, game_id).await? {
+        Some(timer)
+            if timer.phase == GamePhase::BuzzWindow
+                && timer.game_question_id == Some(game_question_id) =>
+        {
+            let timer =
+                enter_phase(conn, game_id, GamePhase::Answering, Some(game_question_id)).await?;
+            Ok(Some(timer))
+        }
+        _ => Ok(None),
+    }
+}
+
+/// Move the timer on after the host judged an answer: a correct answer ends
+/// the question, an incorrect one reopens buzzing. Publish the updated timer
+/// once the caller's transaction has committed.
+///
+/// # Arguments
+/// * `conn` - A mutable reference to an async PostgreSQL connection.
+/// * `game_id` - The unique identifier of the game.
+/// * `game_question_id` - The unique identifier of the game question.
+/// * `correct` - Whether the answer was accepted.
+///
+/// # Returns
+/// A `Result` containing the updated timer, if it changed, or a Diesel error.
+pub async fn on_judged(
+    conn: &mut AsyncPgConnection,
+    game_id: i64,
+    game_question_id: i64,
+    correct: bool,
+) -> Result<Option<GameTimer>, diesel::result::Error> {
+    match GameTimer::find(conn, game_id).await? {
+        Some(timer)
+            if timer.phase != GamePhase::Idle
+                && timer.game_question_id == Some(game_question_id) =>
+        {
+            let phase = if correct {
+                GamePhase::Idle
+            } else {
+                GamePhase::BuzzWindow
+            };
+            let timer = enter_phase(conn, game_id, phase, Some(game_question_id)).await?;
+            Ok(Some(timer))
+        }
+        _ => Ok(None),
+    }
+}
+
+/// Run `advance_expired` every `TIMER_TICK` until the process exits.
+///
+/// # Arguments
+/// * `pool` - The database pool to take connections from.
+/// * `hub` - Where to publish the transitions.
+///
+/// # Returns
+/// The handle of the spawned task.
+pub fn spawn_timer_loop(pool: DBPool, hub: TimerHub) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(TIMER_TICK);
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+        loop {
+            interval.tick().await;
+            let mut conn = match pool.get().await {
+                Ok(conn) => conn,
+                Err(e) => {
+                    tracing::warn!("Timer loop could not get a connection: {}", e);
+                    continue;
+                }
+            };
+            if let Err(e) = advance_expired(&mut conn, &hub, Utc::now()).await {
+                tracing::error!("Timer loop failed to advance timers: {:?}", e);
+            }
+        }
+    })
+}
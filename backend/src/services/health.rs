@@ -0,0 +1,27 @@
+// src/services/health.rs
+//! What `/readyz` reports: whether the server is ready to take traffic.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/// Shared between startup, which flips it once the schema is current, and
+/// the readiness endpoint. Clones share the same state.
+#[derive(Clone, Debug, Default)]
+pub struct Readiness {
+    migrated: Arc<AtomicBool>,
+}
+
+impl Readiness {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record that the database schema matches this binary.
+    pub fn mark_migrated(&self) {
+        self.migrated.store(true, Ordering::Release);
+    }
+
+    pub fn is_migrated(&self) -> bool {
+        self.migrated.load(Ordering::Acquire)
+    }
+}
@@ -0,0 +1,190 @@
+// src/services/live_games.rs
+//! Pausing, resuming and resyncing games in play.
+//!
+//! Everything about a game in play lives in the database: scores, the open
+//! question, timers and the event log. A client that lost its connection, or
+//! every client after the server restarted, can therefore ask for a
+//! `GameSnapshot` and carry on from it. Hosts can also pause a game, which
+//! stops its clock until they unpause it.
+
+use crate::models::game::Game;
+use crate::models::game_event::GameEvent;
+use crate::models::game_question::GameQuestion;
+use crate::models::game_timer::GameTimer;
+use crate::models::player::Player;
+use crate::models::team::Team;
+use crate::services::game_replay::replay_game;
+use crate::services::game_timers::{current_state, TimerHub, TimerState};
+use async_graphql::SimpleObject;
+use chrono::Utc;
+use diesel_async::scoped_futures::ScopedFutureExt;
+use diesel_async::{AsyncConnection, AsyncPgConnection};
+use std::collections::HashSet;
+
+/// A cell of a game's board.
+#[derive(SimpleObject, Clone, Debug)]
+pub struct CellState {
+    pub game_question: GameQuestion,
+    /// Whether the cell has been picked or answered.
+    pub played: bool,
+}
+
+/// Everything a client needs to show a game in play.
+#[derive(SimpleObject, Clone, Debug)]
+pub struct GameSnapshot {
+    pub game: Game,
+    pub cells: Vec<CellState>,
+    pub players: Vec<Player>,
+    pub teams: Vec<Team>,
+    /// The game question being played, if any.
+    pub current_question_id: Option<i64>,
+    /// Teams that buzzed in on the current question, in order.
+    pub buzz_queue: Vec<i64>,
+    /// The player in control of the board, whose turn it is to pick a cell.
+    pub control_player_id: Option<i64>,
+    /// The team whose turn it is to pick a cell.
+    pub control_team_id: Option<i64>,
+    pub round: i32,
+    pub paused: bool,
+    pub timer: TimerState,
+    /// The last event the snapshot includes, so clients can tell whether
+    /// they missed any.
+    pub last_event_sequence: Option<i32>,
+}
+
+impl GameSnapshot {
+    /// Blank out the answers of cells that are unplayed or still in play,
+    /// for anyone but the host.
+    pub fn hide_answers(&mut self) {
+        for cell in &mut self.cells {
+            if !cell.played || self.current_question_id == Some(cell.game_question.id) {
+                cell.game_question.hide_answers();
+            }
+        }
+    }
+}
+
+/// Take a snapshot of a game as it is now.
+///
+/// # Arguments
+/// * `conn` - A mutable reference to an async PostgreSQL connection.
+/// * `hub` - Where to publish any timer transition that was due.
+/// * `game_id` - The unique identifier of the game.
+///
+/// # Returns
+/// A `Result` containing the snapshot or a Diesel error.
+pub async fn game_snapshot(
+    conn: &mut AsyncPgConnection,
+    hub: &TimerHub,
+    game_id: i64,
+) -> Result<GameSnapshot, diesel::result::Error> {
+    // Catch up on any phase that ran out while nobody was watching
+    let timer = current_state(conn, hub, game_id).await?;
+    let game = Game::find_by_id(conn, game_id).await?;
+    let replay = replay_game(conn, game_id, None)
+        .await?
+        .ok_or(diesel::NotFound)?;
+
+    let played: HashSet<i64> = GameEvent::fetch_by_game_id(conn, game_id)
+        .await?
+        .iter()
+        .filter_map(|event| event.event_data().ok()?.game_question_id)
+        .collect();
+    let cells = GameQuestion::fetch_by_game_id(conn, game_id)
+        .await?
+        .into_iter()
+        .map(|game_question| CellState {
+            played: played.contains(&game_question.id),
+            game_question,
+        })
+        .collect();
+
+    let current_question_id = timer
+        .game_question_id
+        .or(game.open_question_id)
+        .or(replay.selected_question_id);
+
+    Ok(GameSnapshot {
+        cells,
+        players: Player::fetch_by_game_id(conn, game_id).await?,
+        teams: Team::fetch_by_game_id(conn, game_id).await?,
+        current_question_id,
+        buzz_queue: replay.buzz_queue,
+        control_player_id: game.control_player_id,
+        control_team_id: game.control_team_id,
+        round: game.round,
+        paused: game.paused_at.is_some(),
+        timer,
+        last_event_sequence: replay.event_index,
+        game,
+    })
+}
+
+/// Pause a game: its clock stops, keeping the time the phase had left, and
+/// contestants cannot buzz or respond until it is unpaused.
+///
+/// # Arguments
+/// * `conn` - A mutable reference to an async PostgreSQL connection.
+/// * `hub` - Where to publish the stopped timer.
+/// * `game_id` - The unique identifier of the game.
+///
+/// # Returns
+/// A `Result` containing the paused game or a Diesel error.
+pub async fn pause_game(
+    conn: &mut AsyncPgConnection,
+    hub: &TimerHub,
+    game_id: i64,
+) -> Result<Game, diesel::result::Error> {
+    let now = Utc::now();
+    let (game, timer) = conn
+        .transaction::<_, diesel::result::Error, _>(|conn| {
+            async move {
+                let game = Game::pause(conn, game_id, now).await?;
+                let timer = match GameTimer::find(conn, game_id).await? {
+                    Some(timer) => GameTimer::pause(conn, &timer, now).await?,
+                    None => None,
+                };
+                Ok((game, timer))
+            }
+            .scope_boxed()
+        })
+        .await?;
+
+    if let Some(timer) = timer {
+        hub.publish(&timer);
+    }
+    Ok(game)
+}
+
+/// Unpause a game: its clock restarts with the time it had left and the
+/// deadline of an open question moves back by the length of the pause.
+///
+/// # Arguments
+/// * `conn` - A mutable reference to an async PostgreSQL connection.
+/// * `hub` - Where to publish the restarted timer.
+/// * `game_id` - The unique identifier of the game.
+///
+/// # Returns
+/// A `Result` containing the unpaused game or a Diesel error.
+pub async fn unpause_game(
+    conn: &mut AsyncPgConnection,
+    hub: &TimerHub,
+    game_id: i64,
+) -> Result<Game, diesel::result::Error> {
+    let now = Utc::now();
+    let (game, timer) = conn
+        .transaction::<_, diesel::result::Error, _>(|conn| {
+            async move {
+                let game = Game::unpause(conn, game_id, now).await?;
+                let timer = GameTimer::unpause(conn, game_id, now).await?;
+                Ok((game, timer))
+            }
+            .scope_boxed()
+        })
+        .await?;
+
+    if let Some(timer) = timer {
+        hub.publish(&timer);
+    }
+    Ok(game)
+}
@@ -0,0 +1,384 @@
+// src/services/localization.rs
+//! Serving questions and boards in the locale a client asks for.
+//!
+//! Questions and boards are written in one locale and may be translated
+//! into others. A client asking for `es-MX` gets the `es-MX` translation if
+//! there is one, then the `es` translation, and otherwise the text as it was
+//! written. Translations for a whole board can be exported as JSON for
+//! translators and imported back.
+
+use crate::graphql::validation::{ValidationErrors, Validator};
+use crate::models::game_board::GameBoard;
+use crate::models::game_board_translation::{GameBoardTranslation, NewGameBoardTranslation};
+use crate::models::question::Question;
+use crate::models::question_translation::{NewQuestionTranslation, QuestionTranslation};
+use crate::models::GBQ;
+use async_graphql::SimpleObject;
+use diesel_async::scoped_futures::ScopedFutureExt;
+use diesel_async::{AsyncConnection, AsyncPgConnection};
+use serde::{Deserialize, Serialize};
+
+/// Put a locale tag in its canonical form: a lowercase language, optionally
+/// followed by an uppercase region, such as `es` or `es-MX`.
+///
+/// # Arguments
+/// * `locale` - The tag as the client sent it, e.g. `es_mx`.
+///
+/// # Returns
+/// The canonical tag, or `None` if it is not a locale tag.
+pub fn normalize_locale(locale: &str) -> Option<String> {
+    let mut parts = locale.trim().split(['-', '_']);
+    let language = parts.next()?;
+    if !(2..=3).contains(&language.len()) || !language.chars().all(|c| c.is_ascii_alphabetic()) {
+        return None;
+    }
+    let language = language.to_ascii_lowercase();
+    match (parts.next(), parts.next()) {
+        (None, _) => Some(language),
+        (Some(region), None)
+            if (region.len() == 2 && region.chars().all(|c| c.is_ascii_alphabetic()))
+                || (region.len() == 3 && region.chars().all(|c| c.is_ascii_digit())) =>
+        {
+            Some(format!("{}-{}", language, region.to_ascii_uppercase()))
+        }
+        _ => None,
+    }
+}
+
+/// Check a `locale` argument and put it in canonical form.
+///
+/// # Arguments
+/// * `locale` - The locale a client asked for, if any.
+///
+/// # Returns
+/// The canonical locale, or a validation error naming the `locale` field.
+pub fn requested_locale(locale: Option<&str>) -> Result<Option<String>, ValidationErrors> {
+    let Some(locale) = locale else {
+        return Ok(None);
+    };
+    let mut v = Validator::default();
+    v.locale("locale", locale);
+    v.finish()?;
+    Ok(normalize_locale(locale))
+}
+
+/// The translations to look for, most specific first: `es-MX` then `es`.
+fn fallback_chain(locale: &str) -> Vec<String> {
+    let mut chain = vec![locale.to_string()];
+    if let Some((language, _)) = locale.split_once('-') {
+        chain.push(language.to_string());
+    }
+    chain
+}
+
+/// Show questions in a locale, where they have been translated into it.
+///
+/// # Arguments
+/// * `conn` - A mutable reference to an async PostgreSQL connection.
+/// * `questions` - The questions as they were written.
+/// * `locale` - The locale wanted, already normalized. `None` leaves the
+///   questions as they are.
+///
+/// # Returns
+/// A `Result` containing the questions, translated where possible, or a
+/// Diesel error.
+pub async fn localize_questions(
+    conn: &mut AsyncPgConnection,
+    mut questions: Vec<Question>,
+    locale: Option<&str>,
+) -> Result<Vec<Question>, diesel::result::Error> {
+    let Some(locale) = locale else {
+        return Ok(questions);
+    };
+    let chain = fallback_chain(locale);
+    let ids: Vec<i64> = questions.iter().map(|q| q.id).collect();
+    let translations = QuestionTranslation::fetch_for_questions(conn, &ids, &chain).await?;
+
+    for question in questions.iter_mut() {
+        for locale in &chain {
+            // A question already written in the locale needs no translation
+            if question.locale == *locale {
+                break;
+            }
+            let translation = translations
+                .iter()
+                .find(|t| t.question_id == question.id && t.locale == *locale);
+            if let Some(t) = translation {
+                question.question = t.question.clone();
+                if let Some(answer) = &t.answer {
+                    question.answer = answer.clone();
+                }
+                question.locale = t.locale.clone();
+                break;
+            }
+        }
+    }
+    Ok(questions)
+}
+
+/// Show a question in a locale, if it has been translated into it.
+///
+/// # Arguments
+/// * `conn` - A mutable reference to an async PostgreSQL connection.
+/// * `question` - The question as it was written.
+/// * `locale` - The locale wanted, already normalized.
+///
+/// # Returns
+/// A `Result` containing the question, translated if possible, or a Diesel
+/// error.
+pub async fn localize_question(
+    conn: &mut AsyncPgConnection,
+    question: Question,
+    locale: Option<&str>,
+) -> Result<Question, diesel::result::Error> {
+    let mut questions = localize_questions(conn, vec![question], locale).await?;
+    Ok(questions.remove(0))
+}
+
+/// Show game boards in a locale, where they have been translated into it.
+///
+/// # Arguments
+/// * `conn` - A mutable reference to an async PostgreSQL connection.
+/// * `boards` - The game boards as they were written.
+/// * `locale` - The locale wanted, already normalized. `None` leaves the
+///   boards as they are.
+///
+/// # Returns
+/// A `Result` containing the boards, translated where possible, or a Diesel
+/// error.
+pub async fn localize_game_boards(
+    conn: &mut AsyncPgConnection,
+    mut boards: Vec<GameBoard>,
+    locale: Option<&str>,
+) -> Result<Vec<GameBoard>, diesel::result::Error> {
+    let Some(locale) = locale else {
+        return Ok(boards);
+    };
+    let chain = fallback_chain(locale);
+    let ids: Vec<i64> = boards.iter().map(|b| b.id).collect();
+    let translations = GameBoardTranslation::fetch_for_game_boards(conn, &ids, &chain).await?;
+
+    for board in boards.iter_mut() {
+        for locale in &chain {
+            if board.locale == *locale {
+                break;
+            }
+            let translation = translations
+                .iter()
+                .find(|t| t.game_board_id == board.id && t.locale == *locale);
+            if let Some(t) = translation {
+                board.title = t.title.clone();
+                for (category, translated) in board.categories.iter_mut().zip(&t.categories) {
+                    if translated.is_some() {
+                        category.clone_from(translated);
+                    }
+                }
+                board.locale = t.locale.clone();
+                break;
+            }
+        }
+    }
+    Ok(boards)
+}
+
+/// Show a game board in a locale, if it has been translated into it.
+///
+/// # Arguments
+/// * `conn` - A mutable reference to an async PostgreSQL connection.
+/// * `board` - The game board as it was written.
+/// * `locale` - The locale wanted, already normalized.
+///
+/// # Returns
+/// A `Result` containing the board, translated if possible, or a Diesel
+/// error.
+pub async fn localize_game_board(
+    conn: &mut AsyncPgConnection,
+    board: GameBoard,
+    locale: Option<&str>,
+) -> Result<GameBoard, diesel::result::Error> {
+    let mut boards = localize_game_boards(conn, vec![board], locale).await?;
+    Ok(boards.remove(0))
+}
+
+/// Show a board's questions in a locale, where they have been translated.
+///
+/// # Arguments
+/// * `conn` - A mutable reference to an async PostgreSQL connection.
+/// * `board_questions` - The board's questions as they were written.
+/// * `locale` - The locale wanted, already normalized.
+///
+/// # Returns
+/// A `Result` containing the board's questions, translated where possible,
+/// or a Diesel error.
+pub async fn localize_board_questions(
+    conn: &mut AsyncPgConnection,
+    board_questions: Vec<GBQ>,
+    locale: Option<&str>,
+) -> Result<Vec<GBQ>, diesel::result::Error> {
+    let (mappings, questions): (Vec<_>, Vec<_>) = board_questions
+        .into_iter()
+        .map(|gbq| (gbq.mapping, gbq.question))
+        .unzip();
+    let questions = localize_questions(conn, questions, locale).await?;
+    Ok(mappings
+        .into_iter()
+        .zip(questions)
+        .map(|(m, q)| GBQ::new(m, q))
+        .collect())
+}
+
+/// A board's translatable text in one locale, as handed to translators.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TranslationBundle {
+    pub locale: String,
+    pub game_board: BoardTranslationEntry,
+    pub questions: Vec<QuestionTranslationEntry>,
+}
+
+/// A board's original title and categories, and their translation so far.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct BoardTranslationEntry {
+    pub id: i64,
+    #[serde(default)]
+    pub source_title: String,
+    #[serde(default)]
+    pub source_categories: Vec<Option<String>>,
+    #[serde(default)]
+    pub title: Option<String>,
+    #[serde(default)]
+    pub categories: Vec<Option<String>>,
+}
+
+/// A question's original text, and its translation so far.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct QuestionTranslationEntry {
+    pub id: i64,
+    #[serde(default)]
+    pub source_question: String,
+    #[serde(default)]
+    pub source_answer: String,
+    #[serde(default)]
+    pub question: Option<String>,
+    #[serde(default)]
+    pub answer: Option<String>,
+}
+
+/// How many translations an import stored.
+#[derive(SimpleObject, Debug, Clone, PartialEq, Eq)]
+pub struct TranslationImportSummary {
+    pub locale: String,
+    pub game_boards: i32,
+    pub questions: i32,
+}
+
+/// Gather a board's text and its translations into a locale for translators.
+///
+/// # Arguments
+/// * `conn` - A mutable reference to an async PostgreSQL connection.
+/// * `game_board_id` - The unique identifier of the game board.
+/// * `locale` - The locale to translate into, already normalized.
+///
+/// # Returns
+/// A `Result` containing the bundle or a Diesel error.
+pub async fn export_translations(
+    conn: &mut AsyncPgConnection,
+    game_board_id: i64,
+    locale: &str,
+) -> Result<TranslationBundle, diesel::result::Error> {
+    let board = GameBoard::find_by_id(conn, game_board_id).await?;
+    let locales = [locale.to_string()];
+    let board_translation =
+        GameBoardTranslation::fetch_for_game_boards(conn, &[board.id], &locales)
+            .await?
+            .pop();
+
+    let questions: Vec<Question> =
+        GBQ::fetch_all_game_board_questions_by_board_id(conn, game_board_id)
+            .await?
+            .into_iter()
+            .map(|gbq| gbq.question)
+            .collect();
+    let ids: Vec<i64> = questions.iter().map(|q| q.id).collect();
+    let translations = QuestionTranslation::fetch_for_questions(conn, &ids, &locales).await?;
+
+    Ok(TranslationBundle {
+        locale: locale.to_string(),
+        game_board: BoardTranslationEntry {
+            id: board.id,
+            source_title: board.title,
+            source_categories: board.categories,
+            title: board_translation.as_ref().map(|t| t.title.clone()),
+            categories: board_translation.map(|t| t.categories).unwrap_or_default(),
+        },
+        questions: questions
+            .into_iter()
+            .map(|q| {
+                let translation = translations.iter().find(|t| t.question_id == q.id);
+                QuestionTranslationEntry {
+                    id: q.id,
+                    source_question: q.question,
+                    source_answer: q.answer,
+                    question: translation.map(|t| t.question.clone()),
+                    answer: translation.and_then(|t| t.answer.clone()),
+                }
+            })
+            .collect(),
+    })
+}
+
+/// Store the translations in a bundle. Entries without a translated title
+/// or question are skipped; the rest replace any earlier translation. The
+/// bundle is stored whole or not at all.
+///
+/// # Arguments
+/// * `conn` - A mutable reference to an async PostgreSQL connection.
+/// * `bundle` - The translations, already checked and normalized.
+///
+/// # Returns
+/// A `Result` containing what was stored or a Diesel error.
+pub async fn import_translations(
+    conn: &mut AsyncPgConnection,
+    bundle: TranslationBundle,
+) -> Result<TranslationImportSummary, diesel::result::Error> {
+    conn.transaction::<_, diesel::result::Error, _>(|conn| {
+        async move {
+            let mut summary = TranslationImportSummary {
+                locale: bundle.locale.clone(),
+                game_boards: 0,
+                questions: 0,
+            };
+            if let Some(title) = bundle.game_board.title {
+                GameBoardTranslation::upsert(
+                    conn,
+                    NewGameBoardTranslation {
+                        game_board_id: bundle.game_board.id,
+                        locale: bundle.locale.clone(),
+                        title,
+                        categories: bundle.game_board.categories,
+                    },
+                )
+                .await?;
+                summary.game_boards += 1;
+            }
+            for entry in bundle.questions {
+                let Some(question) = entry.question else {
+                    continue;
+                };
+                QuestionTranslation::upsert(
+                    conn,
+                    NewQuestionTranslation {
+                        question_id: entry.id,
+                        locale: bundle.locale.clone(),
+                        question,
+                        answer: entry.answer,
+                    },
+                )
+                .await?;
+                summary.questions += 1;
+            }
+            Ok(summary)
+        }
+        .scope_boxed()
+    })
+    .await
+}
@@ -0,0 +1,267 @@
+// src/services/media.rs
+//! Attaching media files to questions.
+//!
+//! Uploads are checked before anything is stored: the content type must be
+//! one of the supported image, audio or video formats, the file's first bytes
+//! must actually look like that format, and the file must fit the size limit
+//! of its kind. Accepted files are written to media storage under a fresh
+//! key and recorded as `QuestionMedia`.
+
+use crate::models::question_media::{MediaKind, NewQuestionMedia, QuestionMedia};
+use crate::storage::{MediaStorage, StorageError};
+use diesel_async::AsyncPgConnection;
+use std::fmt;
+
+pub const MAX_IMAGE_BYTES: usize = 5 * 1024 * 1024;
+pub const MAX_AUDIO_BYTES: usize = 20 * 1024 * 1024;
+pub const MAX_VIDEO_BYTES: usize = 100 * 1024 * 1024;
+/// The largest upload of any kind, which request body limits must allow.
+pub const MAX_UPLOAD_BYTES: usize = MAX_VIDEO_BYTES;
+pub const MAX_MEDIA_PER_QUESTION: usize = 5;
+
+/// The supported content types, their kind and the extension files get.
+const SUPPORTED_TYPES: [(&str, MediaKind, &str); 9] = [
+    ("image/png", MediaKind::Image, "png"),
+    ("image/jpeg", MediaKind::Image, "jpg"),
+    ("image/gif", MediaKind::Image, "gif"),
+    ("image/webp", MediaKind::Image, "webp"),
+    ("audio/mpeg", MediaKind::Audio, "mp3"),
+    ("audio/ogg", MediaKind::Audio, "ogg"),
+    ("audio/wav", MediaKind::Audio, "wav"),
+    ("video/mp4", MediaKind::Video, "mp4"),
+    ("video/webm", MediaKind::Video, "webm"),
+];
+
+/// Why an upload was refused or could not be stored.
+#[derive(Debug)]
+pub enum MediaError {
+    UnsupportedType(String),
+    /// The file's content is not in the format its content type claims.
+    ContentMismatch(String),
+    TooLarge {
+        kind: MediaKind,
+        limit: usize,
+    },
+    Empty,
+    /// The question already has `MAX_MEDIA_PER_QUESTION` files attached.
+    TooMany,
+    Storage(StorageError),
+    Database(diesel::result::Error),
+}
+
+impl fmt::Display for MediaError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MediaError::UnsupportedType(content_type) => {
+                write!(f, "Unsupported media type: {}", content_type)
+            }
+            MediaError::ContentMismatch(content_type) => {
+                write!(f, "File content is not {}", content_type)
+            }
+            MediaError::TooLarge { kind, limit } => write!(
+                f,
+                "{} files may be at most {} MiB",
+                kind.as_str(),
+                limit / (1024 * 1024)
+            ),
+            MediaError::Empty => write!(f, "File is empty"),
+            MediaError::TooMany => write!(
+                f,
+                "A question can have at most {} media files",
+                MAX_MEDIA_PER_QUESTION
+            ),
+            MediaError::Storage(e) => write!(f, "{}", e),
+            MediaError::Database(e) => write!(f, "Database error: {:?}", e),
+        }
+    }
+}
+
+impl std::error::Error for MediaError {}
+
+impl From<StorageError> for MediaError {
+    fn from(e: StorageError) -> Self {
+        MediaError::Storage(e)
+    }
+}
+
+impl From<diesel::result::Error> for MediaError {
+    fn from(e: diesel::result::Error) -> Self {
+        MediaError::Database(e)
+    }
+}
+
+/// The size limit for files of `kind`.
+pub fn max_bytes(kind: MediaKind) -> usize {
+    match kind {
+        MediaKind::Image => MAX_IMAGE_BYTES,
+        MediaKind::Audio => MAX_AUDIO_BYTES,
+        MediaKind::Video => MAX_VIDEO_BYTES,
+    }
+}
+
+/// The content type of a stored file, from the extension it was stored with.
+pub fn content_type_for_key(key: &str) -> Option<&'static str> {
+    let extension = key.rsplit_once('.')?.1;
+    SUPPORTED_TYPES
+        .iter()
+        .find(|(_, _, ext)| *ext == extension)
+        .map(|(content_type, _, _)| *content_type)
+}
+
+/// Recognise a supported format from the first bytes of a file.
+///
+/// # Arguments
+/// * `bytes` - The file's content.
+///
+/// # Returns
+/// The content type the file is in, or `None` if it is not a supported
+/// format.
+pub fn sniff_content_type(bytes: &[u8]) -> Option<&'static str> {
+    let riff_form = |form: &[u8]| bytes.starts_with(b"RIFF") && bytes.get(8..12) == Some(form);
+    if bytes.starts_with(b"\x89PNG\r\n\x1a\n") {
+        Some("image/png")
+    } else if bytes.starts_with(&[0xFF, 0xD8, 0xFF]) {
+        Some("image/jpeg")
+    } else if bytes.starts_with(b"GIF87a") || bytes.starts_with(b"GIF89a") {
+        Some("image/gif")
+    } else if riff_form(b"WEBP") {
+        Some("image/webp")
+    } else if riff_form(b"WAVE") {
+        Some("audio/wav")
+    } else if bytes.starts_with(b"ID3") || (bytes.len() > 1 && bytes[0] == 0xFF && bytes[1] >= 0xE0)
+    {
+        Some("audio/mpeg")
+    } else if bytes.starts_with(b"OggS") {
+        Some("audio/ogg")
+    } else if bytes.get(4..8) == Some(b"ftyp") {
+        Some("video/mp4")
+    } else if bytes.starts_with(&[0x1A, 0x45, 0xDF, 0xA3]) {
+        Some("video/webm")
+    } else {
+        None
+    }
+}
+
+/// Check an upload and work out its kind and file extension.
+///
+/// # Arguments
+/// * `content_type` - The content type the client declared, if any. Without
+///   one, the type is taken from the file's content.
+/// * `bytes` - The file's content.
+///
+/// # Returns
+/// A `Result` containing the content type, kind and extension to store the
+/// file with, or why it was refused.
+pub fn check_upload(
+    content_type: Option<&str>,
+    bytes: &[u8],
+) -> Result<(&'static str, MediaKind, &'static str), MediaError> {
+    if bytes.is_empty() {
+        return Err(MediaError::Empty);
+    }
+    let sniffed = sniff_content_type(bytes);
+    let declared = content_type
+        .map(|ct| {
+            ct.split(';')
+                .next()
+                .unwrap_or_default()
+                .trim()
+                .to_lowercase()
+        })
+        .filter(|ct| !ct.is_empty() && ct != "application/octet-stream");
+
+    let (content_type, kind, extension) = match declared.as_deref().or(sniffed) {
+        Some(ct) => SUPPORTED_TYPES
+            .iter()
+            .find(|(supported, _, _)| *supported == ct)
+            .copied()
+            .ok_or_else(|| MediaError::UnsupportedType(ct.to_string()))?,
+        None => return Err(MediaError::UnsupportedType("unknown".to_string())),
+    };
+    if sniffed != Some(content_type) {
+        return Err(MediaError::ContentMismatch(content_type.to_string()));
+    }
+    if bytes.len() > max_bytes(kind) {
+        return Err(MediaError::TooLarge {
+            kind,
+            limit: max_bytes(kind),
+        });
+    }
+    Ok((content_type, kind, extension))
+}
+
+/// Check a file, store it and attach it to a question.
+///
+/// # Arguments
+/// * `conn` - A mutable reference to an async PostgreSQL connection.
+/// * `storage` - Where to store the file.
+/// * `question_id` - The unique identifier of the question.
+/// * `file_name` - The name the file was uploaded with.
+/// * `content_type` - The content type the client declared, if any.
+/// * `bytes` - The file's content.
+///
+/// # Returns
+/// A `Result` containing the attached media or why it was refused.
+pub async fn attach_media(
+    conn: &mut AsyncPgConnection,
+    storage: &dyn MediaStorage,
+    question_id: i64,
+    file_name: &str,
+    content_type: Option<&str>,
+    bytes: Vec<u8>,
+) -> Result<QuestionMedia, MediaError> {
+    let (content_type, media_kind, extension) = check_upload(content_type, &bytes)?;
+    if QuestionMedia::fetch_by_question_id(conn, question_id)
+        .await?
+        .len()
+        >= MAX_MEDIA_PER_QUESTION
+    {
+        return Err(MediaError::TooMany);
+    }
+    let storage_key = format!(
+        "questions/{}/{}.{}",
+        question_id,
+        uuid::Uuid::new_v4(),
+        extension
+    );
+    let size_bytes = bytes.len() as i64;
+    storage.put(&storage_key, content_type, bytes).await?;
+
+    let new_media = NewQuestionMedia {
+        question_id,
+        media_kind,
+        content_type: content_type.to_string(),
+        file_name: file_name.to_string(),
+        size_bytes,
+        storage_key: storage_key.clone(),
+    };
+    match QuestionMedia::create(conn, new_media).await {
+        Ok(media) => Ok(media),
+        Err(e) => {
+            // Do not leave an orphaned file behind
+            if let Err(cleanup) = storage.delete(&storage_key).await {
+                tracing::warn!("Failed to remove {}: {}", storage_key, cleanup);
+            }
+            Err(e.into())
+        }
+    }
+}
+
+/// Detach a media file from its question and remove it from storage.
+///
+/// # Arguments
+/// * `conn` - A mutable reference to an async PostgreSQL connection.
+/// * `storage` - Where the file is stored.
+/// * `media_id` - The unique identifier of the media file.
+///
+/// # Returns
+/// A `Result` containing the detached media or an error.
+pub async fn detach_media(
+    conn: &mut AsyncPgConnection,
+    storage: &dyn MediaStorage,
+    media_id: i64,
+) -> Result<QuestionMedia, MediaError> {
+    let media = QuestionMedia::delete(conn, media_id).await?;
+    storage.delete(&media.storage_key).await?;
+    Ok(media)
+}
@@ -0,0 +1,209 @@
+// src/services/metrics.rs
+//! Counters behind `/metrics`, written out in the Prometheus text format.
+
+use crate::db::pool::DBPool;
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Upper bounds, in seconds, of the GraphQL latency histogram's buckets.
+pub const LATENCY_BUCKETS: [f64; 11] = [
+    0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+];
+
+/// Operation names are chosen by clients, so only this many get their own
+/// series; the rest are counted under `other`.
+pub const MAX_OPERATIONS: usize = 100;
+
+/// Label for requests that do not name their operation.
+pub const ANONYMOUS_OPERATION: &str = "anonymous";
+
+/// Label for operations past `MAX_OPERATIONS`, or with unusable names.
+pub const OTHER_OPERATION: &str = "other";
+
+/// Games with activity in this many minutes count as active.
+pub const ACTIVE_GAME_MINUTES: i64 = 60;
+
+#[derive(Clone, Debug, Default)]
+struct OperationStats {
+    ok: u64,
+    errors: u64,
+    /// Requests per bucket of `LATENCY_BUCKETS`; not cumulative.
+    buckets: [u64; LATENCY_BUCKETS.len()],
+    seconds: f64,
+}
+
+/// Request counts and latencies per GraphQL operation. Clones share the
+/// same counters.
+#[derive(Clone, Debug, Default)]
+pub struct Metrics {
+    operations: Arc<Mutex<HashMap<String, OperationStats>>>,
+}
+
+/// GraphQL names only: letters, digits and underscores, not starting with a
+/// digit. Anything else would need escaping in a label.
+fn is_graphql_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c == '_' || c.is_ascii_alphabetic())
+        && chars.all(|c| c == '_' || c.is_ascii_alphanumeric())
+}
+
+/// Start a metric with its `# HELP` and `# TYPE` lines.
+fn describe(out: &mut String, name: &str, kind: &str, help: &str) {
+    let _ = writeln!(out, "# HELP {} {}", name, help);
+    let _ = writeln!(out, "# TYPE {} {}", name, kind);
+}
+
+impl Metrics {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Count a finished GraphQL request.
+    ///
+    /// # Arguments
+    /// * `operation` - The request's operation name, if it gave one.
+    /// * `elapsed` - How long the request took to execute.
+    /// * `ok` - Whether it finished without errors.
+    pub fn observe(&self, operation: Option<&str>, elapsed: Duration, ok: bool) {
+        let mut operations = self.operations.lock().unwrap();
+        let label = match operation {
+            None => ANONYMOUS_OPERATION,
+            Some(name) if !is_graphql_name(name) => OTHER_OPERATION,
+            Some(name) if operations.contains_key(name) => name,
+            Some(_) if operations.len() >= MAX_OPERATIONS => OTHER_OPERATION,
+            Some(name) => name,
+        };
+        let stats = operations.entry(label.to_string()).or_default();
+
+        let seconds = elapsed.as_secs_f64();
+        if ok {
+            stats.ok += 1;
+        } else {
+            stats.errors += 1;
+        }
+        stats.seconds += seconds;
+        if let Some(bucket) = LATENCY_BUCKETS.iter().position(|&le| seconds <= le) {
+            stats.buckets[bucket] += 1;
+        }
+    }
+
+    /// Write every metric out for Prometheus to scrape.
+    ///
+    /// # Arguments
+    /// * `pool` - The pool whose connections to report on.
+    /// * `active_games` - How many games are being played, if it could be
+    ///   counted.
+    ///
+    /// # Returns
+    /// The metrics in the Prometheus text exposition format.
+    pub fn render(&self, pool: &DBPool, active_games: Option<i64>) -> String {
+        let mut out = String::new();
+        let mut operations: Vec<(String, OperationStats)> = self
+            .operations
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(name, stats)| (name.clone(), stats.clone()))
+            .collect();
+        operations.sort_by(|a, b| a.0.cmp(&b.0));
+
+        // Writing to a String cannot fail, so results are ignored
+        describe(
+            &mut out,
+            "jeopardy_graphql_requests_total",
+            "counter",
+            "GraphQL requests by operation and outcome.",
+        );
+        for (name, stats) in &operations {
+            for (outcome, count) in [("ok", stats.ok), ("error", stats.errors)] {
+                let _ = writeln!(
+                    out,
+                    "jeopardy_graphql_requests_total{{operation=\"{}\",outcome=\"{}\"}} {}",
+                    name, outcome, count
+                );
+            }
+        }
+
+        describe(
+            &mut out,
+            "jeopardy_graphql_request_duration_seconds",
+            "histogram",
+            "Time spent executing GraphQL requests.",
+        );
+        for (name, stats) in &operations {
+            let mut cumulative = 0;
+            for (le, count) in LATENCY_BUCKETS.iter().zip(stats.buckets) {
+                cumulative += count;
+                let _ = writeln!(
+                    out,
+                    "jeopardy_graphql_request_duration_seconds_bucket{{operation=\"{}\",le=\"{}\"}} {}",
+                    name, le, cumulative
+                );
+            }
+            let total = stats.ok + stats.errors;
+            let _ = writeln!(
+                out,
+                "jeopardy_graphql_request_duration_seconds_bucket{{operation=\"{}\",le=\"+Inf\"}} {}",
+                name, total
+            );
+            let _ = writeln!(
+                out,
+                "jeopardy_graphql_request_duration_seconds_sum{{operation=\"{}\"}} {}",
+                name, stats.seconds
+            );
+            let _ = writeln!(
+                out,
+                "jeopardy_graphql_request_duration_seconds_count{{operation=\"{}\"}} {}",
+                name, total
+            );
+        }
+
+        let status = pool.status();
+        describe(
+            &mut out,
+            "jeopardy_db_pool_max_connections",
+            "gauge",
+            "Most connections the pool will open.",
+        );
+        let _ = writeln!(out, "jeopardy_db_pool_max_connections {}", status.max_size);
+        describe(
+            &mut out,
+            "jeopardy_db_pool_connections",
+            "gauge",
+            "Open connections, idle or checked out.",
+        );
+        let _ = writeln!(
+            out,
+            "jeopardy_db_pool_connections{{state=\"idle\"}} {}",
+            status.available
+        );
+        let _ = writeln!(
+            out,
+            "jeopardy_db_pool_connections{{state=\"in_use\"}} {}",
+            status.size.saturating_sub(status.available)
+        );
+        describe(
+            &mut out,
+            "jeopardy_db_pool_waiting",
+            "gauge",
+            "Requests waiting for a connection.",
+        );
+        let _ = writeln!(out, "jeopardy_db_pool_waiting {}", status.waiting);
+
+        // Left out rather than reported as zero when the count failed
+        if let Some(active_games) = active_games {
+            describe(
+                &mut out,
+                "jeopardy_active_games",
+                "gauge",
+                "Unarchived games with recent activity.",
+            );
+            let _ = writeln!(out, "jeopardy_active_games {}", active_games);
+        }
+        out
+    }
+}
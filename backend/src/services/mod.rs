@@ -0,0 +1,19 @@
+// Service modules
+pub mod admin;
+pub mod answer_matching;
+pub mod archive_import;
+pub mod board_access;
+pub mod board_control;
+pub mod board_generator;
+pub mod board_validation;
+pub mod gallery;
+pub mod game_replay;
+pub mod game_timers;
+pub mod health;
+pub mod live_games;
+pub mod localization;
+pub mod media;
+pub mod metrics;
+pub mod rich_text;
+pub mod team_play;
+pub mod typed_responses;
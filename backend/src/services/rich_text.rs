@@ -0,0 +1,229 @@
+// src/services/rich_text.rs
+//! The Markdown subset clues and answers are written in.
+//!
+//! Only inline formatting is supported:
+//!
+//! * `*emphasis*` or `_emphasis_`, and `**strong**` or `__strong__`
+//! * `` `code` ``
+//! * `$math$`, rendered as a `<span class="math">` for the client to typeset
+//! * line breaks, kept as `<br>`
+//! * a backslash before `\`, `*`, `_`, `` ` `` or `$` to write it literally
+//!
+//! Anything else, HTML included, is escaped and shown as plain text, so the
+//! rendered HTML only ever contains the tags above. Formatting never spans
+//! lines. Rendering never fails: markers that are never closed are shown as
+//! they were typed, and `markdown_problems` reports them so authors can be
+//! told when text is saved.
+
+/// Tidy text before it is stored: line endings become `\n` and control
+/// characters other than newlines and tabs are dropped.
+///
+/// # Arguments
+/// * `text` - The text as the client sent it.
+///
+/// # Returns
+/// The text to store.
+pub fn sanitize_markdown(text: &str) -> String {
+    text.replace("\r\n", "\n")
+        .replace('\r', "\n")
+        .chars()
+        .filter(|c| !c.is_control() || *c == '\n' || *c == '\t')
+        .collect()
+}
+
+/// Escape plain text so it reads the same once rendered, for text that did
+/// not come from an author writing Markdown.
+///
+/// # Arguments
+/// * `text` - Plain text.
+///
+/// # Returns
+/// Markdown that renders as `text`.
+pub fn escape_markdown(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(c, '\\' | '*' | '_' | '`' | '$') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// Render text to HTML.
+///
+/// # Arguments
+/// * `text` - The stored text.
+///
+/// # Returns
+/// An HTML fragment that is safe to insert into a page.
+pub fn render_markdown(text: &str) -> String {
+    render(text).0
+}
+
+/// Find formatting mistakes in text, such as emphasis that is never closed.
+///
+/// # Arguments
+/// * `text` - The text to check.
+///
+/// # Returns
+/// A description of each mistake, or nothing if the text is well formed.
+pub fn markdown_problems(text: &str) -> Vec<String> {
+    render(text).1
+}
+
+fn render(text: &str) -> (String, Vec<String>) {
+    let mut problems = Vec::new();
+    let lines: Vec<String> = text
+        .split('\n')
+        .map(|line| render_line(line, &mut problems))
+        .collect();
+    problems.dedup();
+    (lines.join("<br>"), problems)
+}
+
+/// Emphasis that has been opened but not yet closed.
+struct OpenMarker {
+    marker: &'static str,
+    /// Where in the output the marker was written, to swap for a tag later.
+    at: usize,
+}
+
+fn render_line(line: &str, problems: &mut Vec<String>) -> String {
+    let chars: Vec<char> = line.chars().collect();
+    let mut out: Vec<String> = Vec::new();
+    let mut open: Vec<OpenMarker> = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        match chars[i] {
+            '\\' if chars
+                .get(i + 1)
+                .is_some_and(|c| matches!(c, '\\' | '*' | '_' | '`' | '$')) =>
+            {
+                out.push(chars[i + 1].to_string());
+                i += 2;
+            }
+            '`' => match find(&chars, i + 1, |j| chars[j] == '`') {
+                Some(end) => {
+                    out.push(format!("<code>{}</code>", escape(&chars[i + 1..end])));
+                    i = end + 1;
+                }
+                None => {
+                    problems.push("has an unclosed `".to_string());
+                    out.push("`".to_string());
+                    i += 1;
+                }
+            },
+            '$' => {
+                // Keep prices like "$5 and $10" as plain text
+                let opens = chars.get(i + 1).is_some_and(|c| !c.is_whitespace());
+                let close = find(&chars, i + 2, |j| {
+                    chars[j] == '$'
+                        && !chars[j - 1].is_whitespace()
+                        && !chars.get(j + 1).is_some_and(|c| c.is_ascii_digit())
+                });
+                match close {
+                    Some(end) if opens => {
+                        out.push(format!(
+                            "<span class=\"math\">{}</span>",
+                            escape(&chars[i + 1..end])
+                        ));
+                        i = end + 1;
+                    }
+                    _ => {
+                        out.push("$".to_string());
+                        i += 1;
+                    }
+                }
+            }
+            c @ ('*' | '_') => {
+                let start = i;
+                while i < chars.len() && chars[i] == c {
+                    i += 1;
+                }
+                emphasis(&chars, start, i, &mut out, &mut open);
+            }
+            c => {
+                out.push(escape(&[c]));
+                i += 1;
+            }
+        }
+    }
+
+    for marker in open {
+        problems.push(format!("has an unclosed {}", marker.marker));
+    }
+    out.concat()
+}
+
+/// Handle a run of `*` or `_` from `start` to `end`, closing the innermost
+/// open emphasis or opening new emphasis where the run allows it.
+fn emphasis(
+    chars: &[char],
+    start: usize,
+    end: usize,
+    out: &mut Vec<String>,
+    open: &mut Vec<OpenMarker>,
+) {
+    let c = chars[start];
+    let before = start.checked_sub(1).map(|j| chars[j]);
+    let after = chars.get(end).copied();
+    let can_close = before.is_some_and(|b| !b.is_whitespace());
+    let mut can_open = after.is_some_and(|a| !a.is_whitespace());
+    // snake_case words are not emphasis
+    if c == '_'
+        && before.is_some_and(char::is_alphanumeric)
+        && after.is_some_and(char::is_alphanumeric)
+    {
+        can_open = false;
+    }
+    let (strong, em) = if c == '*' { ("**", "*") } else { ("__", "_") };
+
+    let mut remaining = end - start;
+    while remaining > 0 {
+        let innermost = open.last().map(|m| m.marker);
+        if can_close && innermost == Some(strong) && remaining >= 2 {
+            let opener = open.pop().unwrap();
+            out[opener.at] = "<strong>".to_string();
+            out.push("</strong>".to_string());
+            remaining -= 2;
+        } else if can_close && innermost == Some(em) {
+            let opener = open.pop().unwrap();
+            out[opener.at] = "<em>".to_string();
+            out.push("</em>".to_string());
+            remaining -= 1;
+        } else if can_open {
+            let marker = if remaining >= 2 { strong } else { em };
+            open.push(OpenMarker {
+                marker,
+                at: out.len(),
+            });
+            out.push(marker.to_string());
+            remaining -= marker.len();
+        } else {
+            out.push(c.to_string().repeat(remaining));
+            remaining = 0;
+        }
+    }
+}
+
+/// The first index from `from` on that satisfies `is_end`.
+fn find(chars: &[char], from: usize, is_end: impl Fn(usize) -> bool) -> Option<usize> {
+    (from..chars.len()).find(|&j| is_end(j))
+}
+
+fn escape(chars: &[char]) -> String {
+    let mut escaped = String::with_capacity(chars.len());
+    for c in chars {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            c => escaped.push(*c),
+        }
+    }
+    escaped
+}
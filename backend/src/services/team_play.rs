@@ -0,0 +1,129 @@
+// src/services/team_play.rs
+//! Team play: buzzing in, judging and per-member attribution.
+//!
+//! Teams buzz in on a game question in order. The host then judges each
+//! buzzing team's answer: a correct answer adds the question's points to the
+//! team and closes the question, an incorrect one takes them away. Every
+//! ruling is credited to a team member so individual stats can be shown
+//! alongside the team scores.
+
+use crate::models::team::{Team, TeamMember};
+use crate::models::team_answer::{NewTeamAnswer, TeamAnswer};
+use async_graphql::SimpleObject;
+use diesel_async::scoped_futures::ScopedFutureExt;
+use diesel_async::{AsyncConnection, AsyncPgConnection};
+use std::collections::HashMap;
+
+/// The outcome of judging a team's answer.
+#[derive(SimpleObject, Clone, Debug)]
+pub struct TeamRuling {
+    pub answer: TeamAnswer,
+    /// The team with its updated score.
+    pub team: Team,
+}
+
+/// A member's answering record over one game.
+#[derive(SimpleObject, Clone, Debug)]
+pub struct MemberStats {
+    pub member_id: i64,
+    pub team_id: i64,
+    pub member_name: String,
+    pub correct_answers: i32,
+    pub incorrect_answers: i32,
+    /// Net points the member won for their team.
+    pub points: i32,
+}
+
+/// Whether a question can still be buzzed on and judged, i.e. no team has
+/// answered it correctly yet.
+///
+/// # Arguments
+/// * `conn` - A mutable reference to an async PostgreSQL connection.
+/// * `game_question_id` - The unique identifier of the game question.
+///
+/// # Returns
+/// A `Result` containing whether the question is open or a Diesel error.
+pub async fn question_is_open(
+    conn: &mut AsyncPgConnection,
+    game_question_id: i64,
+) -> Result<bool, diesel::result::Error> {
+    let answers = TeamAnswer::fetch_by_game_question_id(conn, game_question_id).await?;
+    Ok(!answers.iter().any(|a| a.correct))
+}
+
+/// Record a ruling and apply its points to the team's score.
+///
+/// # Arguments
+/// * `conn` - A mutable reference to an async PostgreSQL connection.
+/// * `new_answer` - The ruling, with the points to award already signed.
+///
+/// # Returns
+/// A `Result` containing the ruling and updated team, or a unique violation
+/// if the team was already judged on this question.
+pub async fn judge_team_answer(
+    conn: &mut AsyncPgConnection,
+    new_answer: NewTeamAnswer,
+) -> Result<TeamRuling, diesel::result::Error> {
+    conn.transaction::<_, diesel::result::Error, _>(|conn| {
+        async move {
+            let answer = TeamAnswer::create(conn, new_answer).await?;
+            let team = Team::adjust_score(conn, answer.team_id, answer.points_awarded).await?;
+            Ok(TeamRuling { answer, team })
+        }
+        .scope_boxed()
+    })
+    .await
+}
+
+/// Tally each member's rulings over a game. Members without any ruling are
+/// included with zero counts.
+///
+/// # Arguments
+/// * `conn` - A mutable reference to an async PostgreSQL connection.
+/// * `game_id` - The unique identifier of the game.
+///
+/// # Returns
+/// A `Result` containing the stats in member order or a Diesel error.
+pub async fn member_stats(
+    conn: &mut AsyncPgConnection,
+    game_id: i64,
+) -> Result<Vec<MemberStats>, diesel::result::Error> {
+    let team_ids: Vec<i64> = Team::fetch_by_game_id(conn, game_id)
+        .await?
+        .into_iter()
+        .map(|t| t.id)
+        .collect();
+    let answers = TeamAnswer::fetch_by_team_ids(conn, &team_ids).await?;
+
+    let mut stats: Vec<MemberStats> = TeamMember::fetch_by_game_id(conn, game_id)
+        .await?
+        .into_iter()
+        .map(|m| MemberStats {
+            member_id: m.id,
+            team_id: m.team_id,
+            member_name: m.member_name,
+            correct_answers: 0,
+            incorrect_answers: 0,
+            points: 0,
+        })
+        .collect();
+    let index: HashMap<i64, usize> = stats
+        .iter()
+        .enumerate()
+        .map(|(i, s)| (s.member_id, i))
+        .collect();
+
+    for answer in answers {
+        let Some(&i) = answer.member_id.and_then(|id| index.get(&id)) else {
+            continue;
+        };
+        let entry = &mut stats[i];
+        if answer.correct {
+            entry.correct_answers += 1;
+        } else {
+            entry.incorrect_answers += 1;
+        }
+        entry.points += answer.points_awarded;
+    }
+    Ok(stats)
+}
@@ -0,0 +1,162 @@
+// src/services/typed_responses.rs
+//! Typed responses from remote contestants.
+//!
+//! The host opens a game question for a limited time. While it is open each
+//! player may submit one typed response. The host then reviews the responses,
+//! optionally ranked by the answer matcher, and judges them once each; judging
+//! a response adds or removes the question's points from the player's score.
+
+use crate::models::game::Game;
+use crate::models::game_event::{GameEvent, GameEventData, GameEventType};
+use crate::models::game_question::GameQuestion;
+use crate::models::game_response::GameResponse;
+use crate::models::player::Player;
+use crate::services::answer_matching::{match_answer, AnswerMatch};
+use crate::services::board_control::{hand_control, BoardControl};
+use async_graphql::SimpleObject;
+use chrono::{DateTime, Utc};
+use diesel_async::scoped_futures::ScopedFutureExt;
+use diesel_async::{AsyncConnection, AsyncPgConnection};
+use std::collections::HashMap;
+
+/// How long a question stays open when the host does not say.
+pub const DEFAULT_RESPONSE_SECONDS: i32 = 30;
+pub const MIN_RESPONSE_SECONDS: i32 = 5;
+pub const MAX_RESPONSE_SECONDS: i32 = 300;
+
+/// A response as shown to the host.
+#[derive(SimpleObject, Clone, Debug)]
+pub struct RankedResponse {
+    pub response: GameResponse,
+    pub player_name: String,
+    /// How closely the response matches the question's accepted answers.
+    pub answer_match: AnswerMatch,
+}
+
+/// The outcome of judging a response.
+#[derive(SimpleObject, Clone, Debug)]
+pub struct JudgedResponse {
+    pub response: GameResponse,
+    /// The player with their updated score.
+    pub player: Player,
+    /// The board control a correct response earned, to publish once committed.
+    #[graphql(skip)]
+    pub control: Option<BoardControl>,
+}
+
+/// Whether `game` has `game_question_id` open and its deadline has not
+/// passed at `now`.
+pub fn accepts_responses(game: &Game, game_question_id: i64, now: DateTime<Utc>) -> bool {
+    game.open_question_id == Some(game_question_id)
+        && game
+            .response_deadline
+            .is_some_and(|deadline| now <= deadline)
+}
+
+/// Fetch the responses to a game question, each compared with its answer.
+///
+/// # Arguments
+/// * `conn` - A mutable reference to an async PostgreSQL connection.
+/// * `game_question` - The game question that was answered.
+/// * `ranked` - Order by match confidence, best first, instead of by arrival.
+///
+/// # Returns
+/// A `Result` containing the responses or a Diesel error.
+pub async fn fetch_responses(
+    conn: &mut AsyncPgConnection,
+    game_question: &GameQuestion,
+    ranked: bool,
+) -> Result<Vec<RankedResponse>, diesel::result::Error> {
+    let names: HashMap<i64, String> = Player::fetch_by_game_id(conn, game_question.game_id)
+        .await?
+        .into_iter()
+        .map(|p| (p.id, p.player_name))
+        .collect();
+
+    let mut responses: Vec<RankedResponse> =
+        GameResponse::fetch_by_game_question_id(conn, game_question.id)
+            .await?
+            .into_iter()
+            .map(|response| RankedResponse {
+                player_name: names.get(&response.player_id).cloned().unwrap_or_default(),
+                answer_match: match_answer(
+                    &response.response,
+                    &game_question.answer,
+                    &game_question.alternate_answers,
+                ),
+                response,
+            })
+            .collect();
+
+    if ranked {
+        // Stable, so equally good responses keep their arrival order
+        responses.sort_by(|a, b| {
+            b.answer_match
+                .confidence
+                .total_cmp(&a.answer_match.confidence)
+        });
+    }
+    Ok(responses)
+}
+
+/// Judge a response, apply its points to the player's score, hand the
+/// player control of the board if it was correct and log the ruling. The response is locked while it is judged, so it is only ever
+/// judged once.
+///
+/// # Arguments
+/// * `conn` - A mutable reference to an async PostgreSQL connection.
+/// * `response_id` - The unique identifier of the response to judge.
+/// * `game_question` - The game question the response answers.
+/// * `correct` - Whether the response is accepted.
+///
+/// # Returns
+/// A `Result` containing the judged response and updated player, `None` if
+/// the response was already judged, or a Diesel error.
+pub async fn judge_response(
+    conn: &mut AsyncPgConnection,
+    response_id: i64,
+    game_question: &GameQuestion,
+    correct: bool,
+) -> Result<Option<JudgedResponse>, diesel::result::Error> {
+    let points = game_question.points;
+    let points_awarded = if correct { points } else { -points };
+    let (game_id, game_question_id) = (game_question.game_id, game_question.id);
+    conn.transaction::<_, diesel::result::Error, _>(|conn| {
+        async move {
+            let response = GameResponse::find_for_update(conn, response_id).await?;
+            if response.judged_at.is_some() {
+                return Ok(None);
+            }
+            let response = GameResponse::judge(conn, response.id, correct, points_awarded).await?;
+            let player = Player::adjust_score(conn, response.player_id, points_awarded).await?;
+            GameEvent::record(
+                conn,
+                game_id,
+                GameEventType::Judged,
+                GameEventData {
+                    game_question_id: Some(game_question_id),
+                    player_id: Some(player.id),
+                    response_id: Some(response.id),
+                    correct: Some(correct),
+                    points: Some(points_awarded),
+                    score: Some(player.score),
+                    ..Default::default()
+                },
+            )
+            .await?;
+            // The last correct responder picks the next cell
+            let control = if correct {
+                hand_control(conn, game_id, Some(player.id), None).await?
+            } else {
+                None
+            };
+            Ok(Some(JudgedResponse {
+                response,
+                player,
+                control,
+            }))
+        }
+        .scope_boxed()
+    })
+    .await
+}
@@ -0,0 +1,109 @@
+// backend/src/storage/local.rs
+//! Media stored on the local filesystem.
+//!
+//! Files are written under a root directory and served by the backend itself
+//! at `{base_url}/{key}`. Links carry an expiry time and an HMAC signature of
+//! the key and expiry, which `verify` checks before a file is served.
+
+use super::{constant_time_eq, hex, hmac_sha256, is_valid_key, MediaStorage, StorageError};
+use async_trait::async_trait;
+use chrono::Utc;
+use std::env;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// A `MediaStorage` backed by a local directory.
+#[derive(Clone)]
+pub struct LocalStorage {
+    root: PathBuf,
+    base_url: String,
+    secret: Vec<u8>,
+}
+
+impl LocalStorage {
+    /// Store media under `root`, linked at `base_url` and signed with `secret`.
+    pub fn new(root: impl Into<PathBuf>, base_url: &str, secret: &[u8]) -> Self {
+        LocalStorage {
+            root: root.into(),
+            base_url: base_url.trim_end_matches('/').to_string(),
+            secret: secret.to_vec(),
+        }
+    }
+
+    /// Configure local storage from `MEDIA_DIR`, `MEDIA_BASE_URL` (default
+    /// `/media`) and `MEDIA_SIGNING_SECRET`. `MEDIA_DIR` has no default, so a
+    /// deploy on an ephemeral disk cannot fall back to local storage unnoticed.
+    pub fn from_env() -> Result<Self, Box<dyn std::error::Error>> {
+        let root = env::var("MEDIA_DIR").map_err(|err| {
+            tracing::error!("MEDIA_DIR must be set for local media storage: {}", err);
+            err
+        })?;
+        let base_url = env::var("MEDIA_BASE_URL").unwrap_or_else(|_| "/media".to_string());
+        let secret = env::var("MEDIA_SIGNING_SECRET").map_err(|err| {
+            tracing::error!("MEDIA_SIGNING_SECRET must be set: {}", err);
+            err
+        })?;
+        Ok(Self::new(root, &base_url, secret.as_bytes()))
+    }
+
+    /// Where `key` is stored on disk, if it is a valid key.
+    pub fn path_for(&self, key: &str) -> Option<PathBuf> {
+        is_valid_key(key).then(|| self.root.join(Path::new(key)))
+    }
+
+    fn signature(&self, key: &str, expires: i64) -> String {
+        hex(&hmac_sha256(
+            &self.secret,
+            format!("{}:{}", key, expires).as_bytes(),
+        ))
+    }
+
+    /// Whether a link to `key` with this expiry and signature is genuine and
+    /// has not expired at `now` (in Unix seconds).
+    pub fn verify(&self, key: &str, expires: i64, signature: &str, now: i64) -> bool {
+        now <= expires && constant_time_eq(&self.signature(key, expires), signature)
+    }
+}
+
+#[async_trait]
+impl MediaStorage for LocalStorage {
+    async fn put(
+        &self,
+        key: &str,
+        _content_type: &str,
+        bytes: Vec<u8>,
+    ) -> Result<(), StorageError> {
+        let path = self
+            .path_for(key)
+            .ok_or_else(|| StorageError::InvalidKey(key.to_string()))?;
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        tokio::fs::write(path, bytes).await?;
+        Ok(())
+    }
+
+    async fn delete(&self, key: &str) -> Result<(), StorageError> {
+        let path = self
+            .path_for(key)
+            .ok_or_else(|| StorageError::InvalidKey(key.to_string()))?;
+        match tokio::fs::remove_file(path).await {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.into()),
+            _ => Ok(()),
+        }
+    }
+
+    fn signed_url(&self, key: &str, expires_in: Duration) -> Result<String, StorageError> {
+        if !is_valid_key(key) {
+            return Err(StorageError::InvalidKey(key.to_string()));
+        }
+        let expires = Utc::now().timestamp() + expires_in.as_secs() as i64;
+        Ok(format!(
+            "{}/{}?expires={}&signature={}",
+            self.base_url,
+            key,
+            expires,
+            self.signature(key, expires)
+        ))
+    }
+}
@@ -0,0 +1,105 @@
+// backend/src/storage/mod.rs
+//! Storage for uploaded media.
+//!
+//! Media files live outside the database, behind the `MediaStorage` trait.
+//! `LocalStorage` keeps them on the local filesystem for development and
+//! tests; `S3Storage` talks to any S3-compatible object store. Clients never
+//! get permanent links: they get signed URLs that expire.
+
+pub mod local;
+pub mod s3;
+
+pub use local::LocalStorage;
+pub use s3::S3Storage;
+
+use async_trait::async_trait;
+use hmac::{Hmac, Mac};
+use sha2::{Digest, Sha256};
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// The storage backend shared by the GraphQL resolvers.
+pub type MediaStore = Arc<dyn MediaStorage>;
+
+/// An error from a storage backend.
+#[derive(Debug)]
+pub enum StorageError {
+    /// The key is not one the backend will store.
+    InvalidKey(String),
+    Io(std::io::Error),
+    /// The object store refused or failed a request.
+    Request(String),
+}
+
+impl fmt::Display for StorageError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StorageError::InvalidKey(key) => write!(f, "Invalid storage key: {}", key),
+            StorageError::Io(e) => write!(f, "Storage I/O error: {}", e),
+            StorageError::Request(message) => write!(f, "Storage request failed: {}", message),
+        }
+    }
+}
+
+impl std::error::Error for StorageError {}
+
+impl From<std::io::Error> for StorageError {
+    fn from(e: std::io::Error) -> Self {
+        StorageError::Io(e)
+    }
+}
+
+/// Somewhere media files can be stored and linked to.
+#[async_trait]
+pub trait MediaStorage: Send + Sync {
+    /// Store `bytes` under `key`, replacing anything stored there before.
+    async fn put(&self, key: &str, content_type: &str, bytes: Vec<u8>) -> Result<(), StorageError>;
+
+    /// Remove whatever is stored under `key`. Removing a missing key is fine.
+    async fn delete(&self, key: &str) -> Result<(), StorageError>;
+
+    /// A URL that serves `key` for `expires_in` from now.
+    fn signed_url(&self, key: &str, expires_in: Duration) -> Result<String, StorageError>;
+}
+
+/// Whether `key` is a relative path of plain segments, so that it cannot
+/// escape the storage root or bucket.
+pub fn is_valid_key(key: &str) -> bool {
+    !key.is_empty()
+        && key.split('/').all(|segment| {
+            !segment.is_empty()
+                && segment != "."
+                && segment != ".."
+                && segment
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+        })
+}
+
+/// HMAC-SHA256 of `message` under `key`.
+pub(crate) fn hmac_sha256(key: &[u8], message: &[u8]) -> Vec<u8> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts keys of any length");
+    mac.update(message);
+    mac.finalize().into_bytes().to_vec()
+}
+
+/// Lowercase hex SHA-256 of `bytes`.
+pub(crate) fn sha256_hex(bytes: &[u8]) -> String {
+    hex(&Sha256::digest(bytes))
+}
+
+/// Lowercase hex encoding of `bytes`.
+pub(crate) fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Compare two strings in time that depends only on their lengths, so that
+/// signatures cannot be guessed byte by byte.
+pub(crate) fn constant_time_eq(a: &str, b: &str) -> bool {
+    a.len() == b.len()
+        && a.bytes()
+            .zip(b.bytes())
+            .fold(0u8, |diff, (x, y)| diff | (x ^ y))
+            == 0
+}
@@ -0,0 +1,233 @@
+// backend/src/storage/s3.rs
+//! Media stored in an S3-compatible object store.
+//!
+//! Requests are signed with AWS Signature Version 4, which AWS S3 and the
+//! common self-hosted stores (MinIO, Ceph, R2, ...) all accept. Links are
+//! presigned GET URLs, so the bucket itself can stay private.
+
+use super::{hex, hmac_sha256, is_valid_key, sha256_hex, MediaStorage, StorageError};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use reqwest::Url;
+use std::env;
+use std::time::Duration;
+
+/// Presigned URLs may not last longer than a week.
+const MAX_PRESIGN_SECONDS: u64 = 7 * 24 * 60 * 60;
+
+/// A `MediaStorage` backed by an S3 bucket.
+#[derive(Clone)]
+pub struct S3Storage {
+    endpoint: Url,
+    bucket: String,
+    region: String,
+    access_key_id: String,
+    secret_access_key: String,
+    /// Address the bucket as `{endpoint}/{bucket}` instead of as a
+    /// `{bucket}.{endpoint}` subdomain. Most self-hosted stores need this.
+    path_style: bool,
+    client: reqwest::Client,
+}
+
+impl S3Storage {
+    pub fn new(
+        endpoint: &str,
+        bucket: &str,
+        region: &str,
+        access_key_id: &str,
+        secret_access_key: &str,
+        path_style: bool,
+    ) -> Result<Self, StorageError> {
+        let endpoint = Url::parse(endpoint)
+            .map_err(|e| StorageError::Request(format!("Invalid endpoint: {}", e)))?;
+        Ok(S3Storage {
+            endpoint,
+            bucket: bucket.to_string(),
+            region: region.to_string(),
+            access_key_id: access_key_id.to_string(),
+            secret_access_key: secret_access_key.to_string(),
+            path_style,
+            client: reqwest::Client::new(),
+        })
+    }
+
+    /// Configure S3 storage from `S3_ENDPOINT`, `S3_BUCKET`, `S3_REGION`,
+    /// `S3_ACCESS_KEY_ID`, `S3_SECRET_ACCESS_KEY` and `S3_PATH_STYLE`.
+    pub fn from_env() -> Result<Self, Box<dyn std::error::Error>> {
+        let var = |name: &str| {
+            env::var(name).map_err(|err| {
+                tracing::error!("{} must be set: {}", name, err);
+                err
+            })
+        };
+        let path_style = env::var("S3_PATH_STYLE").is_ok_and(|v| v == "true" || v == "1");
+        Ok(Self::new(
+            &var("S3_ENDPOINT")?,
+            &var("S3_BUCKET")?,
+            &env::var("S3_REGION").unwrap_or_else(|_| "us-east-1".to_string()),
+            &var("S3_ACCESS_KEY_ID")?,
+            &var("S3_SECRET_ACCESS_KEY")?,
+            path_style,
+        )?)
+    }
+
+    /// The host and path of the object at `key`.
+    fn locate(&self, key: &str) -> Result<(String, String), StorageError> {
+        if !is_valid_key(key) {
+            return Err(StorageError::InvalidKey(key.to_string()));
+        }
+        let host = match self.endpoint.port() {
+            Some(port) => format!("{}:{}", self.endpoint.host_str().unwrap_or_default(), port),
+            None => self.endpoint.host_str().unwrap_or_default().to_string(),
+        };
+        let key = uri_encode(key, false);
+        Ok(if self.path_style {
+            (host, format!("/{}/{}", uri_encode(&self.bucket, true), key))
+        } else {
+            (format!("{}.{}", self.bucket, host), format!("/{}", key))
+        })
+    }
+
+    fn scope(&self, now: DateTime<Utc>) -> String {
+        format!("{}/{}/s3/aws4_request", now.format("%Y%m%d"), self.region)
+    }
+
+    /// Sign a canonical request made at `now`.
+    fn signature(&self, canonical_request: &str, now: DateTime<Utc>) -> String {
+        let string_to_sign = format!(
+            "AWS4-HMAC-SHA256\n{}\n{}\n{}",
+            now.format("%Y%m%dT%H%M%SZ"),
+            self.scope(now),
+            sha256_hex(canonical_request.as_bytes())
+        );
+        let date_key = hmac_sha256(
+            format!("AWS4{}", self.secret_access_key).as_bytes(),
+            now.format("%Y%m%d").to_string().as_bytes(),
+        );
+        let region_key = hmac_sha256(&date_key, self.region.as_bytes());
+        let service_key = hmac_sha256(&region_key, b"s3");
+        let signing_key = hmac_sha256(&service_key, b"aws4_request");
+        hex(&hmac_sha256(&signing_key, string_to_sign.as_bytes()))
+    }
+
+    /// A presigned GET URL for `key`, valid for `expires_in` from `now`.
+    pub fn presign_get(
+        &self,
+        key: &str,
+        expires_in: Duration,
+        now: DateTime<Utc>,
+    ) -> Result<String, StorageError> {
+        let (host, path) = self.locate(key)?;
+        let credential = format!("{}/{}", self.access_key_id, self.scope(now));
+        let query = format!(
+            "X-Amz-Algorithm=AWS4-HMAC-SHA256&X-Amz-Credential={}&X-Amz-Date={}&X-Amz-Expires={}&X-Amz-SignedHeaders=host",
+            uri_encode(&credential, true),
+            now.format("%Y%m%dT%H%M%SZ"),
+            expires_in.as_secs().clamp(1, MAX_PRESIGN_SECONDS),
+        );
+        let canonical_request = format!(
+            "GET\n{}\n{}\nhost:{}\n\nhost\nUNSIGNED-PAYLOAD",
+            path, query, host
+        );
+        Ok(format!(
+            "{}://{}{}?{}&X-Amz-Signature={}",
+            self.endpoint.scheme(),
+            host,
+            path,
+            query,
+            self.signature(&canonical_request, now)
+        ))
+    }
+
+    /// Send a request signed in its `Authorization` header.
+    async fn send(
+        &self,
+        method: reqwest::Method,
+        key: &str,
+        content_type: Option<&str>,
+        body: Vec<u8>,
+    ) -> Result<reqwest::Response, StorageError> {
+        let (host, path) = self.locate(key)?;
+        let now = Utc::now();
+        let amz_date = now.format("%Y%m%dT%H%M%SZ").to_string();
+        let payload_hash = sha256_hex(&body);
+        let canonical_request = format!(
+            "{}\n{}\n\nhost:{}\nx-amz-content-sha256:{}\nx-amz-date:{}\n\nhost;x-amz-content-sha256;x-amz-date\n{}",
+            method, path, host, payload_hash, amz_date, payload_hash
+        );
+        let authorization = format!(
+            "AWS4-HMAC-SHA256 Credential={}/{}, SignedHeaders=host;x-amz-content-sha256;x-amz-date, Signature={}",
+            self.access_key_id,
+            self.scope(now),
+            self.signature(&canonical_request, now)
+        );
+
+        let mut request = self
+            .client
+            .request(
+                method,
+                format!("{}://{}{}", self.endpoint.scheme(), host, path),
+            )
+            .header("x-amz-content-sha256", payload_hash)
+            .header("x-amz-date", amz_date)
+            .header("authorization", authorization);
+        if let Some(content_type) = content_type {
+            request = request.header("content-type", content_type);
+        }
+        let response = request
+            .body(body)
+            .send()
+            .await
+            .map_err(|e| StorageError::Request(e.to_string()))?;
+        if response.status().is_success() || response.status() == reqwest::StatusCode::NOT_FOUND {
+            Ok(response)
+        } else {
+            Err(StorageError::Request(format!(
+                "{} returned {}",
+                path,
+                response.status()
+            )))
+        }
+    }
+}
+
+/// Percent-encode everything but unreserved characters, and `/` too when
+/// `encode_slash` is set, as SigV4 canonical requests require.
+fn uri_encode(value: &str, encode_slash: bool) -> String {
+    value
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (b as char).to_string()
+            }
+            b'/' if !encode_slash => "/".to_string(),
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+#[async_trait]
+impl MediaStorage for S3Storage {
+    async fn put(&self, key: &str, content_type: &str, bytes: Vec<u8>) -> Result<(), StorageError> {
+        let response = self
+            .send(reqwest::Method::PUT, key, Some(content_type), bytes)
+            .await?;
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Err(StorageError::Request(format!(
+                "Bucket {} does not exist",
+                self.bucket
+            )));
+        }
+        Ok(())
+    }
+
+    async fn delete(&self, key: &str) -> Result<(), StorageError> {
+        self.send(reqwest::Method::DELETE, key, None, Vec::new())
+            .await?;
+        Ok(())
+    }
+
+    fn signed_url(&self, key: &str, expires_in: Duration) -> Result<String, StorageError> {
+        self.presign_get(key, expires_in, Utc::now())
+    }
+}
@@ -0,0 +1,85 @@
+// tests/answer_matching.rs
+
+use backend::services::answer_matching::{
+    edit_distance, match_answer, normalize_answer, MatchVerdict, ACCEPT_THRESHOLD, REJECT_THRESHOLD,
+};
+
+#[test]
+fn normalize_strips_question_phrasing_articles_and_punctuation() {
+    assert_eq!(
+        normalize_answer("What is the Eiffel Tower?"),
+        "eiffel tower"
+    );
+    assert_eq!(normalize_answer("Who's Mozart"), "mozart");
+    assert_eq!(normalize_answer("AT&T"), "at and t");
+    assert_eq!(normalize_answer("  An   Apple, a Day "), "apple day");
+    // Too short to drop the question words
+    assert_eq!(normalize_answer("What is"), "what is");
+    assert_eq!(normalize_answer("?!"), "");
+}
+
+#[test]
+fn edit_distance_counts_characters() {
+    assert_eq!(edit_distance("kitten", "sitting"), 3);
+    assert_eq!(edit_distance("", "abc"), 3);
+    assert_eq!(edit_distance("abc", ""), 3);
+    assert_eq!(edit_distance("café", "cafe"), 1);
+    assert_eq!(edit_distance("same", "same"), 0);
+}
+
+#[test]
+fn exact_and_close_responses_are_correct() {
+    let exact = match_answer("what is paris", "Paris", &[]);
+    assert_eq!(exact.confidence, 1.0);
+    assert_eq!(exact.verdict, MatchVerdict::Correct);
+    assert_eq!(exact.matched_answer.as_deref(), Some("Paris"));
+    assert_eq!(exact.normalized_response, "paris");
+
+    let misspelt = match_answer("Missisippi", "Mississippi", &[]);
+    assert_eq!(misspelt.verdict, MatchVerdict::Correct);
+}
+
+#[test]
+fn middling_responses_are_left_to_the_host() {
+    let result = match_answer("Franklin", "Frankfurt", &[]);
+    assert_eq!(result.verdict, MatchVerdict::Unsure);
+    assert!(result.confidence > REJECT_THRESHOLD && result.confidence < ACCEPT_THRESHOLD);
+}
+
+#[test]
+fn numbers_must_match_exactly() {
+    let result = match_answer("1985", "1984", &[]);
+    assert_eq!(result.confidence, 0.0);
+    assert_eq!(result.verdict, MatchVerdict::Incorrect);
+    assert_eq!(result.matched_answer, None);
+}
+
+#[test]
+fn the_closest_alternate_answer_wins() {
+    let result = match_answer("everest", "Mt. Everest", &["Everest".to_string()]);
+    assert_eq!(result.confidence, 1.0);
+    assert_eq!(result.matched_answer.as_deref(), Some("Everest"));
+}
+
+#[test]
+fn parenthesized_parts_are_optional() {
+    let result = match_answer("Adams", "(John) Adams", &[]);
+    assert_eq!(result.verdict, MatchVerdict::Correct);
+    assert_eq!(result.matched_answer.as_deref(), Some("(John) Adams"));
+
+    // Unbalanced parentheses drop everything after an unclosed one
+    let unclosed = match_answer("Adams", "Adams (John", &[]);
+    assert_eq!(unclosed.verdict, MatchVerdict::Correct);
+    // A stray closing one is only punctuation
+    let stray = match_answer("Adams John", "Adams) John", &[]);
+    assert_eq!(stray.confidence, 1.0);
+}
+
+#[test]
+fn blank_responses_match_nothing() {
+    let result = match_answer("???", "Paris", &[]);
+    assert_eq!(result.confidence, 0.0);
+    assert_eq!(result.verdict, MatchVerdict::Incorrect);
+    assert_eq!(result.matched_answer, None);
+    assert_eq!(result.normalized_response, "");
+}
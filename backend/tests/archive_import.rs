@@ -0,0 +1,212 @@
+// tests/archive_import.rs
+
+use backend::services::archive_import::{
+    layout_round, parse_html, parse_tsv, ArchiveRound, ArchivedClue,
+};
+use backend::services::rich_text::{escape_markdown, render_markdown};
+
+fn clue(
+    round: ArchiveRound,
+    category: &str,
+    value: Option<i32>,
+    daily_double: bool,
+) -> ArchivedClue {
+    ArchivedClue {
+        round,
+        category: category.to_string(),
+        clue: format!("A clue in {}", category),
+        response: "A response".to_string(),
+        value,
+        daily_double,
+        position: None,
+    }
+}
+
+fn categories(names: &[&str]) -> Vec<String> {
+    names.iter().map(|name| name.to_string()).collect()
+}
+
+#[test]
+fn tsv_reads_dollar_values() {
+    let tsv = "round\tclue_value\tcategory\tanswer\tquestion\n\
+               1\t$1,000\tA\tFirst\tOne\n\
+               1\t$0\tA\tSecond\tTwo\n\
+               1\t\tA\tThird\tThree\n";
+    let values: Vec<Option<i32>> = parse_tsv(tsv).unwrap()[0]
+        .clues
+        .iter()
+        .map(|clue| clue.value)
+        .collect();
+    assert_eq!(values, vec![Some(1000), None, None]);
+}
+
+#[test]
+fn html_decodes_entities_and_leaves_stray_ampersands() {
+    let page = "<div id=\"jeopardy_round\"><td class=\"category_name\">TOM &amp; JERRY</td>\
+                <td class=\"clue\"><td class=\"clue_value\">$200</td>\
+                <td id=\"clue_J_1_1\" class=\"clue_text\">&quot;Cat&quot; &#39;&#x41;&#X42;<br />a & b &bogus; &amp</td>\
+                <td id=\"clue_J_1_1_r\"><em class=\"correct_response\">Tom&nbsp;<i>cat</i></em></td></td></div>";
+    let episode = parse_html(page).unwrap();
+    assert_eq!(
+        episode.categories[&ArchiveRound::Jeopardy],
+        vec!["TOM & JERRY"]
+    );
+    let clue = &episode.clues[0];
+    assert_eq!(clue.clue, "\"Cat\" 'AB a & b &bogus; &amp");
+    assert_eq!(clue.response, "Tom cat");
+}
+
+#[test]
+fn tsv_keeps_prices_in_clues_as_text() {
+    let tsv = "round\tclue_value\tcategory\tanswer\tquestion\n\
+               1\t$200\tSHOPPING\tIt cost $5 and $10 at the *dime* store\tWoolworth's\n";
+    let episodes = parse_tsv(tsv).unwrap();
+    let clue = &episodes[0].clues[0];
+    assert_eq!(clue.clue, "It cost $5 and $10 at the *dime* store");
+    assert_eq!(clue.value, Some(200));
+    // Imported text is escaped, so it renders as it aired
+    assert_eq!(
+        render_markdown(&escape_markdown(&clue.clue)),
+        "It cost $5 and $10 at the *dime* store"
+    );
+}
+
+#[test]
+fn tsv_reads_quoted_fields_and_skips_unrevealed_clues() {
+    let tsv = "\"round\"\t\"value\"\t\"daily_double\"\t\"category\"\t\"clue\"\t\"response\"\t\"show_number\"\n\
+               J\t$400\tyes\t\"QUOTES\"\t\"He said \"\"hello\"\"\"\tAdele\t8000\n\
+               Double Jeopardy!\t$800\t\tQUOTES\t\t\t8000\n";
+    let episodes = parse_tsv(tsv).unwrap();
+    assert_eq!(episodes.len(), 1);
+    assert_eq!(episodes[0].show_number, Some(8000));
+    assert_eq!(episodes[0].clues.len(), 1);
+    let clue = &episodes[0].clues[0];
+    assert_eq!(clue.clue, "He said \"hello\"");
+    assert!(clue.daily_double);
+    assert_eq!(clue.round, ArchiveRound::Jeopardy);
+}
+
+#[test]
+fn tsv_reports_bad_files() {
+    let message = |text: &str| parse_tsv(text).unwrap_err().to_string();
+    assert_eq!(message(" \n"), "the file is empty");
+    assert_eq!(
+        message("round\tvalue\tcategory\tclue\n1\t$200\tX\tY"),
+        "missing a question column"
+    );
+    assert_eq!(
+        message("round\tvalue\tcategory\tclue\tresponse\n\n4\t$200\tX\tY\tZ"),
+        "line 3: unknown round \"4\""
+    );
+}
+
+#[test]
+fn html_reads_six_categories_and_daily_doubles() {
+    let names = ["ONE", "TWO", "THREE", "FOUR", "FIVE", "SIX"];
+    let mut page = String::from(
+        "<title>J! Archive - Show #9000, aired 2024-01-02</title><div id=\"jeopardy_round\"><table>",
+    );
+    for name in names {
+        page.push_str(&format!("<td class=\"category_name\">{}</td>", name));
+    }
+    for (col, value) in [
+        (1, "clue_value\">$200"),
+        (6, "clue_value_daily_double\">DD: $3,000"),
+    ] {
+        page.push_str(&format!(
+            "<td class=\"clue\"><td class=\"{}</td>\
+             <td id=\"clue_J_{}_2\" class=\"clue_text\">Costs $5 &amp; $10</td>\
+             <td id=\"clue_J_{}_2_r\"><em class=\"correct_response\">Answer {}</em></td></td>",
+            value, col, col, col
+        ));
+    }
+    page.push_str("</table></div>");
+
+    let episode = parse_html(&page).unwrap();
+    assert_eq!(episode.show_number, Some(9000));
+    assert_eq!(episode.air_date.as_deref(), Some("2024-01-02"));
+    assert_eq!(
+        episode.categories[&ArchiveRound::Jeopardy],
+        categories(&names)
+    );
+    assert_eq!(episode.clues.len(), 2);
+    assert_eq!(episode.clues[0].clue, "Costs $5 & $10");
+    assert_eq!(episode.clues[0].value, Some(200));
+    assert_eq!(episode.clues[0].position, Some((0, 1)));
+    let daily_double = &episode.clues[1];
+    assert_eq!(daily_double.category, "SIX");
+    assert!(daily_double.daily_double);
+    assert_eq!(daily_double.value, None);
+    assert_eq!(daily_double.response, "Answer 6");
+
+    assert!(parse_html("<html><body>Not a game</body></html>").is_err());
+}
+
+#[test]
+fn layout_places_clues_by_value_and_fills_gaps() {
+    use ArchiveRound::Jeopardy;
+    let clues = [
+        clue(Jeopardy, "A", Some(200), false),
+        clue(Jeopardy, "A", Some(400), false),
+        // A Daily Double's wager says nothing about its row
+        clue(Jeopardy, "A", None, true),
+        clue(Jeopardy, "A", Some(800), false),
+        clue(Jeopardy, "A", Some(1000), false),
+        clue(Jeopardy, "B", Some(200), false),
+        clue(Jeopardy, "B", Some(200), false),
+        clue(Jeopardy, "B", Some(300), false),
+        // The show's sixth category has no column
+        clue(Jeopardy, "F", Some(200), false),
+    ];
+    let refs: Vec<&ArchivedClue> = clues.iter().collect();
+    let cells = layout_round(
+        Jeopardy,
+        &categories(&["A", "B", "C", "D", "E", "F"]),
+        &refs,
+    );
+    assert_eq!(
+        cells,
+        vec![
+            Some((0, 0)),
+            Some((1, 0)),
+            Some((2, 0)),
+            Some((3, 0)),
+            Some((4, 0)),
+            Some((0, 1)),
+            Some((1, 1)),
+            Some((2, 1)),
+            None,
+        ]
+    );
+}
+
+#[test]
+fn layout_prefers_page_positions_and_default_units() {
+    use ArchiveRound::{DoubleJeopardy, FinalJeopardy};
+    let mut placed = clue(DoubleJeopardy, "A", Some(400), false);
+    placed.position = Some((1, 3));
+    let unvalued = clue(DoubleJeopardy, "A", None, true);
+    let valued = clue(DoubleJeopardy, "A", Some(1200), false);
+    let clues = [placed, unvalued, valued];
+    let refs: Vec<&ArchivedClue> = clues.iter().collect();
+    let cells = layout_round(DoubleJeopardy, &categories(&["A", "B"]), &refs);
+    assert_eq!(cells, vec![Some((3, 1)), Some((0, 0)), Some((2, 0))]);
+
+    // Without regular values, rows follow the round's usual amounts
+    let wagered = [clue(DoubleJeopardy, "A", Some(800), true)];
+    let refs: Vec<&ArchivedClue> = wagered.iter().collect();
+    assert_eq!(
+        layout_round(DoubleJeopardy, &categories(&["A"]), &refs),
+        vec![Some((1, 0))]
+    );
+
+    let finals = [
+        clue(FinalJeopardy, "WORDS", None, false),
+        clue(FinalJeopardy, "WORDS", None, false),
+    ];
+    let refs: Vec<&ArchivedClue> = finals.iter().collect();
+    assert_eq!(
+        layout_round(FinalJeopardy, &categories(&["WORDS"]), &refs),
+        vec![Some((0, 0)), None]
+    );
+}
@@ -0,0 +1,171 @@
+// tests/board_generator.rs
+
+use backend::models::question::Question;
+use backend::services::board_generator::{
+    normalize_tag, normalize_tags, plan_board, Candidate, GeneratorError, GeneratorOptions,
+    PlannedCell, QuestionSource, DEFAULT_BASE_POINTS, DEFAULT_DAILY_DOUBLES, DEFAULT_RECENT_GAMES,
+    MAX_DAILY_DOUBLES,
+};
+use chrono::Utc;
+
+fn candidate(id: i64, labels: &[&str], difficulty: Option<i32>, recent: bool) -> Candidate {
+    Candidate {
+        question: Question {
+            id,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            user_id: 1,
+            question: format!("Clue {}", id),
+            answer: format!("Answer {}", id),
+            archived_at: None,
+            alternate_answers: Vec::new(),
+            locale: "en".to_string(),
+            tags: Vec::new(),
+            difficulty,
+            copied_from_id: None,
+        },
+        labels: labels.iter().map(|label| label.to_string()).collect(),
+        recent,
+    }
+}
+
+fn options(categories: &[&str], rows: i32, cols: i32, seed: u64) -> GeneratorOptions {
+    GeneratorOptions {
+        title: "Generated".to_string(),
+        categories: categories.iter().map(|c| c.to_string()).collect(),
+        rows,
+        cols,
+        min_difficulty: None,
+        max_difficulty: None,
+        source: QuestionSource::Mine,
+        base_points: DEFAULT_BASE_POINTS,
+        daily_doubles: DEFAULT_DAILY_DOUBLES,
+        recent_games: DEFAULT_RECENT_GAMES,
+        seed,
+    }
+}
+
+/// Two labels with five questions each, rated 5 down to 1 by id.
+fn library() -> Vec<Candidate> {
+    (1..=10)
+        .map(|id| {
+            let label = if id <= 5 { "science" } else { "history" };
+            candidate(id, &[label], Some(6 - ((id - 1) % 5 + 1) as i32), false)
+        })
+        .collect()
+}
+
+#[test]
+fn normalizes_tags() {
+    assert_eq!(normalize_tag("  Old \t Science "), "old science");
+    assert_eq!(
+        normalize_tags(&["B".to_string(), " a".to_string(), "b ".to_string()]),
+        vec!["b", "a"]
+    );
+}
+
+#[test]
+fn the_same_seed_gives_the_same_plan() {
+    let candidates = library();
+    let options = options(&["Science", "History"], 5, 2, 7);
+    assert_eq!(
+        plan_board(&candidates, &options).unwrap(),
+        plan_board(&candidates, &options).unwrap()
+    );
+}
+
+#[test]
+fn columns_run_from_easiest_to_hardest() {
+    let plan = plan_board(&library(), &options(&[" Science ", "history"], 5, 2, 3)).unwrap();
+    assert_eq!(plan.categories, vec!["Science", "history"]);
+    assert_eq!(plan.cells.len(), 10);
+    let science: Vec<i64> = plan
+        .cells
+        .iter()
+        .filter(|c| c.grid_col == 0)
+        .map(|c| c.question_id)
+        .collect();
+    assert_eq!(science, vec![5, 4, 3, 2, 1]);
+    for cell in &plan.cells {
+        assert_eq!(cell.points, DEFAULT_BASE_POINTS * (cell.grid_row + 1));
+    }
+}
+
+#[test]
+fn daily_doubles_stay_off_the_first_row() {
+    for seed in 0..20 {
+        let mut options = options(&["science", "history"], 5, 2, seed);
+        options.daily_doubles = MAX_DAILY_DOUBLES;
+        let plan = plan_board(&library(), &options).unwrap();
+        let daily_doubles: Vec<&PlannedCell> =
+            plan.cells.iter().filter(|c| c.daily_double).collect();
+        assert_eq!(daily_doubles.len(), MAX_DAILY_DOUBLES as usize);
+        assert!(daily_doubles.iter().all(|c| c.grid_row > 0));
+    }
+
+    // With a single row there is nowhere else to put them
+    let plan = plan_board(&library(), &options(&["science"], 1, 1, 0)).unwrap();
+    assert!(plan.cells[0].daily_double);
+}
+
+#[test]
+fn a_question_fills_only_one_cell() {
+    let candidates: Vec<Candidate> = (1..=4)
+        .map(|id| candidate(id, &["science", "history"], None, false))
+        .collect();
+    let plan = plan_board(&candidates, &options(&["science", "history"], 2, 2, 1)).unwrap();
+    let mut ids: Vec<i64> = plan.cells.iter().map(|c| c.question_id).collect();
+    ids.sort();
+    assert_eq!(ids, vec![1, 2, 3, 4]);
+
+    match plan_board(&candidates, &options(&["science", "history"], 3, 2, 1)) {
+        Err(GeneratorError::NotEnoughQuestions {
+            category,
+            found,
+            needed,
+        }) => assert_eq!((category.as_str(), found, needed), ("history", 1, 3)),
+        other => panic!("expected not enough questions, got {:?}", other),
+    }
+}
+
+#[test]
+fn recent_questions_are_used_only_when_needed() {
+    let candidates: Vec<Candidate> = (1..=5)
+        .map(|id| candidate(id, &["science"], None, id > 3))
+        .collect();
+    for seed in 0..10 {
+        let plan = plan_board(&candidates, &options(&["science"], 3, 1, seed)).unwrap();
+        assert!(plan.cells.iter().all(|c| c.question_id <= 3));
+    }
+    let plan = plan_board(&candidates, &options(&["science"], 5, 1, 0)).unwrap();
+    assert_eq!(plan.cells.len(), 5);
+}
+
+#[test]
+fn unrated_questions_count_as_middling() {
+    let candidates = vec![
+        candidate(1, &["science"], Some(5), false),
+        candidate(2, &["science"], None, false),
+        candidate(3, &["science"], Some(1), false),
+    ];
+    let plan = plan_board(&candidates, &options(&["science"], 3, 1, 0)).unwrap();
+    let ids: Vec<i64> = plan.cells.iter().map(|c| c.question_id).collect();
+    assert_eq!(ids, vec![3, 2, 1]);
+}
+
+#[test]
+fn picks_categories_from_labels_with_enough_questions() {
+    let mut candidates = library();
+    candidates.push(candidate(11, &["art"], None, false));
+    let plan = plan_board(&candidates, &options(&[], 5, 2, 9)).unwrap();
+    let mut categories = plan.categories.clone();
+    categories.sort();
+    assert_eq!(categories, vec!["history", "science"]);
+
+    match plan_board(&candidates, &options(&[], 5, 3, 9)) {
+        Err(GeneratorError::NotEnoughCategories { found, needed }) => {
+            assert_eq!((found, needed), (2, 3))
+        }
+        other => panic!("expected not enough categories, got {:?}", other),
+    }
+}
@@ -1,5 +1,6 @@
 // tests/common/factories.rs
 
+use backend::auth::firebase_auth::{AuthenticatedUser, FirebaseClaims};
 use backend::models::game_board::{GameBoard, NewGameBoard, NewGameBoardBuilder};
 use backend::models::game_board_question_mapping::{
     GameBoardQuestionMapping, NewGameBoardQuestionMapping, NewGameBoardQuestionMappingBuilder,
@@ -117,6 +118,9 @@ pub async fn create_test_question(
         builder.user_id(user_id);
         builder.question(overrides.question);
         builder.answer(overrides.answer);
+        builder.alternate_answers(overrides.alternate_answers);
+        builder.tags(overrides.tags);
+        builder.difficulty(overrides.difficulty);
     } else {
         builder.user_id(user_id);
         builder.question("defaultquestion".to_string());
@@ -190,3 +194,25 @@ pub async fn create_test_game_board_question_mapping(
         .await
         .expect("Failed to create test board question")
 }
+
+/// Builds an `AuthenticatedUser` for a test user, as if their Firebase token was verified.
+///
+/// # Parameters
+/// - `user`: The backend user to authenticate as.
+///
+/// # Returns
+/// - `AuthenticatedUser`: Attach to a GraphQL request with `Request::data`.
+///
+/// # Examples
+///
+/// ```rust
+/// let request = Request::new(mutation).data(authenticate_test_user(&user));
+/// ```
+pub fn authenticate_test_user(user: &User) -> AuthenticatedUser {
+    AuthenticatedUser::new(FirebaseClaims {
+        sub: user.firebase_uid.clone(),
+        iss: "https://securetoken.google.com/jeopardy-b4166".to_string(),
+        aud: "jeopardy-b4166".to_string(),
+        exp: usize::MAX,
+    })
+}
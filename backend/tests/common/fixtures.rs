@@ -68,6 +68,9 @@ pub async fn comprehensive_fixture(
                 user_id: user.id,
                 question: question_text,
                 answer: answer_text,
+                alternate_answers: Vec::new(),
+                tags: Vec::new(),
+                difficulty: None,
             }),
         )
         .await;
@@ -148,3 +151,76 @@ pub async fn board_with_questions_fixture(
 
     (game_board.clone(), mappings, associated_questions)
 }
+
+/// Creates a test fixture with a complete, playable 5 x 5 game board.
+///
+/// Every cell holds its own question worth `100 * (row + 1)` points, and the
+/// cell at `[0, 0]` is the board's only Daily Double.
+///
+/// # Parameters
+/// - `conn`: A mutable reference to the database connection.
+/// - `username`: The username for the test user.
+///
+/// # Returns
+/// - `(User, GameBoard, Vec<GameBoardQuestionMapping>)`: The created user, their board, and its mappings.
+///
+/// # Panics
+/// - If fixture creation fails.
+///
+/// # Examples
+///
+/// ```rust
+/// let (user, board, mappings) = full_board_fixture(conn, "TestUser").await;
+/// ```
+pub async fn full_board_fixture(
+    conn: &mut AsyncPgConnection,
+    username: &str,
+) -> (User, GameBoard, Vec<GameBoardQuestionMapping>) {
+    let firebase_uid = format!("{}_uid", username);
+    let user = create_test_user(conn, Some(username.to_string()), Some(firebase_uid)).await;
+    let game_board = create_test_game_board(
+        conn,
+        user.id,
+        Some(NewGameBoard {
+            user_id: user.id,
+            title: format!("{}'s Full Board", username),
+        }),
+    )
+    .await;
+
+    let mut mappings = Vec::new();
+    for grid_col in 0..5 {
+        for grid_row in 0..5 {
+            let question = create_test_question(
+                conn,
+                user.id,
+                Some(NewQuestion {
+                    user_id: user.id,
+                    question: format!("Clue {}-{}", grid_row, grid_col),
+                    answer: format!("Answer {}-{}", grid_row, grid_col),
+                    alternate_answers: Vec::new(),
+                    tags: Vec::new(),
+                    difficulty: None,
+                }),
+            )
+            .await;
+            let mapping = create_test_game_board_question_mapping(
+                conn,
+                game_board.id,
+                question.id,
+                Some(NewGameBoardQuestionMapping {
+                    board_id: game_board.id,
+                    question_id: question.id,
+                    daily_double: grid_row == 0 && grid_col == 0,
+                    points: 100 * (grid_row + 1),
+                    grid_row,
+                    grid_col,
+                }),
+            )
+            .await;
+            mappings.push(mapping);
+        }
+    }
+
+    (user, game_board, mappings)
+}
@@ -0,0 +1,33 @@
+// tests/localization.rs
+
+use backend::services::localization::{normalize_locale, requested_locale};
+
+#[test]
+fn normalizes_well_formed_locales() {
+    assert_eq!(normalize_locale("en").as_deref(), Some("en"));
+    assert_eq!(normalize_locale("FIL").as_deref(), Some("fil"));
+    assert_eq!(normalize_locale("  es_mx ").as_deref(), Some("es-MX"));
+    assert_eq!(normalize_locale("pt-br").as_deref(), Some("pt-BR"));
+    assert_eq!(normalize_locale("es-419").as_deref(), Some("es-419"));
+}
+
+#[test]
+fn rejects_malformed_locales() {
+    for malformed in [
+        "", " ", "e", "engl", "e1", "én", "en-", "-US", "en-U", "en-USA", "en-1", "en-4190",
+        "en-US-x", "en--US", "en US", "zh-Hans",
+    ] {
+        assert_eq!(normalize_locale(malformed), None, "{:?}", malformed);
+    }
+}
+
+#[test]
+fn requested_locale_reports_the_locale_field() {
+    assert_eq!(requested_locale(None).unwrap(), None);
+    assert_eq!(
+        requested_locale(Some("de_at")).unwrap().as_deref(),
+        Some("de-AT")
+    );
+    let errors = requested_locale(Some("klingon")).unwrap_err();
+    assert_eq!(errors.violations()[0].field, "locale");
+}
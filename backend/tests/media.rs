@@ -0,0 +1,111 @@
+// tests/media.rs
+
+use backend::models::question_media::MediaKind;
+use backend::services::media::{
+    check_upload, content_type_for_key, sniff_content_type, MediaError, MAX_IMAGE_BYTES,
+};
+
+const PNG: &[u8] = b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR";
+const WEBP: &[u8] = b"RIFF\x24\0\0\0WEBPVP8 ";
+const WAV: &[u8] = b"RIFF\x24\0\0\0WAVEfmt ";
+const MP4: &[u8] = b"\0\0\0\x18ftypmp42";
+
+#[test]
+fn sniffs_supported_formats() {
+    assert_eq!(sniff_content_type(PNG), Some("image/png"));
+    assert_eq!(
+        sniff_content_type(&[0xFF, 0xD8, 0xFF, 0xE0]),
+        Some("image/jpeg")
+    );
+    assert_eq!(sniff_content_type(b"GIF89a\x01\0"), Some("image/gif"));
+    assert_eq!(sniff_content_type(WEBP), Some("image/webp"));
+    assert_eq!(sniff_content_type(WAV), Some("audio/wav"));
+    assert_eq!(sniff_content_type(b"ID3\x04\0"), Some("audio/mpeg"));
+    assert_eq!(sniff_content_type(&[0xFF, 0xFB, 0x90]), Some("audio/mpeg"));
+    assert_eq!(sniff_content_type(b"OggS\0\x02"), Some("audio/ogg"));
+    assert_eq!(sniff_content_type(MP4), Some("video/mp4"));
+    assert_eq!(
+        sniff_content_type(&[0x1A, 0x45, 0xDF, 0xA3]),
+        Some("video/webm")
+    );
+}
+
+#[test]
+fn does_not_sniff_unknown_or_truncated_files() {
+    assert_eq!(sniff_content_type(b""), None);
+    assert_eq!(
+        sniff_content_type(b"<svg xmlns=\"http://www.w3.org/2000/svg\">"),
+        None
+    );
+    // A RIFF header too short to hold its form
+    assert_eq!(sniff_content_type(b"RIFF\x24\0\0\0WE"), None);
+    assert_eq!(sniff_content_type(&[0xFF]), None);
+}
+
+#[test]
+fn accepts_files_matching_their_declared_type() {
+    let (content_type, kind, extension) =
+        check_upload(Some("Image/PNG; charset=binary"), PNG).unwrap();
+    assert_eq!((content_type, extension), ("image/png", "png"));
+    assert_eq!(kind, MediaKind::Image);
+}
+
+#[test]
+fn takes_the_type_from_the_content_when_none_is_declared() {
+    for declared in [None, Some(""), Some("application/octet-stream")] {
+        let (content_type, kind, extension) = check_upload(declared, MP4).unwrap();
+        assert_eq!((content_type, extension), ("video/mp4", "mp4"));
+        assert_eq!(kind, MediaKind::Video);
+    }
+}
+
+#[test]
+fn refuses_a_spoofed_content_type() {
+    // An HTML page passed off as a picture
+    let page = b"<html><script>alert(1)</script></html>";
+    assert!(matches!(
+        check_upload(Some("image/png"), page),
+        Err(MediaError::ContentMismatch(ct)) if ct == "image/png"
+    ));
+    // A real file of another supported type
+    assert!(matches!(
+        check_upload(Some("audio/wav"), WEBP),
+        Err(MediaError::ContentMismatch(ct)) if ct == "audio/wav"
+    ));
+}
+
+#[test]
+fn refuses_unsupported_and_empty_files() {
+    assert!(matches!(
+        check_upload(Some("image/svg+xml"), PNG),
+        Err(MediaError::UnsupportedType(ct)) if ct == "image/svg+xml"
+    ));
+    assert!(matches!(
+        check_upload(None, b"plain text"),
+        Err(MediaError::UnsupportedType(ct)) if ct == "unknown"
+    ));
+    assert!(matches!(
+        check_upload(Some("image/png"), b""),
+        Err(MediaError::Empty)
+    ));
+}
+
+#[test]
+fn refuses_files_over_their_kinds_limit() {
+    let mut large = PNG.to_vec();
+    large.resize(MAX_IMAGE_BYTES + 1, 0);
+    assert!(matches!(
+        check_upload(Some("image/png"), &large),
+        Err(MediaError::TooLarge { limit, .. }) if limit == MAX_IMAGE_BYTES
+    ));
+}
+
+#[test]
+fn maps_stored_keys_back_to_content_types() {
+    assert_eq!(
+        content_type_for_key("questions/1/abc.webm"),
+        Some("video/webm")
+    );
+    assert_eq!(content_type_for_key("questions/1/abc.svg"), None);
+    assert_eq!(content_type_for_key("no-extension"), None);
+}
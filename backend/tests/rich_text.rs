@@ -0,0 +1,93 @@
+// tests/rich_text.rs
+
+use backend::services::rich_text::{
+    escape_markdown, markdown_problems, render_markdown, sanitize_markdown,
+};
+
+#[test]
+fn renders_inline_formatting() {
+    assert_eq!(
+        render_markdown("*a* and **b**, _c_ and __d__"),
+        "<em>a</em> and <strong>b</strong>, <em>c</em> and <strong>d</strong>"
+    );
+    assert_eq!(render_markdown("`x < y`"), "<code>x &lt; y</code>");
+    assert_eq!(
+        render_markdown("$E = mc^2$"),
+        "<span class=\"math\">E = mc^2</span>"
+    );
+    assert_eq!(render_markdown("one\ntwo"), "one<br>two");
+}
+
+#[test]
+fn renders_nested_markers() {
+    assert_eq!(
+        render_markdown("**bold *both* bold**"),
+        "<strong>bold <em>both</em> bold</strong>"
+    );
+    assert_eq!(
+        render_markdown("***both***"),
+        "<strong><em>both</em></strong>"
+    );
+    assert_eq!(
+        render_markdown("*em __strong__ em*"),
+        "<em>em <strong>strong</strong> em</em>"
+    );
+    assert!(markdown_problems("**bold *both* bold**").is_empty());
+}
+
+#[test]
+fn shows_unclosed_markers_as_typed() {
+    assert_eq!(render_markdown("*open"), "*open");
+    assert_eq!(markdown_problems("*open"), vec!["has an unclosed *"]);
+    assert_eq!(render_markdown("**open"), "**open");
+    assert_eq!(markdown_problems("**open"), vec!["has an unclosed **"]);
+    assert_eq!(render_markdown("`open"), "`open");
+    assert_eq!(markdown_problems("`open"), vec!["has an unclosed `"]);
+    // The inner marker is closed, the outer one is not
+    assert_eq!(render_markdown("**a *b* c"), "**a <em>b</em> c");
+    assert_eq!(markdown_problems("**a *b* c"), vec!["has an unclosed **"]);
+}
+
+#[test]
+fn does_not_close_crossed_markers() {
+    let text = "*a **b* c**";
+    assert_eq!(render_markdown(text), "*a <strong>b* c</strong>");
+    assert_eq!(markdown_problems(text), vec!["has an unclosed *"]);
+}
+
+#[test]
+fn formatting_does_not_span_lines() {
+    assert_eq!(render_markdown("*a\nb*"), "*a<br>b*");
+    assert_eq!(markdown_problems("*a\nb*"), vec!["has an unclosed *"]);
+}
+
+#[test]
+fn leaves_plain_uses_of_markers_alone() {
+    assert_eq!(render_markdown("snake_case_name"), "snake_case_name");
+    assert_eq!(render_markdown("2 * 3 * 4"), "2 * 3 * 4");
+    assert_eq!(render_markdown("$5 and $10"), "$5 and $10");
+    assert_eq!(render_markdown("it costs $5"), "it costs $5");
+    assert!(markdown_problems("$5 and $10").is_empty());
+}
+
+#[test]
+fn escapes_html_and_backslashed_markers() {
+    assert_eq!(
+        render_markdown("<script>alert('x')</script> & \"y\""),
+        "&lt;script&gt;alert(&#39;x&#39;)&lt;/script&gt; &amp; &quot;y&quot;"
+    );
+    assert_eq!(render_markdown(r"\*not\* \$x\$"), "*not* $x$");
+    assert_eq!(render_markdown(r"a \n b"), r"a \n b");
+}
+
+#[test]
+fn escaped_text_renders_as_written() {
+    let plain = r"*_`$\ <b>";
+    assert_eq!(render_markdown(&escape_markdown(plain)), "*_`$\\ &lt;b&gt;");
+    assert!(markdown_problems(&escape_markdown(plain)).is_empty());
+}
+
+#[test]
+fn sanitize_normalizes_line_endings_and_drops_controls() {
+    assert_eq!(sanitize_markdown("a\r\nb\rc\u{0}d\te"), "a\nb\ncd\te");
+}
@@ -164,6 +164,12 @@ export type GameBoardQuestionMapping = {
   questionId: Scalars['Int']['output'];
 };
 
+/** A position on the game board grid */
+export type GridCellInput = {
+  col: Scalars['Int']['input'];
+  row: Scalars['Int']['input'];
+};
+
 /**
  * Represents a player in the application.
  *
@@ -219,6 +225,8 @@ export type RootMutation = {
   deletePlayer: Player;
   /** Delete a question by ID */
   deleteQuestion: Scalars['Boolean']['output'];
+  /** Swap the questions in two cells of a gameboard atomically */
+  swapCells: Array<GameBoardQuestionMapping>;
   /** Update gameboard title or categories */
   updateGameBoard: GameBoard;
   /** Update gameboard category at specific index */
@@ -271,6 +279,13 @@ export type RootMutationDeleteQuestionArgs = {
 };
 
 
+export type RootMutationSwapCellsArgs = {
+  boardId: Scalars['Int']['input'];
+  first: GridCellInput;
+  second: GridCellInput;
+};
+
+
 export type RootMutationUpdateGameBoardArgs = {
   input: UpdateGameBoardInput;
 };
@@ -492,6 +507,15 @@ export type UpdateMappingMutationVariables = Exact<{
 
 export type UpdateMappingMutation = { __typename?: 'RootMutation', updateMapping: { __typename?: 'GameBoardQuestionMapping', boardId: number, questionId: number, dailyDouble: boolean, points: number, gridRow: number, gridCol: number } };
 
+export type SwapCellsMutationVariables = Exact<{
+  boardId: Scalars['Int']['input'];
+  first: GridCellInput;
+  second: GridCellInput;
+}>;
+
+
+export type SwapCellsMutation = { __typename?: 'RootMutation', swapCells: Array<{ __typename?: 'GameBoardQuestionMapping', boardId: number, questionId: number, dailyDouble: boolean, points: number, gridRow: number, gridCol: number }> };
+
 export type CreateGameBoardMutationVariables = Exact<{
   input: CreateGameBoardInput;
 }>;
@@ -773,6 +797,46 @@ export function useUpdateMappingMutation(baseOptions?: Apollo.MutationHookOption
 export type UpdateMappingMutationHookResult = ReturnType<typeof useUpdateMappingMutation>;
 export type UpdateMappingMutationResult = Apollo.MutationResult<UpdateMappingMutation>;
 export type UpdateMappingMutationOptions = Apollo.BaseMutationOptions<UpdateMappingMutation, UpdateMappingMutationVariables>;
+export const SwapCellsDocument = gql`
+    mutation SwapCells($boardId: Int!, $first: GridCellInput!, $second: GridCellInput!) {
+  swapCells(boardId: $boardId, first: $first, second: $second) {
+    boardId
+    questionId
+    dailyDouble
+    points
+    gridRow
+    gridCol
+  }
+}
+    `;
+export type SwapCellsMutationFn = Apollo.MutationFunction<SwapCellsMutation, SwapCellsMutationVariables>;
+
+/**
+ * __useSwapCellsMutation__
+ *
+ * To run a mutation, you first call `useSwapCellsMutation` within a React component and pass it any options that fit your needs.
+ * When your component renders, `useSwapCellsMutation` returns a tuple that includes:
+ * - A mutate function that you can call at any time to execute the mutation
+ * - An object with fields that represent the current status of the mutation's execution
+ *
+ * @param baseOptions options that will be passed into the mutation, supported options are listed on: https://www.apollographql.com/docs/react/api/react-hooks/#options-2;
+ *
+ * @example
+ * const [swapCellsMutation, { data, loading, error }] = useSwapCellsMutation({
+ *   variables: {
+ *      boardId: // value for 'boardId'
+ *      first: // value for 'first'
+ *      second: // value for 'second'
+ *   },
+ * });
+ */
+export function useSwapCellsMutation(baseOptions?: Apollo.MutationHookOptions<SwapCellsMutation, SwapCellsMutationVariables>) {
+        const options = {...defaultOptions, ...baseOptions}
+        return Apollo.useMutation<SwapCellsMutation, SwapCellsMutationVariables>(SwapCellsDocument, options);
+      }
+export type SwapCellsMutationHookResult = ReturnType<typeof useSwapCellsMutation>;
+export type SwapCellsMutationResult = Apollo.MutationResult<SwapCellsMutation>;
+export type SwapCellsMutationOptions = Apollo.BaseMutationOptions<SwapCellsMutation, SwapCellsMutationVariables>;
 export const CreateGameBoardDocument = gql`
     mutation CreateGameBoard($input: CreateGameBoardInput!) {
   createGameBoard(input: $input) {
@@ -161,6 +161,12 @@ export type GameBoardQuestionMapping = {
   questionId: Scalars['Int']['output'];
 };
 
+/** A position on the game board grid */
+export type GridCellInput = {
+  col: Scalars['Int']['input'];
+  row: Scalars['Int']['input'];
+};
+
 /**
  * Represents a player in the application.
  *
@@ -216,6 +222,8 @@ export type RootMutation = {
   deletePlayer: Player;
   /** Delete a question by ID */
   deleteQuestion: Scalars['Boolean']['output'];
+  /** Swap the questions in two cells of a gameboard atomically */
+  swapCells: Array<GameBoardQuestionMapping>;
   /** Update gameboard title or categories */
   updateGameBoard: GameBoard;
   /** Update gameboard category at specific index */
@@ -268,6 +276,13 @@ export type RootMutationDeleteQuestionArgs = {
 };
 
 
+export type RootMutationSwapCellsArgs = {
+  boardId: Scalars['Int']['input'];
+  first: GridCellInput;
+  second: GridCellInput;
+};
+
+
 export type RootMutationUpdateGameBoardArgs = {
   input: UpdateGameBoardInput;
 };
@@ -5,6 +5,7 @@ import { useState, ReactElement, useEffect } from "react";
 import {
   useFetchGbQsQuery,
   useUpdateMappingMutation,
+  useSwapCellsMutation,
   FetchGbQsDocument,
 } from "@/__generated__/graphql";
 import {
@@ -48,6 +49,7 @@ const GameBoardGrid: React.FC<GameBoardGridProps> = ({ gameBoard, userId }) => {
     variables: { gameBoardId: gameBoard.id },
   });
   const [updateMapping] = useUpdateMappingMutation();
+  const [swapCells] = useSwapCellsMutation();
 
   // Initialize mapping as state
   const [gameBoardQuestionsMap, setGameBoardQuestionsMap] = useState<
@@ -141,27 +143,13 @@ const GameBoardGrid: React.FC<GameBoardGridProps> = ({ gameBoard, userId }) => {
         // console.log("Swapping questions");
         newMapping[activeKey] = overGBQ;
         newMapping[overKey] = activeGBQ;
-        // Update GBQ in backend
-        const activeGBQInput: UpdateGameBoardMappingInput = {
-          boardId: gameBoard.id,
-          questionId: activeGBQ.question.id,
-          gridRow: overRow,
-          gridCol: overCol,
-          points: 100 * (overRow + 1),
-        };
-        const overGBQInput: UpdateGameBoardMappingInput = {
-          boardId: gameBoard.id,
-          questionId: overGBQ.question.id,
-          gridRow: activeRow,
-          gridCol: activeCol,
-          points: 100 * (activeRow + 1),
-        };
-
-        await updateMapping({
-          variables: { input: activeGBQInput },
-        });
-        await updateMapping({
-          variables: { input: overGBQInput },
+        // Swap both cells in one request so they never collide
+        await swapCells({
+          variables: {
+            boardId: gameBoard.id,
+            first: { row: activeRow, col: activeCol },
+            second: { row: overRow, col: overCol },
+          },
           refetchQueries: [
             {
               query: FetchGbQsDocument,
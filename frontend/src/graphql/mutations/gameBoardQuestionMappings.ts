@@ -25,3 +25,20 @@ export const UPDATE_GBQMAPPING = gql`
     }
   }
 `;
+
+export const SWAP_GBQ_CELLS = gql`
+  mutation SwapCells(
+    $boardId: Int!
+    $first: GridCellInput!
+    $second: GridCellInput!
+  ) {
+    swapCells(boardId: $boardId, first: $first, second: $second) {
+      boardId
+      questionId
+      dailyDouble
+      points
+      gridRow
+      gridCol
+    }
+  }
+`;